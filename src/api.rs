@@ -55,6 +55,7 @@ pub mod playwright;
 
 pub mod accessibility;
 pub mod artifact;
+pub mod artifact_dir;
 pub mod browser;
 pub mod browser_context;
 pub mod browser_type;
@@ -84,23 +85,28 @@ pub use crate::imp::{core::DateTime, utils::*};
 pub use self::playwright::Playwright;
 pub use accessibility::Accessibility;
 pub use api_request::APIRequest;
-pub use api_request_context::{APIRequestContext, NewContextOptions, RequestData, RequestOptions};
+pub use api_request_context::{
+    APIRequestContext, MultipartEntry, NewContextOptions, RequestData, RequestOptions,
+};
 pub use api_response::APIResponse;
 pub use artifact::Artifact;
+pub use artifact_dir::ArtifactDir;
 pub use browser::Browser;
-pub use browser_context::BrowserContext;
-pub use browser_type::BrowserType;
-pub use cdp_session::CDPSession;
+pub use browser_context::{BrowserContext, RouteFromHarOptions};
+#[cfg(feature = "tungstenite")]
+pub use browser_type::ConnectOptions;
+pub use browser_type::{BrowserType, ConnectOverCdpOptions};
+pub use cdp_session::{CDPSession, DownloadBehavior, NetworkConditions};
 pub use console_message::ConsoleMessage;
 pub use dialog::Dialog;
 pub use download::Download;
-pub use element_handle::ElementHandle;
+pub use element_handle::{ElementHandle, ElementScreenshotOptions};
 pub use file_chooser::FileChooser;
 pub use frame::Frame;
-pub use input_device::{Keyboard, Mouse, TouchScreen};
+pub use input_device::{GestureBuilder, Keyboard, Mouse, TouchScreen};
 pub use js_handle::JsHandle;
 pub use locator::{FrameLocator, GetByRoleOptions, Locator};
-pub use page::Page;
+pub use page::{DragOptions, NavigationOptions, Page, Subscription, UrlOrPredicate};
 pub use request::Request;
 pub use response::Response;
 pub use route::Route;
@@ -108,6 +114,7 @@ pub use selectors::Selectors;
 pub use tracing::{
     StartChunkOptions as TracingStartChunkOptions, StartOptions as TracingStartOptions,
     StopChunkOptions as TracingStopChunkOptions, StopOptions as TracingStopOptions, Tracing,
+    TracingGuard,
 };
 pub use video::Video;
 pub use web_error::WebError;
@@ -86,6 +86,14 @@ impl SnapshotBuilder {
         upgrade(&inner)?.accessibility_snapshot(args).await
     }
 
+    /// Same tree as [`SnapshotBuilder::snapshot`] but untyped, e.g. to diff it against a golden file.
+    /// `Value::Null` if there is no accessible node.
+    pub async fn snapshot_json(self) -> ArcResult<Value> {
+        let Self { inner, args } = self;
+        let v = upgrade(&inner)?.accessibility_snapshot_json(args).await?;
+        Ok(v.unwrap_or(Value::Null))
+    }
+
     /// The root DOM element for the snapshot. Defaults to the whole page.
     pub fn try_root(mut self, x: ElementHandle) -> Result<Self, Error> {
         let guid = x.guid()?;
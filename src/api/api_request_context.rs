@@ -25,14 +25,34 @@ impl APIRequestContext {
         Self { inner }
     }
 
+    /// Sends the request. With `retry_delays` set in the options, the request is sent again after each delay while it
+    /// fails to connect, e.g. the connection is refused or reset, or gets a 5xx response. Other errors, such as an
+    /// invalid url or a timeout, are returned right away.
     pub async fn fetch(
         &self,
         url: &str,
         options: Option<RequestOptions>,
     ) -> Result<APIResponse, Arc<Error>> {
-        let args = options.unwrap_or_default().into_fetch_args(url);
-        let payload = upgrade(&self.inner)?.fetch(args).await?;
-        Ok(APIResponse::new(self.clone(), payload))
+        let mut options = options.unwrap_or_default();
+        let mut delays = options.retry_delays.take().unwrap_or_default().into_iter();
+        let inner = upgrade(&self.inner)?;
+        loop {
+            let args = options.clone().into_fetch_args(url)?;
+            let res = inner.fetch(args).await;
+            let retry = match &res {
+                Ok(payload) => payload.status >= 500,
+                Err(e) => is_network_error(e),
+            };
+            match delays.next() {
+                Some(delay) if retry => {
+                    if let Ok(payload) = &res {
+                        let _ = inner.dispose_api_response(&payload.fetch_uid).await;
+                    }
+                    sleep(Duration::from_millis(delay as u64)).await;
+                }
+                _ => return Ok(APIResponse::new(self.clone(), res?)),
+            }
+        }
     }
 
     pub async fn get(
@@ -89,6 +109,17 @@ impl APIRequestContext {
         self.fetch(url, options).await
     }
 
+    /// Sends an `OPTIONS` request, e.g. a CORS preflight. Other methods such as `PURGE` go through
+    /// [`APIRequestContext::fetch`] with [`RequestOptions::method`].
+    pub async fn options(
+        &self,
+        url: &str,
+        mut options: Option<RequestOptions>,
+    ) -> Result<APIResponse, Arc<Error>> {
+        options.get_or_insert_with(RequestOptions::default).method = Some("OPTIONS".into());
+        self.fetch(url, options).await
+    }
+
     pub async fn storage_state(&self) -> Result<String, Arc<Error>> {
         upgrade(&self.inner)?.storage_state().await
     }
@@ -100,6 +131,8 @@ impl APIRequestContext {
 
 #[derive(Clone, Default)]
 pub struct RequestOptions {
+    /// Any HTTP method, not only the ones with a shortcut on [`APIRequestContext`]. It is uppercased before
+    /// sending. Defaults to `GET`.
     pub method: Option<String>,
     pub headers: Option<HashMap<String, String>>,
     pub params: Option<HashMap<String, String>>,
@@ -111,6 +144,9 @@ pub struct RequestOptions {
     pub ignore_https_errors: Option<bool>,
     pub max_redirects: Option<i32>,
     pub max_retries: Option<i32>,
+    /// Delays in milliseconds before each client-side retry, so at most `retry_delays.len() + 1` attempts are
+    /// made. Independent of `max_retries`, which the driver handles.
+    pub retry_delays: Option<Vec<f64>>,
 }
 
 impl RequestOptions {
@@ -128,11 +164,27 @@ impl RequestOptions {
         self
     }
 
+    /// Only one of `data`, `form` and `multipart` can be set, [`APIRequestContext::fetch`] fails with
+    /// [`Error::InvalidParams`] otherwise.
     pub fn data(mut self, data: RequestData) -> Self {
         self.data = Some(data);
         self
     }
 
+    /// Adds a field of an `application/x-www-form-urlencoded` body.
+    pub fn form_field(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.form
+            .get_or_insert_with(HashMap::new)
+            .insert(key.into(), value.into());
+        self
+    }
+
+    /// Adds a part of a `multipart/form-data` body.
+    pub fn multipart_field(mut self, entry: MultipartEntry) -> Self {
+        self.multipart.get_or_insert_with(Vec::new).push(entry);
+        self
+    }
+
     pub fn timeout(mut self, timeout: f64) -> Self {
         self.timeout = Some(timeout);
         self
@@ -148,12 +200,27 @@ impl RequestOptions {
         self
     }
 
-    fn into_fetch_args(self, url: &str) -> FetchArgs {
+    /// e.g. `vec![100., 200., 400.]` for exponential backoff
+    pub fn retry_delays(mut self, delays: Vec<f64>) -> Self {
+        self.retry_delays = Some(delays);
+        self
+    }
+
+    fn into_fetch_args(self, url: &str) -> Result<FetchArgs, Error> {
+        let bodies = [
+            self.data.is_some(),
+            self.form.is_some(),
+            self.multipart.is_some(),
+        ];
+        if bodies.iter().filter(|&&b| b).count() > 1 {
+            let msg = "only one of data, form and multipart can be set on a request";
+            return Err(Error::InvalidParams(msg.into()));
+        }
         let mut args = FetchArgs {
             url: url.to_owned(),
             ..FetchArgs::default()
         };
-        args.method = self.method;
+        args.method = self.method.map(|m| m.to_ascii_uppercase());
         if let Some(headers) = self.headers {
             args.headers = Some(headers.into_iter().map(Header::from).collect());
         }
@@ -192,7 +259,7 @@ impl RequestOptions {
         args.max_redirects = self.max_redirects;
         args.max_retries = self.max_retries;
         args.timeout = self.timeout;
-        args
+        Ok(args)
     }
 }
 
@@ -334,3 +401,65 @@ impl From<NewContextOptions> for NewContextArgs {
         }
     }
 }
+
+/// Whether the driver failed to reach the server, judging by the Node.js error code in the message
+fn is_network_error(e: &Error) -> bool {
+    const CODES: &[&str] = &[
+        "ECONNREFUSED",
+        "ECONNRESET",
+        "EPIPE",
+        "ENOTFOUND",
+        "EAI_AGAIN",
+        "EHOSTUNREACH",
+        "ENETUNREACH",
+        "socket hang up",
+    ];
+    match e {
+        Error::ErrorResponded(m) => CODES.iter().any(|c| m.message.contains(c)),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn network_errors() {
+        let responded = |message: &str| {
+            Error::ErrorResponded(Arc::new(ErrorMessage {
+                name: "Error".into(),
+                message: message.into(),
+                stack: String::new(),
+            }))
+        };
+        let refused = "apiRequestContext.fetch: connect ECONNREFUSED 127.0.0.1:1";
+        assert!(is_network_error(&responded(refused)));
+        assert!(!is_network_error(&responded("Invalid URL")));
+        assert!(!is_network_error(&responded(
+            "apiRequestContext.fetch: Request context disposed."
+        )));
+        assert!(!is_network_error(&Error::Timeout));
+    }
+
+    #[test]
+    fn request_body() {
+        let args = RequestOptions::default()
+            .form_field("a", "1")
+            .into_fetch_args("/")
+            .unwrap();
+        assert_eq!(args.form_data.unwrap().len(), 1);
+        let args = RequestOptions::default()
+            .multipart_field(MultipartEntry::value("a", "1"))
+            .multipart_field(MultipartEntry::file("b", "b.txt", None, b"b".to_vec()))
+            .into_fetch_args("/")
+            .unwrap();
+        assert_eq!(args.multipart_data.unwrap().len(), 2);
+        let err = RequestOptions::default()
+            .form_field("a", "1")
+            .data(RequestData::Text("b".into()))
+            .into_fetch_args("/")
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidParams(_)));
+    }
+}
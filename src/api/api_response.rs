@@ -40,6 +40,37 @@ impl APIResponse {
         self.payload.headers.clone()
     }
 
+    /// Value of the header `name`, matched case-insensitively. Repeated headers are joined with `, `,
+    /// or with `\n` for `set-cookie`.
+    pub fn header_value(&self, name: &str) -> Option<String> {
+        let sep = if name.eq_ignore_ascii_case("set-cookie") {
+            "\n"
+        } else {
+            ", "
+        };
+        let values: Vec<&str> = self
+            .payload
+            .headers
+            .iter()
+            .filter(|h| h.name.eq_ignore_ascii_case(name))
+            .map(|h| h.value.as_str())
+            .collect();
+        if values.is_empty() {
+            None
+        } else {
+            Some(values.join(sep))
+        }
+    }
+
+    pub fn content_type(&self) -> Option<String> {
+        self.header_value("content-type")
+    }
+
+    /// Size of the body as announced by `Content-Length`. `None` for chunked or compressed responses without it.
+    pub fn body_size(&self) -> Option<usize> {
+        self.header_value("content-length")?.trim().parse().ok()
+    }
+
     pub fn ok(&self) -> bool {
         let s = self.payload.status;
         s == 0 || (200..=299).contains(&s)
@@ -70,3 +101,43 @@ impl APIResponse {
             .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(headers: &[(&str, &str)]) -> APIResponse {
+        let payload = APIResponsePayload {
+            fetch_uid: String::new(),
+            url: String::new(),
+            status: 200,
+            status_text: String::new(),
+            headers: headers
+                .iter()
+                .map(|(name, value)| Header {
+                    name: (*name).into(),
+                    value: (*value).into(),
+                })
+                .collect(),
+        };
+        APIResponse::new(APIRequestContext::new(Weak::new()), payload)
+    }
+
+    #[test]
+    fn header_value() {
+        let res = response(&[
+            ("Content-Type", "application/json"),
+            ("Content-Length", "42"),
+            ("Vary", "Accept"),
+            ("vary", "Origin"),
+            ("Set-Cookie", "a=1"),
+            ("set-cookie", "b=2"),
+        ]);
+        assert_eq!(res.content_type().as_deref(), Some("application/json"));
+        assert_eq!(res.body_size(), Some(42));
+        assert_eq!(res.header_value("VARY").as_deref(), Some("Accept, Origin"));
+        assert_eq!(res.header_value("set-cookie").as_deref(), Some("a=1\nb=2"));
+        assert_eq!(res.header_value("etag"), None);
+        assert_eq!(response(&[]).body_size(), None);
+    }
+}
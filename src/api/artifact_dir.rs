@@ -0,0 +1,90 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+static NEXT: AtomicUsize = AtomicUsize::new(0);
+
+/// A fresh directory under [`std::env::temp_dir`] for downloads, screenshots, videos and traces.
+///
+/// Directories are named `playwright-rust-{pid}-{n}`, numbered in creation order within the process.
+/// The directory and everything saved into it are removed on drop unless [`ArtifactDir::keep`] is called.
+///
+/// ```no_run
+/// # async fn f(download: playwright::api::Download) -> Result<(), Box<dyn std::error::Error>> {
+/// let dir = playwright::api::ArtifactDir::new()?;
+/// download.save_as(dir.join("report.pdf")).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ArtifactDir {
+    path: PathBuf,
+    keep: bool,
+}
+
+impl ArtifactDir {
+    pub fn new() -> io::Result<Self> {
+        let base = std::env::temp_dir();
+        let pid = std::process::id();
+        loop {
+            let n = NEXT.fetch_add(1, Ordering::Relaxed);
+            let path = base.join(format!("playwright-rust-{}-{}", pid, n));
+            match std::fs::create_dir(&path) {
+                Ok(()) => return Ok(Self { path, keep: false }),
+                // left over from an earlier process with the same pid
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Path of `name` inside this directory.
+    pub fn join<P: AsRef<Path>>(&self, name: P) -> PathBuf {
+        self.path.join(name)
+    }
+
+    /// Leaves the directory on disk, e.g. to inspect the artifacts of a failed test, and returns its path.
+    pub fn keep(mut self) -> PathBuf {
+        self.keep = true;
+        self.path.clone()
+    }
+}
+
+impl AsRef<Path> for ArtifactDir {
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for ArtifactDir {
+    fn drop(&mut self) {
+        if !self.keep {
+            let _ = std::fs::remove_dir_all(&self.path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn removed_on_drop() {
+        let a = ArtifactDir::new().unwrap();
+        let b = ArtifactDir::new().unwrap();
+        assert_ne!(a.path(), b.path());
+        std::fs::write(a.join("x.txt"), "x").unwrap();
+        let path = a.path().to_owned();
+        drop(a);
+        assert!(!path.exists());
+        let kept = b.keep();
+        assert!(kept.is_dir());
+        std::fs::remove_dir(kept).unwrap();
+    }
+}
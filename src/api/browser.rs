@@ -7,7 +7,10 @@ use crate::{
         core::*,
         playwright::DeviceDescriptor,
         prelude::*,
-        utils::{ColorScheme, Geolocation, HttpCredentials, ProxySettings, StorageState, Viewport},
+        utils::{
+            ColorScheme, Geolocation, HttpCredentials, ProxySettings, ServiceWorkerPolicy,
+            StorageState, Viewport,
+        },
     },
     Error,
 };
@@ -15,6 +18,8 @@ use crate::{
 #[derive(Debug)]
 pub struct Browser {
     inner: Weak<imp::browser::Browser>,
+    /// Owns the objects of a browser from [`BrowserType::connect`](crate::api::BrowserType::connect)
+    connection: Mutex<Option<Connection>>,
 }
 
 impl PartialEq for Browser {
@@ -29,7 +34,21 @@ impl PartialEq for Browser {
 
 impl Browser {
     pub(crate) fn new(inner: Weak<imp::browser::Browser>) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            connection: Mutex::default(),
+        }
+    }
+
+    #[cfg(feature = "tungstenite")]
+    pub(crate) fn with_connection(
+        inner: Weak<imp::browser::Browser>,
+        connection: Connection,
+    ) -> Self {
+        Self {
+            inner,
+            connection: Mutex::new(Some(connection)),
+        }
     }
 
     /// Returns an array of all open browser contexts. In a newly created browser, this will return zero browser contexts.
@@ -72,7 +91,10 @@ impl Browser {
             None => return Ok(()),
             Some(inner) => inner,
         };
-        inner.close().await
+        let res = inner.close().await;
+        // Dropping the connection disconnects from the server
+        self.connection.lock().unwrap().take();
+        res
     }
 
     // new_browser_cdp_session
@@ -178,6 +200,8 @@ impl<'e, 'f, 'g, 'h, 'i, 'j, 'k> ContextBuilder<'e, 'f, 'g, 'h, 'i, 'j, 'k> {
         /// Enables [HAR](http://www.softwareishard.com/blog/har-12-spec) recording for all pages into `recordHar.path` file. If not
         /// specified, the HAR is not recorded. Make sure to await [`method: BrowserContext.close`] for the HAR to be saved.
         record_har: Option<RecordHar<'k>>,
+        /// Whether to allow sites to register service workers. Defaults to `'allow'`.
+        service_workers: Option<ServiceWorkerPolicy>,
         /// Enables video recording for all pages into `recordVideo.dir` directory. If not specified videos are not recorded. Make
         /// sure to await [`method: BrowserContext.close`] for videos to be saved.
         record_video: Option<RecordVideo<'j>>,
@@ -1,11 +1,12 @@
 use crate::api::websocket_route::{Side as WebSocketRouteSide, WebSocketRoute};
-pub use crate::imp::browser_context::EventType;
+pub use crate::imp::browser_context::{EventType, HarNotFound};
 use crate::{
     api::{
         APIRequestContext, Browser, CDPSession, ConsoleMessage, Frame, Page, Request, Response,
-        Route, Tracing, WebError,
+        Route, Tracing, WebError, Worker,
     },
     imp::{
+        binding_call::binding_handler,
         browser_context::{BrowserContext as Impl, Evt},
         core::*,
         prelude::*,
@@ -54,6 +55,15 @@ impl BrowserContext {
             .collect())
     }
 
+    /// All existing service workers in the context. Dedicated workers are listed by [`Page::workers`] instead.
+    pub fn service_workers(&self) -> Result<Vec<Worker>, Error> {
+        Ok(upgrade(&self.inner)?
+            .service_workers()
+            .into_iter()
+            .map(Worker::new)
+            .collect())
+    }
+
     /// Returns the browser instance of the context. If it was launched as a persistent context None gets returned.
     pub fn browser(&self) -> Result<Option<Browser>, Error> {
         Ok(upgrade(&self.inner)?.browser().map(Browser::new))
@@ -97,12 +107,17 @@ impl BrowserContext {
         Ok(CDPSession::new(session))
     }
 
+    /// Changes the default maximum navigation time for every page in this context, including pages that already exist.
+    /// A timeout set with [`Page::set_default_navigation_timeout`](crate::api::Page::set_default_navigation_timeout)
+    /// takes priority.
     pub async fn set_default_navigation_timeout(&self, timeout: u32) -> ArcResult<()> {
         upgrade(&self.inner)?
             .set_default_navigation_timeout(timeout)
             .await
     }
 
+    /// Changes the default maximum time for every page in this context, including pages that already exist.
+    /// A timeout set with [`Page::set_default_timeout`](crate::api::Page::set_default_timeout) takes priority.
     pub async fn set_default_timeout(&self, timeout: u32) -> ArcResult<()> {
         upgrade(&self.inner)?.set_default_timeout(timeout).await
     }
@@ -150,6 +165,8 @@ impl BrowserContext {
     /// - `'clipboard-read'`
     /// - `'clipboard-write'`
     /// - `'payment-handler'`
+    ///
+    /// [`Permission`] converts into these strings.
     /// ## origin
     /// The origin to grant permissions to, e.g. `"https://example.com"`.
     pub async fn grant_permissions(
@@ -210,11 +227,45 @@ impl BrowserContext {
         upgrade(&self.inner)?.add_init_script(script).await
     }
 
+    /// Adds a function called `name` on the `window` object of every frame in every page in the context, including pages
+    /// and popups opened later. When called, the function executes `callback` and returns a Promise which resolves to the
+    /// return value of `callback`.
+    ///
+    /// The callback is the same as for [`Page::expose_function`]. The driver rejects a name that is already exposed on the
+    /// context or on any of its pages.
+    ///
+    /// ```ignore
+    /// context
+    ///     .expose_function("add", |(a, b): (i32, i32)| async move { a + b })
+    ///     .await?;
+    /// ```
+    pub async fn expose_function<F, Fut, A, R>(&self, name: &str, callback: F) -> ArcResult<()>
+    where
+        F: Fn(A) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = R> + Send + 'static,
+        A: DeserializeOwned + 'static,
+        R: Serialize + 'static,
+    {
+        upgrade(&self.inner)?
+            .expose_binding(name, binding_handler(callback))
+            .await
+    }
+
+    /// Removes a function added with [`BrowserContext::expose_function`]. `window[name]` is removed from the current
+    /// documents of every page and from documents created afterwards until `name` is exposed again. Does nothing if
+    /// `name` is not exposed on this context.
+    pub async fn remove_exposed_function(&self, name: &str) -> ArcResult<()> {
+        upgrade(&self.inner)?.remove_binding(name).await
+    }
+
     /// The extra HTTP headers will be sent with every request initiated by any page in the context. These headers are merged
     /// with page-specific extra HTTP headers set with [`method: Page.setExtraHTTPHeaders`]. If page overrides a particular
     /// header, page-specific header value will be used instead of the browser context header value.
     ///
     /// > NOTE: [`method: BrowserContext.setExtraHTTPHeaders`] does not guarantee the order of headers in the outgoing requests.
+    ///
+    /// `headers` are passed to the browser in iteration order, so use an ordered collection such as `Vec` rather than
+    /// `HashMap` if the order matters.
     pub async fn set_extra_http_headers<T>(&self, headers: T) -> ArcResult<()>
     where
         T: IntoIterator<Item = (String, String)>,
@@ -222,10 +273,6 @@ impl BrowserContext {
         upgrade(&self.inner)?.set_extra_http_headers(headers).await
     }
 
-    // async fn expose_binding(&mut self) -> Result<(), Error> { unimplemented!() }
-
-    // async fn expose_function(&mut self) -> Result<(), Error> { unimplemented!() }
-
     /// Enable request routing for the given glob pattern. Routes are handled on this context; the provided async handler
     /// is invoked for every matching request.
     pub async fn route<F, Fut>(&self, glob: &str, handler: F) -> ArcResult<()>
@@ -278,6 +325,30 @@ impl BrowserContext {
             .await
     }
 
+    /// Serves network requests from the HAR file at `har_path`.
+    ///
+    /// Requests matching `options.url` (every request when it is `None`) are looked up in the
+    /// HAR by url and method, and fulfilled with the recorded response.
+    /// Fails with [`Error::InvalidParams`] if the file doesn't exist.
+    ///
+    /// With `options.update`, requests go to the network instead and `har_path` is
+    /// (re)written with the observed traffic when the context is closed.
+    pub async fn route_from_har(
+        &self,
+        har_path: &Path,
+        options: RouteFromHarOptions,
+    ) -> ArcResult<()> {
+        let inner = upgrade(&self.inner)?;
+        let RouteFromHarOptions {
+            url,
+            not_found,
+            update,
+        } = options;
+        inner
+            .route_from_har(har_path, url.as_deref(), not_found, update)
+            .await
+    }
+
     /// Remove any previously installed route handler.
     pub async fn unroute(&self) -> ArcResult<()> {
         upgrade(&self.inner)?.unroute(None).await
@@ -363,12 +434,21 @@ impl BrowserContext {
 
     // background_page for chromium
     // new_cdp_session
-    // service_workers
+}
+
+/// Options of [`BrowserContext::route_from_har`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RouteFromHarOptions {
+    /// Glob of the requests to serve from the HAR. Defaults to every request.
+    pub url: Option<String>,
+    /// What to do with matching requests that are not found in the HAR. Defaults to abort.
+    pub not_found: HarNotFound,
+    /// Record the traffic into the HAR instead of serving from it.
+    pub update: bool,
 }
 
 pub enum Event {
     // BackgroundPage for chromium persistent
-    // ServiceWorker
     /// Emitted when Browser context gets closed. This might happen because of one of the following:
     /// - Browser context is closed.
     /// - Browser application is closed or crashed.
@@ -393,6 +473,8 @@ pub enum Event {
     Route(Route),
     /// Emitted when a console message is logged in any page in the context.
     Console(ConsoleMessage),
+    /// Emitted when a new service worker is created in the context.
+    ServiceWorker(Worker),
     Request(Request),
     RequestFailed(Request),
     RequestFinished(Request),
@@ -408,6 +490,7 @@ impl std::fmt::Debug for Event {
             Event::Page(_) => write!(f, "Page(..)"),
             Event::Route(_) => write!(f, "Route(..)"),
             Event::Console(_) => write!(f, "Console(..)"),
+            Event::ServiceWorker(_) => write!(f, "ServiceWorker(..)"),
             Event::Request(_) => write!(f, "Request(..)"),
             Event::RequestFailed(_) => write!(f, "RequestFailed(..)"),
             Event::RequestFinished(_) => write!(f, "RequestFinished(..)"),
@@ -425,6 +508,7 @@ impl PartialEq for Event {
             (Page(_), Page(_)) => true,
             (Route(_), Route(_)) => true,
             (Console(_), Console(_)) => true,
+            (ServiceWorker(_), ServiceWorker(_)) => true,
             (Request(_), Request(_)) => true,
             (RequestFailed(_), RequestFailed(_)) => true,
             (RequestFinished(_), RequestFinished(_)) => true,
@@ -442,6 +526,7 @@ impl From<Evt> for Event {
             Evt::Page(w) => Event::Page(Page::new(w)),
             Evt::Route(r) => Event::Route(Route::new(r)),
             Evt::Console(c) => Event::Console(ConsoleMessage::new(c)),
+            Evt::ServiceWorker(w) => Event::ServiceWorker(Worker::new(w)),
             Evt::Request(r) => Event::Request(Request::new(r)),
             Evt::RequestFailed(r) => Event::RequestFailed(Request::new(r)),
             Evt::RequestFinished(r) => Event::RequestFinished(Request::new(r)),
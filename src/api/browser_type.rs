@@ -1,3 +1,5 @@
+#[cfg(feature = "tungstenite")]
+use crate::imp::browser_type::ConnectArgs;
 pub use crate::imp::browser_type::{RecordHar, RecordVideo};
 use crate::{
     api::{browser::Browser, browser_context::BrowserContext, playwright::DeviceDescriptor},
@@ -8,7 +10,8 @@ use crate::{
         core::*,
         prelude::*,
         utils::{
-            BrowserChannel, ColorScheme, Geolocation, HttpCredentials, ProxySettings, Viewport,
+            BrowserChannel, ColorScheme, Geolocation, HttpCredentials, ProxySettings,
+            ServiceWorkerPolicy, Viewport,
         },
     },
     Error,
@@ -88,13 +91,55 @@ impl BrowserType {
     /// The default browser context is accessible via [`method: Browser.contexts`].
     ///
     /// > NOTE: Connecting over the Chrome DevTools Protocol is only supported for Chromium-based browsers.
+    /// # Args
+    /// ## endpoint_url
     /// A CDP websocket endpoint or http url to connect to. For example `http://localhost:9222/` or
     /// `ws://127.0.0.1:9222/devtools/browser/387adf4c-243f-4051-a181-46798f4a46f4`.
     pub fn connect_over_cdp_builder<'a>(&self, endpoint_url: &'a str) -> ConnectOverCdpBuilder<'a> {
         ConnectOverCdpBuilder::new(self.inner.clone(), endpoint_url)
     }
 
-    // connect
+    /// Same as [`BrowserType::connect_over_cdp_builder`] with the options given at once.
+    ///
+    /// The returned browser is remote: artifacts such as videos and downloads can't be read through
+    /// `path()`, which fails with [`Error::RemoteArtifact`]; use `save_as` instead.
+    pub async fn connect_over_cdp(
+        &self,
+        endpoint_url: &str,
+        options: ConnectOverCdpOptions,
+    ) -> ArcResult<Browser> {
+        let ConnectOverCdpOptions {
+            headers,
+            slow_mo,
+            timeout,
+        } = options;
+        let mut builder = self.connect_over_cdp_builder(endpoint_url);
+        builder.args.headers = headers;
+        builder.args.slowmo = slow_mo;
+        builder.args.timeout = timeout;
+        builder.connect_over_cdp().await
+    }
+
+    /// This methods attaches Playwright to a Playwright server, e.g. `playwright run-server` or a remote browser grid.
+    /// The server provides a browser of this type unless `x-playwright-browser` is set in `headers`.
+    ///
+    /// The connection goes over a WebSocket instead of the local driver. The returned browser is remote: artifacts such
+    /// as videos and downloads fail with [`Error::RemoteArtifact`] on `path()` and have to be streamed with `save_as`.
+    /// When the socket is lost, every pending and later call fails with [`Error::Transport`]. Closing or dropping the
+    /// returned browser disconnects from the server, after which its contexts and pages can no longer be used.
+    /// # Args
+    /// ## ws_endpoint
+    /// A browser websocket endpoint to connect to.
+    #[cfg(feature = "tungstenite")]
+    pub async fn connect(&self, ws_endpoint: &str, options: ConnectOptions) -> ArcResult<Browser> {
+        let ConnectOptions { headers, timeout } = options;
+        let mut args = ConnectArgs::new(ws_endpoint);
+        args.headers = headers;
+        args.timeout = timeout;
+        let (r, connection) = upgrade(&self.inner)?.connect(args).await?;
+        Ok(Browser::with_connection(r, connection))
+    }
+
     // launch_server
 }
 
@@ -286,6 +331,8 @@ impl<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i, 'j, 'k>
         /// Enables [HAR](http://www.softwareishard.com/blog/har-12-spec) recording for all pages into `recordHar.path` file. If not
         /// specified, the HAR is not recorded. Make sure to await [`method: BrowserContext.close`] for the HAR to be saved.
         record_har: Option<RecordHar<'k>>,
+        /// Whether to allow sites to register service workers. Defaults to `'allow'`.
+        service_workers: Option<ServiceWorkerPolicy>,
 
         channel: Option<BrowserChannel>
     }
@@ -304,6 +351,29 @@ impl<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i, 'j, 'k>
     //#[doc = "**DEPRECATED** Use `recordVideo` instead."] videos_path: Option<path>,
 }
 
+/// Options of [`BrowserType::connect`]
+#[cfg(feature = "tungstenite")]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConnectOptions {
+    /// Additional HTTP headers to be sent with web socket connect request.
+    pub headers: Option<HashMap<String, String>>,
+    /// Maximum time in milliseconds to wait for the connection to be established. Defaults to `30000` (30 seconds). Pass
+    /// `0` to disable timeout.
+    pub timeout: Option<f64>,
+}
+
+/// Options of [`BrowserType::connect_over_cdp`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConnectOverCdpOptions {
+    /// Additional HTTP headers to be sent with web socket connect request.
+    pub headers: Option<HashMap<String, String>>,
+    /// Slows down Playwright operations by the specified amount of milliseconds.
+    pub slow_mo: Option<f64>,
+    /// Maximum time in milliseconds to wait for the connection to be established. Defaults to `30000` (30 seconds). Pass
+    /// `0` to disable timeout.
+    pub timeout: Option<f64>,
+}
+
 pub struct ConnectOverCdpBuilder<'a> {
     inner: Weak<Impl>,
     args: ConnectOverCdpArgs<'a>,
@@ -33,6 +33,44 @@ impl CDPSession {
         upgrade(&self.inner)?.send(method, params).await
     }
 
+    /// `Network.enable`, needed before most other `Network` commands and to receive `Network.*` events.
+    pub async fn enable_network(&self) -> ArcResult<()> {
+        self.send("Network.enable", None).await?;
+        Ok(())
+    }
+
+    /// `Browser.setDownloadBehavior`. `download_path` is required for [`DownloadBehavior::Allow`] and
+    /// [`DownloadBehavior::AllowAndName`].
+    pub async fn set_download_behavior(
+        &self,
+        behavior: DownloadBehavior,
+        download_path: Option<&Path>,
+    ) -> ArcResult<()> {
+        #[skip_serializing_none]
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Params<'a> {
+            behavior: DownloadBehavior,
+            download_path: Option<&'a Path>,
+        }
+        let params = serde_json::to_value(Params {
+            behavior,
+            download_path,
+        })
+        .map_err(Error::Serde)?;
+        self.send("Browser.setDownloadBehavior", Some(params))
+            .await?;
+        Ok(())
+    }
+
+    /// `Network.emulateNetworkConditions`. Requires [`CDPSession::enable_network`] first.
+    pub async fn emulate_network_conditions(&self, conditions: NetworkConditions) -> ArcResult<()> {
+        let params = serde_json::to_value(conditions).map_err(Error::Serde)?;
+        self.send("Network.emulateNetworkConditions", Some(params))
+            .await?;
+        Ok(())
+    }
+
     /// Detaches the session from its target.
     pub async fn detach(&self) -> ArcResult<()> {
         upgrade(&self.inner)?.detach().await
@@ -41,6 +79,40 @@ impl CDPSession {
     subscribe_event! {}
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DownloadBehavior {
+    Deny,
+    Allow,
+    /// Like `Allow`, but files are named by their guid
+    AllowAndName,
+    Default,
+}
+
+/// Parameters of [`CDPSession::emulate_network_conditions`]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkConditions {
+    pub offline: bool,
+    /// Minimum latency from request sent to response headers received in milliseconds.
+    pub latency: f64,
+    /// Maximal aggregated download throughput in bytes/sec. -1 disables download throttling.
+    pub download_throughput: f64,
+    /// Maximal aggregated upload throughput in bytes/sec. -1 disables upload throttling.
+    pub upload_throughput: f64,
+}
+
+impl Default for NetworkConditions {
+    fn default() -> Self {
+        Self {
+            offline: false,
+            latency: 0.,
+            download_throughput: -1.,
+            upload_throughput: -1.,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Event {
     pub method: String,
@@ -52,3 +124,28 @@ impl From<Evt> for Event {
         Self { method, params }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn params() {
+        let v = serde_json::to_value(NetworkConditions {
+            offline: true,
+            ..NetworkConditions::default()
+        })
+        .unwrap();
+        assert_eq!(
+            v,
+            serde_json::json!({
+                "offline": true,
+                "latency": 0.0,
+                "downloadThroughput": -1.0,
+                "uploadThroughput": -1.0
+            })
+        );
+        let v = serde_json::to_value(DownloadBehavior::AllowAndName).unwrap();
+        assert_eq!(v, "allowAndName");
+    }
+}
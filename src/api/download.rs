@@ -59,8 +59,19 @@ impl Download {
         self.inner.save_as(path).await
     }
 
-    ///// Returns readable stream for current download or `null` if download failed.
-    // fn create_read_stream(&self) -> Result<Option<Readable>, Arc<Error>> { todo!() }
+    /// Cancels a download. Will not fail if the download is already finished or canceled. Upon successful cancellations,
+    /// `download.failure()` would resolve to `"canceled"`.
+    pub async fn cancel(&self) -> ArcResult<()> {
+        self.inner.cancel().await
+    }
+
+    /// Returns the contents of the download. Waits for the download to finish if necessary.
+    ///
+    /// The bytes are streamed over the connection, so this works with remote browsers and read-only file systems. The
+    /// whole file is loaded into memory; prefer [`Download::save_as`] for large downloads.
+    pub async fn read(&self) -> ArcResult<Vec<u8>> {
+        self.inner.read().await
+    }
 
     /// Returns download error if any. Will wait for the download to finish if necessary.
     pub async fn failure(&self) -> Result<Option<String>, Arc<Error>> {
@@ -9,7 +9,8 @@ use crate::{
         },
         prelude::*,
         utils::{
-            ElementState, File, FloatRect, KeyboardModifier, MouseButton, Position, ScreenshotType,
+            ElementState, File, FloatRect, KeyboardModifier, MouseButton, Position,
+            ScreenshotAnimations, ScreenshotScale, ScreenshotType, SetInputFilesSource,
             WaitForSelectorState,
         },
     },
@@ -68,6 +69,10 @@ impl ElementHandle {
         Ok(upgrade(&self.inner)?.guid().to_owned())
     }
 
+    pub(crate) async fn dispose(&self) -> ArcResult<()> {
+        upgrade(&self.inner)?.dispose().await
+    }
+
     /// The method finds an element matching the specified selector in the `ElementHandle`'s subtree.
     /// If no elements match the selector, returns `null`.
     pub async fn query_selector(&self, selector: &str) -> ArcResult<Option<ElementHandle>> {
@@ -313,6 +318,29 @@ impl ElementHandle {
         ScreenshotBuilder::new(self.inner.clone())
     }
 
+    /// Returns the buffer with the screenshot of this element, also when `options.path` is set.
+    /// The element is scrolled into view and only its bounding box is captured.
+    pub async fn screenshot(&self, options: ElementScreenshotOptions) -> ArcResult<Vec<u8>> {
+        let ElementScreenshotOptions {
+            r#type,
+            path,
+            quality,
+            scale,
+            animations,
+            timeout,
+        } = options;
+        let args = ScreenshotArgs {
+            path: path.as_deref(),
+            timeout,
+            r#type,
+            quality,
+            scale,
+            animations,
+            ..ScreenshotArgs::default()
+        };
+        upgrade(&self.inner)?.screenshot(args).await
+    }
+
     /// Returns when the element satisfies the `state`.
     pub async fn wait_for_element_state(
         &self,
@@ -419,6 +447,25 @@ impl ElementHandle {
         SetInputFilesBuilder::new(self.inner.clone(), file)
     }
 
+    /// Sets the value of the file input to `files`, which are either all in-memory [`File`]s or all local paths.
+    /// Local paths are read by the driver instead of being embedded in the message, so prefer them for large files.
+    /// A browser obtained through `BrowserType::connect` can't read them, which fails with
+    /// [`Error::RemoteInputFiles`]. For empty `files`, clears the selected files.
+    ///
+    /// ```ignore
+    /// input
+    ///     .set_input_files(vec![PathBuf::from("large.bin").into()], None)
+    ///     .await?;
+    /// ```
+    pub async fn set_input_files(
+        &self,
+        files: Vec<SetInputFilesSource>,
+        timeout: Option<f64>,
+    ) -> ArcResult<()> {
+        let args = SetInputFilesArgs::from_sources(files, timeout)?;
+        upgrade(&self.inner)?.set_input_files(args).await
+    }
+
     // eval_on_selector
     // eval_on_selector_all
 }
@@ -644,6 +691,21 @@ macro_rules! type_builder {
 type_builder!(TypeBuilder, TypeArgs, text, r#type);
 type_builder!(PressBuilder, PressArgs, key, press);
 
+/// Options of [`ElementHandle::screenshot`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ElementScreenshotOptions {
+    /// Specify screenshot type. Inferred from the extension of `path` if not set, defaults to `png`.
+    pub r#type: Option<ScreenshotType>,
+    /// The file path to save the image to. If `path` is a relative path, then it is resolved relative to the current
+    /// working directory.
+    pub path: Option<PathBuf>,
+    /// The quality of the image, between 0-100. Only applicable to `jpeg` images.
+    pub quality: Option<i64>,
+    pub scale: Option<ScreenshotScale>,
+    pub animations: Option<ScreenshotAnimations>,
+    pub timeout: Option<f64>,
+}
+
 pub struct ScreenshotBuilder<'a> {
     inner: Weak<Impl>,
     args: ScreenshotArgs<'a>,
@@ -675,6 +737,11 @@ impl<'a> ScreenshotBuilder<'a> {
         /// the disk.
         path: Option<&'a Path>,
         quality: Option<i64>,
+        /// When set to `Css`, the screenshot has a single pixel per each css pixel on the page. Defaults to `Device`.
+        scale: Option<ScreenshotScale>,
+        /// When set to `Disabled`, stops CSS animations, CSS transitions and Web Animations before taking the screenshot.
+        /// Defaults to `Allow`.
+        animations: Option<ScreenshotAnimations>,
         timeout: Option<f64>
     }
 
@@ -807,7 +874,7 @@ pub struct SetInputFilesBuilder {
 impl SetInputFilesBuilder {
     pub(crate) fn new(inner: Weak<Impl>, file: File) -> Self {
         let args = SetInputFilesArgs {
-            files: vec![file],
+            files: Some(vec![file]),
             ..SetInputFilesArgs::default()
         };
         Self { inner, args }
@@ -819,7 +886,7 @@ impl SetInputFilesBuilder {
     }
 
     pub fn add_file(mut self, x: File) -> Self {
-        self.args.files.push(x);
+        self.args.files.get_or_insert_with(Vec::new).push(x);
         self
     }
 
@@ -832,7 +899,7 @@ impl SetInputFilesBuilder {
     }
 
     pub fn clear_files(mut self) -> Self {
-        self.args.files = vec![];
+        self.args.files = Some(vec![]);
         self
     }
 }
@@ -1,16 +1,21 @@
 pub use crate::imp::frame::{FrameNavigatedEvent, FrameState, Polling};
 use crate::{
-    api::{ElementHandle, JsHandle, Page, Response},
+    api::{
+        CDPSession, DragOptions, ElementHandle, JsHandle, Page, Request, Response, UrlOrPredicate,
+    },
     api::{FrameLocator, Locator},
     imp::{
         core::*,
         frame::{
-            AddScriptTagArgs, CheckArgs, ClickArgs, Evt, FillArgs, Frame as Impl, GotoArgs,
-            HoverArgs, Opt, PressArgs, SelectOptionArgs, SetContentArgs, SetInputFilesArgs,
-            TapArgs, TypeArgs, WaitForFunctionArgs, WaitForSelectorArgs,
+            AddScriptTagArgs, AddStyleTagArgs, CheckArgs, ClickArgs, DragAndDropArgs, Evt,
+            FillArgs, Frame as Impl, GotoArgs, HoverArgs, Opt, PressArgs, SelectOptionArgs,
+            SetContentArgs, SetInputFilesArgs, TapArgs, TypeArgs, WaitForFunctionArgs,
+            WaitForSelectorArgs,
         },
         prelude::*,
-        utils::{DocumentLoadState, File, KeyboardModifier, MouseButton, Position},
+        utils::{
+            DocumentLoadState, File, KeyboardModifier, MouseButton, Position, SetInputFilesSource,
+        },
     },
 };
 
@@ -62,11 +67,20 @@ impl PartialEq for Frame {
 macro_rules! is_checked {
     ($f: ident) => {
         pub async fn $f(&self, selector: &str, timeout: Option<f64>) -> ArcResult<bool> {
-            upgrade(&self.inner)?.$f(selector, timeout).await
+            attached(&self.inner)?.$f(selector, timeout).await
         }
     };
 }
 
+/// Like `upgrade`, but fails with [`Error::FrameDetached`] once the frame has been detached.
+fn attached(inner: &Weak<Impl>) -> Result<Arc<Impl>, Error> {
+    let frame = upgrade(inner)?;
+    if frame.is_detached() {
+        return Err(Error::FrameDetached);
+    }
+    Ok(frame)
+}
+
 impl Frame {
     pub(crate) fn new(inner: Weak<Impl>) -> Self {
         Self { inner }
@@ -76,6 +90,14 @@ impl Frame {
         self.inner.clone()
     }
 
+    /// Attribute matched by `get_by_test_id`
+    pub(crate) fn test_id_attribute(&self) -> String {
+        self.inner
+            .upgrade()
+            .and_then(|f| f.test_id_attribute())
+            .unwrap_or_else(|| "data-testid".into())
+    }
+
     pub fn url(&self) -> Result<String, Error> {
         Ok(upgrade(&self.inner)?.url())
     }
@@ -93,6 +115,22 @@ impl Frame {
         Ok(upgrade(&self.inner)?.page().map(Page::new))
     }
 
+    /// Creates a CDP session attached to this frame, which must be an out-of-process iframe or the main frame.
+    /// Fails with [`Error::ChromiumOnly`] on other browsers.
+    pub async fn new_cdp_session(&self) -> ArcResult<CDPSession> {
+        let page = self.page()?.ok_or(Error::ObjectNotFound)?;
+        page.context().new_cdp_session_for_frame(self).await
+    }
+
+    /// Returns `true` once the frame has been detached from its page, e.g. when its `<iframe>` is removed. Actions on a
+    /// detached frame fail with [`Error::FrameDetached`].
+    pub fn is_detached(&self) -> bool {
+        self.inner
+            .upgrade()
+            .map(|f| f.is_detached())
+            .unwrap_or(true)
+    }
+
     /// Parent frame, if any. Detached frames and main frames return `null`.
     pub fn parent_frame(&self) -> Result<Option<Frame>, Error> {
         Ok(upgrade(&self.inner)?.parent_frame().map(Frame::new))
@@ -191,7 +229,7 @@ impl Frame {
     /// This method fetches an element with `selector` and focuses it. If there's no element matching `selector`, the method
     /// waits until a matching element appears in the DOM.
     pub async fn focus(&self, selector: &str, timeout: Option<f64>) -> ArcResult<()> {
-        upgrade(&self.inner)?.focus(selector, timeout).await
+        attached(&self.inner)?.focus(selector, timeout).await
     }
 
     /// Returns `element.textContent`.
@@ -200,17 +238,24 @@ impl Frame {
         selector: &str,
         timeout: Option<f64>,
     ) -> ArcResult<Option<String>> {
-        upgrade(&self.inner)?.text_content(selector, timeout).await
+        attached(&self.inner)?.text_content(selector, timeout).await
     }
 
     /// Returns `element.innerText`.
     pub async fn inner_text(&self, selector: &str, timeout: Option<f64>) -> ArcResult<String> {
-        upgrade(&self.inner)?.inner_text(selector, timeout).await
+        attached(&self.inner)?.inner_text(selector, timeout).await
     }
 
     /// Returns `element.innerHTML`.
     pub async fn inner_html(&self, selector: &str, timeout: Option<f64>) -> ArcResult<String> {
-        upgrade(&self.inner)?.inner_html(selector, timeout).await
+        attached(&self.inner)?.inner_html(selector, timeout).await
+    }
+
+    /// Returns the ARIA tree of the element as YAML, e.g. `- link "Home"`.
+    pub async fn aria_snapshot(&self, selector: &str, timeout: Option<f64>) -> ArcResult<String> {
+        attached(&self.inner)?
+            .aria_snapshot(selector, timeout)
+            .await
     }
 
     /// Returns element attribute value.
@@ -220,20 +265,20 @@ impl Frame {
         name: &str,
         timeout: Option<f64>,
     ) -> ArcResult<Option<String>> {
-        upgrade(&self.inner)?
+        attached(&self.inner)?
             .get_attribute(selector, name, timeout)
             .await
     }
 
     pub async fn query_selector(&self, selector: &str) -> ArcResult<Option<ElementHandle>> {
-        Ok(upgrade(&self.inner)?
+        Ok(attached(&self.inner)?
             .query_selector(selector)
             .await?
             .map(ElementHandle::new))
     }
 
     pub async fn query_selector_all(&self, selector: &str) -> ArcResult<Vec<ElementHandle>> {
-        let es = upgrade(&self.inner)?.query_selector_all(selector).await?;
+        let es = attached(&self.inner)?.query_selector_all(selector).await?;
         Ok(es.into_iter().map(ElementHandle::new).collect())
     }
 
@@ -289,9 +334,10 @@ impl Frame {
     }
 
     pub fn get_by_test_id(&self, test_id: &str) -> Locator {
+        let attribute = self.test_id_attribute();
         Locator::new(
             self.clone(),
-            crate::api::locator::build_test_id_selector(test_id),
+            crate::api::locator::build_test_id_selector(&attribute, test_id),
         )
     }
 
@@ -314,7 +360,7 @@ impl Frame {
     /// ```
     pub async fn frame_element(&self) -> ArcResult<ElementHandle> {
         Ok(ElementHandle::new(
-            upgrade(&self.inner)?.frame_element().await?,
+            attached(&self.inner)?.frame_element().await?,
         ))
     }
 
@@ -346,7 +392,7 @@ impl Frame {
     }
 
     pub async fn title(&self) -> ArcResult<String> {
-        upgrade(&self.inner)?.title().await
+        attached(&self.inner)?.title().await
     }
 
     /// Sends a `keydown`, `keypress`/`input`, and `keyup` event for each character in the text. `frame.type` can be used to
@@ -405,7 +451,7 @@ impl Frame {
 
     /// Gets the full HTML contents of the frame, including the doctype.
     pub async fn content<'a>(&self) -> ArcResult<String> {
-        upgrade(&self.inner)?.content().await
+        attached(&self.inner)?.content().await
     }
 
     pub fn set_content_builder<'a>(&self, html: &'a str) -> SetContentBuilder<'a> {
@@ -446,6 +492,41 @@ impl Frame {
         UncheckBuilder::new(self.inner.clone(), selector)
     }
 
+    /// Checks or unchecks an element matching `selector`, as [`Frame::check_builder`] or [`Frame::uncheck_builder`]
+    /// would depending on `checked`.
+    pub fn set_checked_builder<'a>(
+        &self,
+        selector: &'a str,
+        checked: bool,
+    ) -> SetCheckedBuilder<'a> {
+        SetCheckedBuilder::new(self.inner.clone(), selector, checked)
+    }
+
+    /// Drags the element matching `source` onto the element matching `target`. Unlike [`Page::drag_and_drop`],
+    /// the drag is performed by the driver, so it works for any frame and doesn't need the page's mouse.
+    pub async fn drag_and_drop(
+        &self,
+        source: &str,
+        target: &str,
+        options: DragOptions,
+    ) -> ArcResult<()> {
+        let DragOptions {
+            source_position,
+            target_position,
+            force,
+            timeout,
+        } = options;
+        let args = DragAndDropArgs {
+            source,
+            target,
+            source_position,
+            target_position,
+            force,
+            timeout,
+        };
+        attached(&self.inner)?.drag_and_drop(args).await
+    }
+
     // = |timeout| async { sleep(timeout).await }
     pub async fn wait_for_timeout(&self, timeout: f64) {
         sleep(std::time::Duration::from_millis(timeout as u64)).await
@@ -460,12 +541,22 @@ impl Frame {
         content: &str,
         url: Option<&str>,
     ) -> ArcResult<ElementHandle> {
-        upgrade(&self.inner)?
-            .add_style_tag(content, url)
+        let args = AddStyleTagArgs {
+            content: Some(content.to_owned()),
+            url,
+        };
+        attached(&self.inner)?
+            .add_style_tag(args)
             .await
             .map(ElementHandle::new)
     }
 
+    /// Adds a `<link rel="stylesheet">` tag with `url` or a `<style type="text/css">` tag with `content` or the
+    /// contents of `path`, and returns the added tag.
+    pub fn add_style_tag_builder(&self) -> AddStyleTagBuilder<'_, '_> {
+        AddStyleTagBuilder::new(self.inner.clone())
+    }
+
     /// Returns the added tag when the script's onload fires or when the script content was injected into frame.
     ///
     /// Adds a `<script>` tag into the page with the desired url or content.
@@ -479,7 +570,7 @@ impl Frame {
         state: Option<DocumentLoadState>,
         timeout: Option<f64>,
     ) -> ArcResult<()> {
-        upgrade(&self.inner)?
+        attached(&self.inner)?
             .wait_for_load_state(state, timeout)
             .await
     }
@@ -491,11 +582,41 @@ impl Frame {
         wait_until: Option<DocumentLoadState>,
         timeout: Option<f64>,
     ) -> ArcResult<()> {
-        upgrade(&self.inner)?
+        attached(&self.inner)?
             .wait_for_url(url, wait_until, timeout)
             .await
     }
 
+    /// Waits for the next navigation of this frame whose url matches `options.url`, including same-document ones such as
+    /// a single-page app pushing a route. Returns the main resource response, `None` for same-document navigations.
+    ///
+    /// Listening starts when the future is first polled, so poll it before the action, e.g. first in `tokio::join!`.
+    pub async fn wait_for_navigation(
+        &self,
+        options: WaitForNavigationOptions,
+    ) -> ArcResult<Option<Response>> {
+        let WaitForNavigationOptions {
+            url,
+            wait_until,
+            timeout,
+        } = options;
+        let inner = attached(&self.inner)?;
+        let matches = match url {
+            Some(m) => Some(m.into_fn()?),
+            None => None,
+        };
+        let timeout = timeout.map_or_else(|| inner.default_navigation_timeout(), |t| t as u32);
+        let wait_until = wait_until.unwrap_or(DocumentLoadState::Load);
+        let url = |u: &str| matches.iter().all(|m| m(u, &u.to_owned()));
+        let request = inner
+            .wait_for_navigation(inner.subscribe_event(), url, wait_until, timeout)
+            .await?;
+        match request {
+            Some(r) => Request::new(r).response().await,
+            None => Ok(None),
+        }
+    }
+
     pub async fn evaluate_element_handle<T>(
         &self,
         expression: &str,
@@ -504,7 +625,7 @@ impl Frame {
     where
         T: Serialize,
     {
-        upgrade(&self.inner)?
+        attached(&self.inner)?
             .evaluate_element_handle(expression, args)
             .await
             .map(ElementHandle::new)
@@ -543,17 +664,29 @@ impl Frame {
     where
         T: Serialize,
     {
-        upgrade(&self.inner)?
+        attached(&self.inner)?
             .evaluate_js_handle(expression, arg)
             .await
             .map(JsHandle::new)
     }
 
+    /// Returns the return value of `expression` as a [`JsHandle`] that keeps referring to the live object, e.g. `window`,
+    /// across calls. Unlike [`Frame::evaluate_js_handle`], DOM elements are accepted too.
+    pub async fn evaluate_handle<T>(&self, expression: &str, arg: Option<T>) -> ArcResult<JsHandle>
+    where
+        T: Serialize,
+    {
+        let handle = attached(&self.inner)?
+            .evaluate_handle(expression, arg)
+            .await?;
+        Ok(JsHandle::from_handle(handle))
+    }
+
     pub async fn eval<U>(&self, expression: &str) -> ArcResult<U>
     where
         U: DeserializeOwned,
     {
-        upgrade(&self.inner)?.eval(expression).await
+        attached(&self.inner)?.eval(expression).await
     }
 
     /// Returns the return value of `expression`.
@@ -584,7 +717,7 @@ impl Frame {
         T: Serialize,
         U: DeserializeOwned,
     {
-        upgrade(&self.inner)?.evaluate(expression, Some(arg)).await
+        attached(&self.inner)?.evaluate(expression, Some(arg)).await
     }
 
     /// Returns the return value of `expression`.
@@ -613,7 +746,7 @@ impl Frame {
         T: Serialize,
         U: DeserializeOwned,
     {
-        upgrade(&self.inner)?
+        attached(&self.inner)?
             .evaluate_on_selector(selector, expression, arg)
             .await
     }
@@ -641,7 +774,7 @@ impl Frame {
         T: Serialize,
         U: DeserializeOwned,
     {
-        upgrade(&self.inner)?
+        attached(&self.inner)?
             .evaluate_on_selector_all(selector, expression, arg)
             .await
     }
@@ -683,7 +816,7 @@ impl Frame {
         T: Serialize,
     {
         // timeout not supported
-        upgrade(&self.inner)?
+        attached(&self.inner)?
             .dispatch_event(selector, r#type, event_init)
             .await
     }
@@ -724,6 +857,57 @@ impl Frame {
         SetInputFilesBuilder::new(self.inner.clone(), selector, file)
     }
 
+    /// Like [`Frame::set_input_files_builder`] but starts from a file on the local file system.
+    /// Add more with [`SetInputFilesBuilder::add_path`].
+    pub fn set_input_paths_builder<'a, P: Into<PathBuf>>(
+        &self,
+        selector: &'a str,
+        path: P,
+    ) -> SetInputFilesBuilder<'a> {
+        SetInputFilesBuilder::with_path(self.inner.clone(), selector, path.into())
+    }
+
+    /// Sets the value of the file input matching `selector` to `files`, which are either all in-memory [`File`]s or all
+    /// local paths. See [`ElementHandle::set_input_files`]. For empty `files`, clears the selected files.
+    pub async fn set_input_files(
+        &self,
+        selector: &str,
+        files: Vec<SetInputFilesSource>,
+        timeout: Option<f64>,
+    ) -> ArcResult<()> {
+        let args = SetInputFilesArgs::from_sources(selector, files, timeout)?;
+        attached(&self.inner)?.set_input_files(args).await
+    }
+
+    /// Waits until `expression` returns a truthy value and returns a handle to that value.
+    ///
+    /// `arg` is passed to `expression` when it is a function.
+    ///
+    /// ```js
+    /// await frame.waitForFunction(() => window.innerWidth < 100);
+    /// ```
+    pub async fn wait_for_function<T>(
+        &self,
+        expression: &str,
+        arg: Option<T>,
+        options: WaitForFunctionOptions,
+    ) -> ArcResult<JsHandle>
+    where
+        T: Serialize,
+    {
+        let WaitForFunctionOptions { polling, timeout } = options;
+        let mut args = WaitForFunctionArgs::new(expression);
+        args.polling = polling;
+        args.timeout = timeout;
+        if let Some(arg) = arg {
+            args.arg = Some(ser::to_value(&arg).map_err(Error::SerializationPwJson)?);
+        }
+        attached(&self.inner)?
+            .wait_for_function(args)
+            .await
+            .map(JsHandle::new)
+    }
+
     /// Returns when the `expression` returns a truthy value, returns that value.
     ///
     /// The [`method: Frame.waitForFunction`] can be used to observe viewport size change:
@@ -777,7 +961,7 @@ impl<'a, 'b> GotoBuilder<'a, 'b> {
 
     pub async fn goto(self) -> Result<Option<Response>, Arc<Error>> {
         let Self { inner, args } = self;
-        let r = upgrade(&inner)?.goto(args).await?;
+        let r = attached(&inner)?.goto(args).await?;
         Ok(r.map(Response::new))
     }
 
@@ -785,6 +969,8 @@ impl<'a, 'b> GotoBuilder<'a, 'b> {
         /// Referer header value. If provided it will take preference over the referer header value set by
         /// [`method: Page.setExtraHTTPHeaders`].
         referer: Option<&'b str>,
+        /// Maximum operation time in milliseconds. Defaults to the page's default navigation timeout, which falls back to
+        /// the context's.
         timeout: Option<f64>,
         wait_until: Option<DocumentLoadState>
     }
@@ -805,7 +991,7 @@ macro_rules! clicker {
 
             pub async fn $f(self) -> Result<(), Arc<Error>> {
                 let Self { inner, args } = self;
-                let _ = upgrade(&inner)?.$f(args).await?;
+                let _ = attached(&inner)?.$f(args).await?;
                 Ok(())
             }
 
@@ -853,7 +1039,7 @@ impl<'a> WaitForSelectorBuilder<'a> {
 
     pub async fn wait_for_selector(self) -> Result<Option<ElementHandle>, Arc<Error>> {
         let Self { inner, args } = self;
-        let e = upgrade(&inner)?.wait_for_selector(args).await?;
+        let e = attached(&inner)?.wait_for_selector(args).await?;
         Ok(e.map(ElementHandle::new))
     }
 
@@ -879,7 +1065,7 @@ macro_rules! type_builder {
 
             pub async fn $m(self) -> Result<(), Arc<Error>> {
                 let Self { inner, args } = self;
-                let _ = upgrade(&inner)?.$m(args).await?;
+                let _ = attached(&inner)?.$m(args).await?;
                 Ok(())
             }
 
@@ -912,7 +1098,7 @@ impl<'a> HoverBuilder<'a> {
 
     pub async fn goto(self) -> Result<(), Arc<Error>> {
         let Self { inner, args } = self;
-        upgrade(&inner)?.hover(args).await
+        attached(&inner)?.hover(args).await
     }
 
     setter! {
@@ -944,7 +1130,7 @@ impl<'a> SetContentBuilder<'a> {
 
     pub async fn set_content(self) -> Result<(), Arc<Error>> {
         let Self { inner, args } = self;
-        upgrade(&inner)?.set_content(args).await
+        attached(&inner)?.set_content(args).await
     }
 
     setter! {
@@ -966,7 +1152,7 @@ impl<'a> TapBuilder<'a> {
 
     pub async fn tap(self) -> Result<(), Arc<Error>> {
         let Self { inner, args } = self;
-        let _ = upgrade(&inner)?.tap(args).await?;
+        let _ = attached(&inner)?.tap(args).await?;
         Ok(())
     }
 
@@ -1003,7 +1189,7 @@ impl<'a, 'b> FillBuilder<'a, 'b> {
 
     pub async fn fill(self) -> Result<(), Arc<Error>> {
         let Self { inner, args } = self;
-        let _ = upgrade(&inner)?.fill(args).await?;
+        let _ = attached(&inner)?.fill(args).await?;
         Ok(())
     }
 
@@ -1031,7 +1217,7 @@ macro_rules! check_builder {
 
             pub async fn $m(self) -> Result<(), Arc<Error>> {
                 let Self { inner, args } = self;
-                let _ = upgrade(&inner)?.$m(args).await?;
+                let _ = attached(&inner)?.$m(args).await?;
                 Ok(())
             }
 
@@ -1056,6 +1242,126 @@ macro_rules! check_builder {
 check_builder!(CheckBuilder, check);
 check_builder!(UncheckBuilder, uncheck);
 
+/// Runs a [`CheckBuilder`] or an [`UncheckBuilder`] depending on `checked`.
+pub struct SetCheckedBuilder<'a> {
+    inner: Weak<Impl>,
+    args: CheckArgs<'a>,
+    checked: bool,
+}
+
+impl<'a> SetCheckedBuilder<'a> {
+    pub(crate) fn new(inner: Weak<Impl>, selector: &'a str, checked: bool) -> Self {
+        let args = CheckArgs::new(selector);
+        Self {
+            inner,
+            args,
+            checked,
+        }
+    }
+
+    pub async fn set_checked(self) -> Result<(), Arc<Error>> {
+        let Self {
+            inner,
+            args,
+            checked,
+        } = self;
+        let inner = attached(&inner)?;
+        if checked {
+            inner.check(args).await
+        } else {
+            inner.uncheck(args).await
+        }
+    }
+
+    setter! {
+        /// A point to use relative to the top-left corner of element padding box. If not specified, uses some visible point of the element.
+        position: Option<Position>,
+        /// Whether to bypass the actionability checks. Defaults to `false`.
+        force: Option<bool>,
+        /// Actions that initiate navigations are waiting for these navigations to happen and for pages to start loading. You can
+        /// opt out of waiting via setting this flag. You would only need this option in the exceptional cases such as navigating to
+        /// inaccessible pages. Defaults to `false`.
+        no_wait_after: Option<bool>,
+        timeout: Option<f64>,
+        /// When set, this method only performs the [actionability](https://playwright.dev/docs/actionability/) checks and skips the action. Defaults to
+        /// `false`. Useful to wait until the element is ready for the action without performing it.
+        trial: Option<bool>
+    }
+}
+
+pub struct AddStyleTagBuilder<'a, 'b> {
+    inner: Weak<Impl>,
+    content: Option<&'a str>,
+    url: Option<&'b str>,
+    path: Option<PathBuf>,
+}
+
+impl<'a, 'b> AddStyleTagBuilder<'a, 'b> {
+    pub(crate) fn new(inner: Weak<Impl>) -> Self {
+        Self {
+            inner,
+            content: None,
+            url: None,
+            path: None,
+        }
+    }
+
+    pub async fn add_style_tag(self) -> Result<ElementHandle, Arc<Error>> {
+        let Self {
+            inner,
+            content,
+            url,
+            path,
+        } = self;
+        let content = match path {
+            Some(p) => {
+                let mut css = std::fs::read_to_string(&p).map_err(Error::Io)?;
+                css.push_str(&format!("\n/*# sourceURL={}*/", p.display()));
+                Some(css)
+            }
+            None => content.map(str::to_owned),
+        };
+        let args = AddStyleTagArgs { content, url };
+        attached(&inner)?
+            .add_style_tag(args)
+            .await
+            .map(ElementHandle::new)
+    }
+
+    /// Raw CSS content to be injected into frame.
+    pub fn content(mut self, x: &'a str) -> Self {
+        self.content = Some(x);
+        self
+    }
+
+    /// URL of the `<link>` tag.
+    pub fn url(mut self, x: &'b str) -> Self {
+        self.url = Some(x);
+        self
+    }
+
+    /// Path to the CSS file to be injected into frame. Takes precedence over `content`.
+    pub fn path<P: AsRef<Path>>(mut self, x: P) -> Self {
+        self.path = Some(x.as_ref().to_owned());
+        self
+    }
+
+    pub fn clear_content(mut self) -> Self {
+        self.content = None;
+        self
+    }
+
+    pub fn clear_url(mut self) -> Self {
+        self.url = None;
+        self
+    }
+
+    pub fn clear_path(mut self) -> Self {
+        self.path = None;
+        self
+    }
+}
+
 pub struct AddScriptTagBuilder<'a, 'b, 'c> {
     inner: Weak<Impl>,
     args: AddScriptTagArgs<'a, 'b, 'c>,
@@ -1081,7 +1387,7 @@ impl<'a, 'b, 'c> AddScriptTagBuilder<'a, 'b, 'c> {
         if let Some(p) = path {
             args.path = Some(p);
         }
-        upgrade(&inner)?
+        attached(&inner)?
             .add_script_tag(args)
             .await
             .map(ElementHandle::new)
@@ -1137,7 +1443,7 @@ impl<'a> SelectOptionBuilder<'a> {
         if let Some(e) = err {
             return Err(e.into());
         }
-        upgrade(&inner)?.select_option(args).await
+        attached(&inner)?.select_option(args).await
     }
 
     pub fn add_element(mut self, x: &ElementHandle) -> Self {
@@ -1216,17 +1522,49 @@ pub struct SetInputFilesBuilder<'a> {
 impl<'a> SetInputFilesBuilder<'a> {
     pub(crate) fn new(inner: Weak<Impl>, selector: &'a str, file: File) -> Self {
         let mut args = SetInputFilesArgs::new(selector);
-        args.files = vec![file];
+        args.files = Some(vec![file]);
+        Self { inner, args }
+    }
+
+    pub(crate) fn with_path(inner: Weak<Impl>, selector: &'a str, path: PathBuf) -> Self {
+        let mut args = SetInputFilesArgs::new(selector);
+        args.local_paths = Some(vec![path]);
         Self { inner, args }
     }
 
     pub async fn set_input_files(self) -> Result<(), Arc<Error>> {
-        let Self { inner, args } = self;
-        upgrade(&inner)?.set_input_files(args).await
+        let Self { inner, mut args } = self;
+        if let Some(paths) = args.local_paths.take().filter(|p| !p.is_empty()) {
+            if args.files.as_ref().is_some_and(|f| !f.is_empty()) {
+                return Err(crate::imp::element_handle::mixed_input_files().into());
+            }
+            let cwd = std::env::current_dir().map_err(Error::from)?;
+            args.files = None;
+            args.local_paths = Some(paths.into_iter().map(|p| cwd.join(p)).collect());
+        }
+        attached(&inner)?.set_input_files(args).await
     }
 
     pub fn add_file(mut self, x: File) -> Self {
-        self.args.files.push(x);
+        self.args.files.get_or_insert_with(Vec::new).push(x);
+        self
+    }
+
+    /// Adds a file on the local file system. The driver reads it from disk, so large files are not sent in memory.
+    /// Relative paths are resolved against the current working directory. Can't be combined with in-memory files.
+    /// Fails with [`Error::RemoteInputFiles`] when the browser was obtained through `BrowserType::connect`.
+    pub fn add_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.args
+            .local_paths
+            .get_or_insert_with(Vec::new)
+            .push(path.into());
+        self
+    }
+
+    /// Drops every queued file and path so that the input ends up with an empty selection.
+    pub fn clear(mut self) -> Self {
+        self.args.files = Some(vec![]);
+        self.args.local_paths = None;
         self
     }
 
@@ -1238,12 +1576,33 @@ impl<'a> SetInputFilesBuilder<'a> {
         timeout: Option<f64>
     }
 
-    pub fn clear_files(mut self) -> Self {
-        self.args.files = vec![];
-        self
+    /// Sets no files, which clears the selected files of the input. Same as [`SetInputFilesBuilder::clear`].
+    pub fn clear_files(self) -> Self {
+        self.clear()
     }
 }
 
+/// Options of [`Frame::wait_for_function`]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct WaitForFunctionOptions {
+    /// If `polling` is `'raf'`, then `expression` is constantly executed in `requestAnimationFrame` callback. If `polling` is a
+    /// number, then it is treated as an interval in milliseconds at which the function would be executed. Defaults to `raf`.
+    pub polling: Option<Polling>,
+    /// Maximum time to wait for in milliseconds. Defaults to the default timeout.
+    pub timeout: Option<f64>,
+}
+
+/// Options of [`Frame::wait_for_navigation`]
+#[derive(Default)]
+pub struct WaitForNavigationOptions {
+    /// Only navigations to a matching url resolve the wait. A predicate receives the new url.
+    pub url: Option<UrlOrPredicate<String>>,
+    /// When to consider the navigation succeeded. Defaults to `load`.
+    pub wait_until: Option<DocumentLoadState>,
+    /// Maximum time in milliseconds. Defaults to the default navigation timeout.
+    pub timeout: Option<f64>,
+}
+
 pub struct WaitForFunctionBuilder<'a> {
     inner: Weak<Impl>,
     args: WaitForFunctionArgs<'a>,
@@ -1265,7 +1624,7 @@ impl<'a> WaitForFunctionBuilder<'a> {
         if let Some(e) = err {
             return Err(e.into());
         }
-        upgrade(&inner)?
+        attached(&inner)?
             .wait_for_function(args)
             .await
             .map(JsHandle::new)
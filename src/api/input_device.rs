@@ -118,9 +118,17 @@ impl Keyboard {
     ///
     ///
     /// > NOTE: Modifier keys DO NOT effect `keyboard.insertText`. Holding down `Shift` will not type the text in upper case.
-    pub async fn input_text(&self, text: &str) -> Result<(), Arc<Error>> {
+    ///
+    /// Unlike [`Keyboard::type`](Keyboard::r#type), this works for text that can't be typed with keys, such as emoji or
+    /// IME-composed text.
+    pub async fn insert_text(&self, text: &str) -> Result<(), Arc<Error>> {
         let inner = upgrade(&self.inner)?;
-        inner.key_input_text(text).await
+        inner.key_insert_text(text).await
+    }
+
+    #[deprecated(note = "renamed to `insert_text`")]
+    pub async fn input_text(&self, text: &str) -> Result<(), Arc<Error>> {
+        self.insert_text(text).await
     }
 
     /// Sends a `keydown`, `keypress`/`input`, and `keyup` event for each character in the text.
@@ -174,6 +182,29 @@ impl Mouse {
         inner.mouse_up(button, click_count).await
     }
 
+    /// Dispatches a `wheel` event at the current mouse position. This method is usually used to manually scroll the page.
+    ///
+    /// > NOTE: Wheel events may cause scrolling if they are not handled, and this method does not wait for the scrolling to
+    /// finish before returning.
+    pub async fn wheel(&self, delta_x: f64, delta_y: f64) -> Result<(), Arc<Error>> {
+        let inner = upgrade(&self.inner)?;
+        inner.mouse_wheel(delta_x, delta_y).await
+    }
+
+    /// Queues [`method: Mouse.move`], [`method: Mouse.down`] and [`method: Mouse.up`] steps and runs them in order with
+    /// [`GestureBuilder::perform`]. Useful for drawing on a canvas or dragging custom sliders.
+    ///
+    /// ```js
+    ///// Drawing a line
+    /// await page.mouse.move(10, 10);
+    /// await page.mouse.down();
+    /// await page.mouse.move(100, 100, {steps: 10});
+    /// await page.mouse.up();
+    /// ```
+    pub fn gesture(&self) -> GestureBuilder {
+        GestureBuilder::new(self.inner.clone())
+    }
+
     /// Shortcut for [`method: Mouse.move`], [`method: Mouse.down`], [`method: Mouse.up`].
     pub fn click_builder(&self, x: f64, y: f64) -> ClickBuilder {
         ClickBuilder::new(self.inner.clone(), x, y)
@@ -191,6 +222,10 @@ impl TouchScreen {
         Self { inner }
     }
 
+    /// Dispatches a `touchstart` and `touchend` event with a single touch at the position (`x`,`y`) in CSS pixels
+    /// relative to the top-left corner of the viewport.
+    ///
+    /// Fails with [`Error::TouchNotEnabled`] unless the browser context was created with `has_touch`.
     pub async fn tap(&self, x: f64, y: f64) -> Result<(), Arc<Error>> {
         let inner = upgrade(&self.inner)?;
         inner.screen_tap(x, y).await
@@ -230,3 +265,96 @@ macro_rules! clicker {
 
 clicker!(ClickBuilder, click, mouse_click);
 clicker!(DblClickBuilder, dblclick, mouse_dblclick);
+
+enum GestureStep {
+    Move { x: f64, y: f64, steps: Option<i32> },
+    Down(Option<MouseButton>),
+    Up(Option<MouseButton>),
+    Pause(f64),
+}
+
+/// Sequence of mouse steps built by [`Mouse::gesture`].
+pub struct GestureBuilder {
+    inner: Weak<PageImpl>,
+    steps: Vec<GestureStep>,
+    delay: Option<f64>,
+}
+
+impl GestureBuilder {
+    pub(crate) fn new(inner: Weak<PageImpl>) -> Self {
+        Self {
+            inner,
+            steps: Vec::new(),
+            delay: None,
+        }
+    }
+
+    /// Runs the queued steps in order, waiting `delay` milliseconds between them.
+    pub async fn perform(self) -> Result<(), Arc<Error>> {
+        let Self {
+            inner,
+            steps,
+            delay,
+        } = self;
+        for (i, step) in steps.into_iter().enumerate() {
+            if i > 0 {
+                if let Some(ms) = delay {
+                    sleep(Duration::from_micros((ms * 1000.) as u64)).await;
+                }
+            }
+            let inner = upgrade(&inner)?;
+            match step {
+                GestureStep::Move { x, y, steps } => inner.mouse_move(x, y, steps).await?,
+                GestureStep::Down(button) => inner.mouse_down(button, None).await?,
+                GestureStep::Up(button) => inner.mouse_up(button, None).await?,
+                GestureStep::Pause(ms) => sleep(Duration::from_micros((ms * 1000.) as u64)).await,
+            }
+        }
+        Ok(())
+    }
+
+    /// Moves the mouse to `x`, `y` in one `mousemove` event.
+    pub fn move_to(self, x: f64, y: f64) -> Self {
+        self.push(GestureStep::Move { x, y, steps: None })
+    }
+
+    /// Moves the mouse to `x`, `y`, interpolating `steps` intermediate `mousemove` events.
+    pub fn move_to_in_steps(self, x: f64, y: f64, steps: i32) -> Self {
+        self.push(GestureStep::Move {
+            x,
+            y,
+            steps: Some(steps),
+        })
+    }
+
+    /// Presses `button`. Defaults to `left`.
+    pub fn down(self, button: Option<MouseButton>) -> Self {
+        self.push(GestureStep::Down(button))
+    }
+
+    /// Releases `button`. Defaults to `left`.
+    pub fn up(self, button: Option<MouseButton>) -> Self {
+        self.push(GestureStep::Up(button))
+    }
+
+    /// Waits `ms` milliseconds before the next step.
+    pub fn pause(self, ms: f64) -> Self {
+        self.push(GestureStep::Pause(ms))
+    }
+
+    fn push(mut self, step: GestureStep) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    /// Time to wait between every two steps in milliseconds. Defaults to 0.
+    pub fn delay(mut self, ms: f64) -> Self {
+        self.delay = Some(ms);
+        self
+    }
+
+    pub fn clear_delay(mut self) -> Self {
+        self.delay = None;
+        self
+    }
+}
@@ -1,4 +1,4 @@
-use crate::imp::{core::*, js_handle::JsHandle as Impl, prelude::*};
+use crate::imp::{core::*, frame::Handle, js_handle::JsHandle as Impl, prelude::*};
 use std::fmt;
 
 /// JsHandle represents an in-page JavaScript object. JsHandles can be created with the [`method: Page.evaluateHandle`]
@@ -16,34 +16,43 @@ use std::fmt;
 /// JsHandle instances can be used as an argument in [`method: Page.evalOnSelector`], [`method: Page.evaluate`] and
 /// [`method: Page.evaluateHandle`] methods.
 pub struct JsHandle {
-    inner: Weak<Impl>,
+    inner: Handle,
 }
 
 impl PartialEq for JsHandle {
     fn eq(&self, other: &Self) -> bool {
-        let a = self.inner.upgrade();
-        let b = other.inner.upgrade();
-        a.and_then(|a| b.map(|b| (a, b)))
-            .map(|(a, b)| a.guid() == b.guid())
-            .unwrap_or_default()
+        let a = self.guid().ok();
+        let b = other.guid().ok();
+        a.and_then(|a| b.map(|b| a == b)).unwrap_or_default()
     }
 }
 
 impl JsHandle {
     pub(crate) fn new(inner: Weak<Impl>) -> Self {
+        Self {
+            inner: Handle::Js(inner),
+        }
+    }
+
+    /// Wraps either kind of handle returned by `evaluateExpressionHandle`
+    pub(crate) fn from_handle(inner: Handle) -> Self {
         Self { inner }
     }
 
     pub(crate) fn guid(&self) -> Result<Str<Guid>, Error> {
-        Ok(upgrade(&self.inner)?.guid().to_owned())
+        match &self.inner {
+            Handle::Js(j) => Ok(upgrade(j)?.guid().to_owned()),
+            Handle::Element(e) => Ok(upgrade(e)?.guid().to_owned()),
+        }
     }
 
     /// Fetches a single property from the referenced object.
     pub async fn get_property(&mut self, name: &str) -> ArcResult<JsHandle> {
-        upgrade(&self.inner)?
-            .get_property(name)
-            .await
-            .map(JsHandle::new)
+        let j = match &self.inner {
+            Handle::Js(j) => upgrade(j)?.get_property(name).await?,
+            Handle::Element(e) => upgrade(e)?.get_property(name).await?,
+        };
+        Ok(JsHandle::new(j))
     }
 
     /// The method returns a map with **own property names** as keys and JsHandle instances for the property values.
@@ -55,13 +64,22 @@ impl JsHandle {
     /// const documentHandle = properties.get('document');
     /// await handle.dispose();
     /// ```
-    pub async fn get_properties(&mut self) -> ArcResult<HashMap<String, JsHandle>> {
-        let m = upgrade(&self.inner)?.get_properties().await?;
+    ///
+    /// Every returned handle is independent of `self` and of each other: disposing one doesn't dispose the rest.
+    /// Dispose them with [`JsHandle::dispose`] when done, otherwise they are released when their frame navigates.
+    pub async fn get_properties(&self) -> ArcResult<HashMap<String, JsHandle>> {
+        let m = match &self.inner {
+            Handle::Js(j) => upgrade(j)?.get_properties().await?,
+            Handle::Element(e) => upgrade(e)?.get_properties().await?,
+        };
         Ok(m.into_iter().map(|(k, v)| (k, JsHandle::new(v))).collect())
     }
 
     pub async fn dispose(&mut self) -> ArcResult<()> {
-        upgrade(&self.inner)?.dispose().await
+        match &self.inner {
+            Handle::Js(j) => upgrade(j)?.dispose().await,
+            Handle::Element(e) => upgrade(e)?.dispose().await,
+        }
     }
 
     /// Returns a JSON representation of the object. If the object has a `toJSON` function, it **will not be called**.
@@ -72,7 +90,10 @@ impl JsHandle {
     where
         U: DeserializeOwned,
     {
-        upgrade(&self.inner)?.json_value().await
+        match &self.inner {
+            Handle::Js(j) => upgrade(j)?.json_value().await,
+            Handle::Element(e) => upgrade(e)?.json_value().await,
+        }
     }
 
     // evaluate
@@ -80,10 +101,13 @@ impl JsHandle {
 
 impl fmt::Display for JsHandle {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if let Some(inner) = self.inner.upgrade() {
-            inner.fmt(f)
-        } else {
-            write!(f, "")
+        match &self.inner {
+            Handle::Js(j) => match j.upgrade() {
+                Some(inner) => inner.fmt(f),
+                None => write!(f, ""),
+            },
+            // Element handles don't track a preview
+            Handle::Element(_) => write!(f, "JSHandle@node"),
         }
     }
 }
@@ -27,7 +27,7 @@ impl Locator {
         Self { frame, selector }
     }
 
-    fn selector(&self) -> &str {
+    pub(crate) fn selector(&self) -> &str {
         &self.selector
     }
 
@@ -39,6 +39,8 @@ impl Locator {
         )
     }
 
+    /// Locates `selector` under this locator. CSS and text selectors pierce open shadow roots, e.g. to reach the button
+    /// of a `<my-widget>`; closed shadow roots are never pierced.
     pub fn locator(&self, selector: &str) -> Self {
         Self::new(
             self.frame.clone(),
@@ -109,9 +111,10 @@ impl Locator {
         self.chain_selector(selector)
     }
 
-    /// Locate by data-testid (Playwright's default test id attribute).
+    /// Locate by test id attribute, `data-testid` unless changed with
+    /// [`Selectors::set_test_id_attribute`](crate::api::Selectors::set_test_id_attribute).
     pub fn get_by_test_id(&self, test_id: &str) -> Self {
-        let selector = build_test_id_selector(test_id);
+        let selector = build_test_id_selector(&self.frame.test_id_attribute(), test_id);
         self.chain_selector(selector)
     }
 
@@ -143,12 +146,24 @@ impl Locator {
     pub fn uncheck_builder(&self) -> crate::api::frame::UncheckBuilder<'_> {
         self.frame.uncheck_builder(self.selector())
     }
+    pub fn set_checked_builder(&self, checked: bool) -> crate::api::frame::SetCheckedBuilder<'_> {
+        self.frame.set_checked_builder(self.selector(), checked)
+    }
+    pub fn select_option_builder(&self) -> crate::api::frame::SelectOptionBuilder<'_> {
+        self.frame.select_option_builder(self.selector())
+    }
     pub fn set_input_files_builder(
         &self,
         file: crate::imp::utils::File,
     ) -> crate::api::frame::SetInputFilesBuilder<'_> {
         self.frame.set_input_files_builder(self.selector(), file)
     }
+    pub fn set_input_paths_builder<P: Into<std::path::PathBuf>>(
+        &self,
+        path: P,
+    ) -> crate::api::frame::SetInputFilesBuilder<'_> {
+        self.frame.set_input_paths_builder(self.selector(), path)
+    }
 
     // Convenience async methods
     pub async fn focus(&self, timeout: Option<f64>) -> crate::imp::core::ArcResult<()> {
@@ -166,6 +181,9 @@ impl Locator {
     pub async fn inner_html(&self, timeout: Option<f64>) -> crate::imp::core::ArcResult<String> {
         self.frame.inner_html(self.selector(), timeout).await
     }
+    pub async fn aria_snapshot(&self, timeout: Option<f64>) -> crate::imp::core::ArcResult<String> {
+        self.frame.aria_snapshot(self.selector(), timeout).await
+    }
     pub async fn is_visible(&self, timeout: Option<f64>) -> crate::imp::core::ArcResult<bool> {
         self.frame.is_visible(self.selector(), timeout).await
     }
@@ -178,6 +196,41 @@ impl Locator {
     pub async fn is_checked(&self, timeout: Option<f64>) -> crate::imp::core::ArcResult<bool> {
         self.frame.is_checked(self.selector(), timeout).await
     }
+    pub async fn dispatch_event<T>(
+        &self,
+        r#type: &str,
+        event_init: Option<T>,
+    ) -> crate::imp::core::ArcResult<()>
+    where
+        T: serde::Serialize,
+    {
+        self.frame
+            .dispatch_event(self.selector(), r#type, event_init)
+            .await
+    }
+    /// Drags this element onto `target`, which must be in the same frame.
+    pub async fn drag_to(
+        &self,
+        target: &Locator,
+        options: crate::api::DragOptions,
+    ) -> crate::imp::core::ArcResult<()> {
+        self.frame
+            .drag_and_drop(self.selector(), target.selector(), options)
+            .await
+    }
+
+    /// Taps the element, at `position` relative to its padding box if given. Fails with [`Error::TouchNotEnabled`]
+    /// unless the browser context was created with `has_touch`.
+    pub async fn tap(
+        &self,
+        position: Option<crate::imp::utils::Position>,
+    ) -> crate::imp::core::ArcResult<()> {
+        let mut b = self.tap_builder();
+        if let Some(p) = position {
+            b = b.position(p);
+        }
+        b.tap().await
+    }
 
     pub async fn count(&self) -> crate::imp::core::ArcResult<usize> {
         self.frame
@@ -230,6 +283,98 @@ impl Locator {
     ) -> crate::imp::core::ArcResult<Option<crate::api::ElementHandle>> {
         self.frame.query_selector(self.selector()).await
     }
+
+    /// Returns the buffer with the screenshot of the first matching element, also when `options.path` is set.
+    /// Waits for the element to be visible, respecting `options.timeout`, before capturing it.
+    pub async fn screenshot(
+        &self,
+        options: crate::api::ElementScreenshotOptions,
+    ) -> crate::imp::core::ArcResult<Vec<u8>> {
+        let element = self
+            .first()
+            .resolve(crate::api::frame::FrameState::Visible, options.timeout)
+            .await?;
+        element.screenshot(options).await
+    }
+
+    /// Returns the result of `expression` as a [`JsHandle`](crate::api::JsHandle). The first matching element,
+    /// once attached, is passed as the only argument, e.g. `el => el.parentElement`. Elements are returned as plain
+    /// handles; use [`Locator::evaluate_element_handle`] to act on them.
+    pub async fn evaluate_handle(
+        &self,
+        expression: &str,
+    ) -> crate::imp::core::ArcResult<crate::api::JsHandle> {
+        let element = self
+            .first()
+            .resolve(crate::api::frame::FrameState::Attached, None)
+            .await?;
+        self.frame.evaluate_handle(expression, Some(element)).await
+    }
+
+    /// Same as [`Locator::evaluate_handle`] for expressions resolving to an element.
+    pub async fn evaluate_element_handle(
+        &self,
+        expression: &str,
+    ) -> crate::imp::core::ArcResult<crate::api::ElementHandle> {
+        let element = self
+            .first()
+            .resolve(crate::api::frame::FrameState::Attached, None)
+            .await?;
+        self.frame
+            .evaluate_element_handle(expression, Some(element))
+            .await
+    }
+
+    async fn resolve(
+        &self,
+        state: crate::api::frame::FrameState,
+        timeout: Option<f64>,
+    ) -> crate::imp::core::ArcResult<crate::api::ElementHandle> {
+        let mut b = self
+            .frame
+            .wait_for_selector_builder(self.selector())
+            .state(state);
+        if let Some(t) = timeout {
+            b = b.timeout(t);
+        }
+        Ok(b.wait_for_selector().await?.ok_or(Error::ObjectNotFound)?)
+    }
+
+    /// Returns the computed ARIA role of the element, e.g. `slider` for `<input type=range>`.
+    pub async fn aria_role(&self) -> crate::imp::core::ArcResult<String> {
+        Ok(self.accessible_node().await?.0)
+    }
+
+    /// Returns the accessible name of the element as exposed to assistive technology.
+    pub async fn accessible_name(&self) -> crate::imp::core::ArcResult<String> {
+        Ok(self.accessible_node().await?.1)
+    }
+
+    /// Uses `computedRole`/`computedName` where the engine exposes them, otherwise an
+    /// accessibility snapshot rooted at the element.
+    async fn accessible_node(&self) -> crate::imp::core::ArcResult<(String, String)> {
+        let (role, name) = self
+            .frame
+            .evaluate_on_selector::<_, (Option<String>, Option<String>)>(
+                self.selector(),
+                "el => [el.computedRole ?? null, el.computedName ?? null]",
+                Option::<()>::None,
+            )
+            .await?;
+        if let (Some(role), Some(name)) = (role, name) {
+            return Ok((role, name));
+        }
+        let handle = self.element_handle().await?.ok_or(Error::ObjectNotFound)?;
+        let page = self.frame.page()?.ok_or(Error::ObjectNotFound)?;
+        let snapshot = page
+            .accessibility
+            .snapshot_builder()
+            .try_root(handle)?
+            .interesting_only(false)
+            .snapshot()
+            .await?;
+        Ok(snapshot.map(|s| (s.role, s.name)).unwrap_or_default())
+    }
 }
 
 /// FrameLocator is approximated by chaining selectors; it reuses the underlying Frame.
@@ -276,7 +421,9 @@ impl FrameLocator {
     /// Returns an error if the locator belongs to a different frame.
     pub fn locator_from(&self, locator: &Locator) -> Result<Locator, Error> {
         if locator.frame != self.frame {
-            return Err(Error::InvalidParams);
+            return Err(Error::InvalidParams(
+                "the locator belongs to a different frame".into(),
+            ));
         }
         Ok(self.locator(locator.selector()))
     }
@@ -317,7 +464,10 @@ impl FrameLocator {
     }
 
     pub fn get_by_test_id(&self, test_id: &str) -> Locator {
-        self.locator(&build_test_id_selector(test_id))
+        self.locator(&build_test_id_selector(
+            &self.frame.test_id_attribute(),
+            test_id,
+        ))
     }
 
     /// Returns a locator that resolves to the owning frame element.
@@ -396,8 +546,8 @@ pub(crate) fn build_title_selector(text: &str, exact: bool) -> String {
     selector
 }
 
-pub(crate) fn build_test_id_selector(test_id: &str) -> String {
-    format!("[data-testid=\"{}\"]", escape(test_id))
+pub(crate) fn build_test_id_selector(attribute: &str, test_id: &str) -> String {
+    format!("[{}=\"{}\"]", attribute, escape(test_id))
 }
 
 pub(crate) fn build_role_selector<'a>(role: &str, options: Option<GetByRoleOptions<'a>>) -> String {
@@ -1,34 +1,42 @@
 pub use crate::{
     api::{
         frame::{
-            AddScriptTagBuilder, CheckBuilder, ClickBuilder, DblClickBuilder, FillBuilder,
-            GotoBuilder, HoverBuilder, PressBuilder, SelectOptionBuilder, SetContentBuilder,
-            SetInputFilesBuilder, TapBuilder, TypeBuilder, UncheckBuilder, WaitForFunctionBuilder,
-            WaitForSelectorBuilder,
+            AddScriptTagBuilder, AddStyleTagBuilder, CheckBuilder, ClickBuilder, DblClickBuilder,
+            FillBuilder, GotoBuilder, HoverBuilder, PressBuilder, SelectOptionBuilder,
+            SetCheckedBuilder, SetContentBuilder, SetInputFilesBuilder, TapBuilder, TypeBuilder,
+            UncheckBuilder, WaitForFunctionBuilder, WaitForFunctionOptions,
+            WaitForNavigationOptions, WaitForSelectorBuilder,
         },
-        Download, JsHandle, Request,
+        Download, FileChooser, JsHandle, Request,
     },
-    imp::page::{EventType, Media},
+    imp::page::{EventType, ForcedColors, Media, ReducedMotion, VisionDeficiency},
 };
 use crate::{
     api::{
-        input_device::*, Accessibility, BrowserContext, ConsoleMessage, Dialog, ElementHandle,
-        Frame, FrameLocator, Keyboard, Locator, Response, Route, TouchScreen, Video, WebSocket,
-        WebSocketRoute, Worker,
+        input_device::*, Accessibility, BrowserContext, CDPSession, ConsoleMessage, Dialog,
+        ElementHandle, Frame, FrameLocator, Keyboard, Locator, Response, Route, TouchScreen, Video,
+        WebSocket, WebSocketRoute, Worker,
     },
     imp::{
+        binding_call::binding_handler,
         core::*,
         frame::Frame as FrameImpl,
         page::{EmulateMediaArgs, Evt, Page as Impl, PdfArgs, ReloadArgs, ScreenshotArgs},
         prelude::*,
         utils::{
-            ColorScheme, DocumentLoadState, File, FloatRect, Length, PdfMargins, ScreenshotType,
-            Viewport,
+            glob_to_regex, ColorScheme, DocumentLoadState, File, FloatRect, Length, PdfMargins,
+            Position, ResourceType, ScreenshotAnimations, ScreenshotScale, ScreenshotType,
+            SetInputFilesSource, Viewport,
         },
     },
     Error,
 };
+use futures::channel::{
+    mpsc::{self, UnboundedReceiver},
+    oneshot,
+};
 use regex::Regex;
+use tokio::sync::broadcast::error::RecvError;
 
 /// Page provides methods to interact with a single tab in a `Browser`, or an
 /// [extension background page](https://developer.chrome.com/extensions/background_pages) in Chromium. One `Browser`
@@ -111,6 +119,11 @@ impl Page {
         weak_and_then(&self.inner, |rc| rc.main_frame())
     }
 
+    /// Creates a CDP session attached to this page. Fails with [`Error::ChromiumOnly`] on other browsers.
+    pub async fn new_cdp_session(&self) -> ArcResult<CDPSession> {
+        self.context().new_cdp_session(self).await
+    }
+
     /// The page's main frame. Page is guaranteed to have a main frame which persists during navigations.
     pub fn main_frame(&self) -> Frame {
         Frame::new(self.main_frame_weak())
@@ -130,6 +143,16 @@ impl Page {
         Locator::new(self.main_frame(), selector.to_owned())
     }
 
+    /// Same as [`Page::locator`] followed by [`Locator::filter`], like `page.locator(selector, { has, hasText })`.
+    pub fn locator_with(
+        &self,
+        selector: &str,
+        has: Option<&Locator>,
+        has_text: Option<&str>,
+    ) -> Locator {
+        self.locator(selector).filter(has, has_text)
+    }
+
     /// Create a frame locator (approximate) relative to the main frame.
     pub fn frame_locator(&self, selector: &str) -> FrameLocator {
         FrameLocator::new(self.main_frame(), selector.to_owned())
@@ -183,10 +206,7 @@ impl Page {
     }
 
     pub fn get_by_test_id(&self, test_id: &str) -> Locator {
-        Locator::new(
-            self.main_frame(),
-            crate::api::locator::build_test_id_selector(test_id),
-        )
+        self.main_frame().get_by_test_id(test_id)
     }
 
     /// This method returns all of the dedicated [WebWorkers](https://developer.mozilla.org/en-US/docs/Web/API/Web_Workers_API)
@@ -210,6 +230,9 @@ impl Page {
     /// last redirect. If can not go back, returns `null`.
     ///
     /// Navigate to the previous page in history.
+    ///
+    /// The response may also be `None` when the page is restored from the back-forward cache, which Firefox does
+    /// for most history navigations. Check [`Page::url`] instead when the target matters more than the response.
     pub fn go_back_builder(&self) -> GoBackBuilder {
         GoBackBuilder::new(self.inner.clone())
     }
@@ -217,6 +240,8 @@ impl Page {
     /// last redirect. If can not go forward, returns `null`.
     ///
     /// Navigate to the next page in history.
+    ///
+    /// Like [`Page::go_back_builder`], the response may be `None` after a back-forward cache restore.
     pub fn go_forward_builder(&self) -> GoForwardBuilder {
         GoForwardBuilder::new(self.inner.clone())
     }
@@ -231,10 +256,38 @@ impl Page {
         upgrade(&self.inner)?.set_default_timeout(timeout).await
     }
 
+    /// Runs `f` with the default timeout set to `timeout` and restores the previous default afterwards,
+    /// so that a tightened timeout does not leak into later steps.
+    /// A default inherited from the browser context is restored as an explicit page timeout of the same value.
+    ///
+    /// ```no_run
+    /// # async fn f(page: playwright::api::Page) -> Result<(), playwright::Error> {
+    /// let clicked = page.with_timeout(500, page.click_builder("#maybe").click()).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn with_timeout<F>(&self, timeout: u32, f: F) -> ArcResult<F::Output>
+    where
+        F: std::future::Future,
+    {
+        let previous = upgrade(&self.inner)?.default_timeout();
+        self.set_default_timeout(timeout).await?;
+        let output = f.await;
+        self.set_default_timeout(previous).await?;
+        Ok(output)
+    }
+
     pub fn viewport_size(&self) -> Result<Option<Viewport>, Error> {
         Ok(upgrade(&self.inner)?.viewport_size())
     }
 
+    /// Returns `window.devicePixelRatio` of the main frame, as set by the context's `device_scale_factor`. Screenshots
+    /// in the default [`ScreenshotScale::Device`](crate::api::ScreenshotScale::Device) are this many times larger than
+    /// [`Page::viewport_size`], which is in CSS pixels.
+    pub async fn device_scale_factor(&self) -> ArcResult<f64> {
+        self.eval("() => window.devicePixelRatio").await
+    }
+
     /// In the case of multiple pages in a single browser, each page can have its own viewport size. However,
     /// [`method: Browser.newContext`] allows to set viewport size (and more) for all pages in the context at once.
     ///
@@ -252,6 +305,21 @@ impl Page {
     ///// Returns frame matching the specified criteria. Either `name` or `url` must be specified.
     // fn frame(&self) -> Option<Frame> { unimplemented!() }
 
+    /// Pauses script execution until the user resumes it from the Playwright Inspector.
+    ///
+    /// The Inspector is only available when the driver runs with the `PWDEBUG` environment variable set,
+    /// e.g. `PWDEBUG=1 cargo test`. Without it this returns immediately instead of blocking forever.
+    /// Default timeouts are disabled while paused.
+    pub async fn pause(&self) -> ArcResult<()> {
+        let inner = upgrade(&self.inner)?;
+        match std::env::var("PWDEBUG") {
+            Ok(v) if !v.is_empty() && v != "0" => {}
+            _ => return Ok(()),
+        }
+        let context = upgrade(&inner.browser_context())?;
+        context.pause().await
+    }
+
     /// Brings page to front (activates tab).
     pub async fn bring_to_front(&self) -> ArcResult<()> {
         upgrade(&self.inner)?.bring_to_front().await
@@ -385,6 +453,16 @@ impl Page {
         EmulateMediaBuilder::new(self.inner.clone())
     }
 
+    /// Emulates a vision deficiency through the Chrome DevTools Protocol
+    /// (`Emulation.setEmulatedVisionDeficiency`). Pass [`VisionDeficiency::None`] to reset.
+    ///
+    /// Only available on Chromium; other browsers return [`Error::ChromiumOnly`].
+    pub async fn emulate_vision_deficiency(&self, deficiency: VisionDeficiency) -> ArcResult<()> {
+        upgrade(&self.inner)?
+            .emulate_vision_deficiency(deficiency)
+            .await
+    }
+
     /// Waits for the required load state in the main frame. Defaults to `load` if not specified.
     pub async fn wait_for_load_state(
         &self,
@@ -406,6 +484,14 @@ impl Page {
             .await
     }
 
+    /// Shortcut for main frame's [`Frame::wait_for_navigation`]
+    pub async fn wait_for_navigation(
+        &self,
+        options: WaitForNavigationOptions,
+    ) -> ArcResult<Option<Response>> {
+        self.main_frame().wait_for_navigation(options).await
+    }
+
     /// Returns the opener for popup pages and `null` for others. If the opener has been closed already the returns `null`.
     pub async fn opener(&self) -> ArcResult<Option<Page>> {
         Ok(upgrade(&self.inner)?.opener().await?.map(Page::new))
@@ -414,6 +500,10 @@ impl Page {
     /// The extra HTTP headers will be sent with every request the page initiates.
     ///
     /// > NOTE: [`method: Page.setExtraHTTPHeaders`] does not guarantee the order of headers in the outgoing requests.
+    ///
+    /// Replaces the headers set on this page before. Headers set on the `BrowserContext` are still sent.
+    /// `headers` are passed to the browser in iteration order, so use an ordered collection such as `Vec` rather than
+    /// `HashMap` if the order matters.
     pub async fn set_extra_http_headers<T>(&self, headers: T) -> ArcResult<()>
     where
         T: IntoIterator<Item = (String, String)>,
@@ -421,17 +511,385 @@ impl Page {
         upgrade(&self.inner)?.set_extra_http_headers(headers).await
     }
 
+    /// Adds `headers` to the ones set by [`Page::set_extra_http_headers`] or previous calls of this method, where
+    /// [`Page::set_extra_http_headers`] replaces them all. A header with the same name, compared case-insensitively,
+    /// is overwritten in place, and new headers are appended.
+    pub async fn add_extra_http_headers<T>(&self, headers: T) -> ArcResult<()>
+    where
+        T: IntoIterator<Item = (String, String)>,
+    {
+        upgrade(&self.inner)?.add_extra_http_headers(headers).await
+    }
+
     pub async fn expect_event(&self, evt: EventType) -> Result<Event, Error> {
         let stream = upgrade(&self.inner)?.subscribe_event();
         let timeout = upgrade(&self.inner)?.default_timeout();
+        if evt == EventType::FileChooser {
+            upgrade(&self.inner)?.intercept_file_chooser().await?;
+        }
         expect_event(stream, evt, timeout).await.map(Event::from)
     }
 
+    /// Typed version of [`Page::expect_event`], which returns the payload of the event.
+    ///
+    /// ```ignore
+    /// let (download, _) = tokio::join!(
+    ///     page.wait_for::<page::DownloadEvent>(),
+    ///     page.click_builder("a").click()
+    /// );
+    /// let download: Download = download?;
+    /// ```
+    pub async fn wait_for<E: TypedEvent>(&self) -> Result<E::Payload, Error> {
+        self.expect_event(E::EVENT_TYPE).await.map(E::payload)
+    }
+
     subscribe_event! {}
 
+    /// Calls `handler` with every event of type `event` until the returned [`Subscription`] is dropped or the page is
+    /// closed, like `page.on('console', handler)`. Events are delivered from a spawned task in the order they occurred.
+    ///
+    /// ```ignore
+    /// let _console = page.on(page::EventType::Console, |e| {
+    ///     if let page::Event::Console(msg) = e {
+    ///         println!("{}", msg.text().unwrap_or_default());
+    ///     }
+    /// })?;
+    /// ```
+    pub fn on<F>(&self, event: EventType, mut handler: F) -> Result<Subscription, Error>
+    where
+        F: FnMut(Event) + Send + 'static,
+    {
+        let mut rx = upgrade(&self.inner)?.subscribe_event();
+        let (cancel, mut cancelled) = oneshot::channel::<()>();
+        spawn(async move {
+            loop {
+                let evt = tokio::select! {
+                    _ = &mut cancelled => break,
+                    evt = rx.recv() => evt,
+                };
+                match evt {
+                    Ok(evt) => {
+                        let closed = matches!(evt, Evt::Close);
+                        if evt.event_type() == event {
+                            handler(Event::from(evt));
+                        }
+                        if closed {
+                            break;
+                        }
+                    }
+                    Err(RecvError::Closed) => break,
+                    Err(RecvError::Lagged(_)) => continue,
+                }
+            }
+        });
+        Ok(Subscription { _cancel: cancel })
+    }
+
+    /// Waits for a response of this page matching `matcher` and returns it.
+    /// Unlike [`Page::expect_event`] this ignores responses that don't match, so it can be joined with
+    /// the action that triggers the request.
+    ///
+    /// ```ignore
+    /// let (response, _) = tokio::join!(
+    ///     page.wait_for_response("**/api/login".into(), None),
+    ///     page.click_builder("#submit").click()
+    /// );
+    /// ```
+    pub async fn wait_for_response(
+        &self,
+        matcher: UrlOrPredicate<Response>,
+        timeout: Option<f64>,
+    ) -> ArcResult<Response> {
+        let pick = |evt: &Evt| match evt {
+            Evt::Response(r) => Some(Response::new(r.clone())),
+            _ => None,
+        };
+        self.wait_for_url_event(matcher, timeout, pick, Response::url)
+            .await
+    }
+
+    /// Streams `(url, body)` of the responses of this page matching `matcher` whose content type is JSON, such as
+    /// `application/json` or `application/problem+json`. Responses whose body can't be read or parsed are skipped.
+    /// The stream ends when the page is closed.
+    ///
+    /// ```ignore
+    /// let mut rx = page.capture_json_responses("**/api/**".into())?;
+    /// page.click_builder("#load").click().await?;
+    /// let (url, json) = rx.next().await.unwrap();
+    /// ```
+    pub fn capture_json_responses(
+        &self,
+        matcher: UrlOrPredicate<Response>,
+    ) -> Result<UnboundedReceiver<(String, Value)>, Error> {
+        let matches = matcher.into_fn()?;
+        let mut rx = upgrade(&self.inner)?.subscribe_event();
+        let (tx, captured) = mpsc::unbounded();
+        spawn(async move {
+            loop {
+                let response = match rx.recv().await {
+                    Ok(Evt::Response(r)) => Response::new(r),
+                    Ok(Evt::Close) | Err(RecvError::Closed) => break,
+                    Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                };
+                let is_json = response.headers().is_ok_and(|h| {
+                    h.get("content-type")
+                        .is_some_and(|t| t.to_ascii_lowercase().contains("json"))
+                });
+                let url = match response.url() {
+                    Ok(url) if is_json && matches(&url, &response) => url,
+                    _ => continue,
+                };
+                let json = match response.body().await {
+                    Ok(body) => serde_json::from_slice(&body),
+                    Err(_) => continue,
+                };
+                if let Ok(json) = json {
+                    if tx.unbounded_send((url, json)).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+        Ok(captured)
+    }
+
+    /// Waits for a request of this page matching `matcher` and returns it. Requests issued by other
+    /// pages of the same context are not observed.
+    ///
+    /// ```ignore
+    /// let (request, _) = tokio::join!(
+    ///     page.wait_for_request("**/api/login".into(), None),
+    ///     page.click_builder("#submit").click()
+    /// );
+    /// ```
+    pub async fn wait_for_request(
+        &self,
+        matcher: UrlOrPredicate<Request>,
+        timeout: Option<f64>,
+    ) -> ArcResult<Request> {
+        let pick = |evt: &Evt| match evt {
+            Evt::Request(r) => Some(Request::new(r.clone())),
+            _ => None,
+        };
+        self.wait_for_url_event(matcher, timeout, pick, Request::url)
+            .await
+    }
+
+    /// Waits for a request of this page matching `matcher` to finish, i.e. its response body is downloaded, and returns it.
+    /// The body of [`Request::response`] can be read right away then.
+    ///
+    /// ```ignore
+    /// let (request, _) = tokio::join!(
+    ///     page.expect_request_finished("**/api/data".into(), None),
+    ///     page.click_builder("#load").click()
+    /// );
+    /// let body = request?.response().await?.unwrap().body().await?;
+    /// ```
+    pub async fn expect_request_finished(
+        &self,
+        matcher: UrlOrPredicate<Request>,
+        timeout: Option<f64>,
+    ) -> ArcResult<Request> {
+        let pick = |evt: &Evt| match evt {
+            Evt::RequestFinished(r) => Some(Request::new(r.clone())),
+            _ => None,
+        };
+        self.wait_for_url_event(matcher, timeout, pick, Request::url)
+            .await
+    }
+
+    /// Waits for the first event that `pick` turns into a `T` whose url matches `matcher`.
+    async fn wait_for_url_event<T>(
+        &self,
+        matcher: UrlOrPredicate<T>,
+        timeout: Option<f64>,
+        pick: fn(&Evt) -> Option<T>,
+        url: fn(&T) -> Result<String, Error>,
+    ) -> ArcResult<T> {
+        let inner = upgrade(&self.inner)?;
+        let timeout = timeout.map_or_else(|| inner.default_timeout(), |t| t as u32);
+        let matches = matcher.into_fn()?;
+        let evt = wait_for_event(inner.subscribe_event(), timeout, |evt| {
+            pick(evt).is_some_and(|x| url(&x).is_ok_and(|u| matches(u.as_str(), &x)))
+        })
+        .await?;
+        Ok(pick(&evt).expect("picked by the filter"))
+    }
+
+    /// Waits for a console message of this page for which `predicate` returns true and returns it.
+    ///
+    /// ```ignore
+    /// let (msg, _) = tokio::join!(
+    ///     page.expect_console_message(|m| m.r#type().is_ok_and(|t| t == "error"), None),
+    ///     page.click_builder("#fail").click()
+    /// );
+    /// ```
+    pub async fn expect_console_message<P>(
+        &self,
+        predicate: P,
+        timeout: Option<f64>,
+    ) -> ArcResult<ConsoleMessage>
+    where
+        P: Fn(&ConsoleMessage) -> bool + Send,
+    {
+        let inner = upgrade(&self.inner)?;
+        let timeout = timeout.map_or_else(|| inner.default_timeout(), |t| t as u32);
+        let evt = wait_for_event(inner.subscribe_event(), timeout, |evt| match evt {
+            Evt::Console(c) => predicate(&ConsoleMessage::new(c.clone())),
+            _ => false,
+        })
+        .await?;
+        match evt {
+            Evt::Console(c) => Ok(ConsoleMessage::new(c)),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Runs `action` and waits for the download it starts.
+    /// An error returned by `action` before the download begins is propagated.
+    ///
+    /// ```ignore
+    /// let download = page
+    ///     .expect_download(|| page.click_builder("a").click())
+    ///     .await?;
+    /// ```
+    pub async fn expect_download<F, Fut, T>(&self, action: F) -> ArcResult<Download>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = ArcResult<T>>,
+    {
+        let evt = self
+            .wait_for_event_during(|e| matches!(e, Evt::Download(_)), action)
+            .await?;
+        match evt {
+            Evt::Download(x) => Ok(Download::new(x)),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Runs `action` and returns the popup page it opens.
+    /// An error returned by `action` before the popup appears is propagated.
+    ///
+    /// ```ignore
+    /// let popup = page
+    ///     .expect_popup(|| page.click_builder("a[target=_blank]").click())
+    ///     .await?;
+    /// ```
+    pub async fn expect_popup<F, Fut, T>(&self, action: F) -> ArcResult<Page>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = ArcResult<T>>,
+    {
+        let evt = self
+            .wait_for_event_during(|e| matches!(e, Evt::Popup(_)), action)
+            .await?;
+        match evt {
+            Evt::Popup(x) => Ok(Page::new(x)),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Runs `action` and returns the file chooser it opens.
+    /// File choosers are intercepted from this call on, so no native dialog is shown for this page.
+    ///
+    /// ```ignore
+    /// let file_chooser = page
+    ///     .expect_file_chooser(|| page.click_builder("input[type=file]").click())
+    ///     .await?;
+    /// ```
+    pub async fn expect_file_chooser<F, Fut, T>(&self, action: F) -> ArcResult<FileChooser>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = ArcResult<T>>,
+    {
+        upgrade(&self.inner)?.intercept_file_chooser().await?;
+        let evt = self
+            .wait_for_event_during(|e| matches!(e, Evt::FileChooser(_)), action)
+            .await?;
+        match evt {
+            Evt::FileChooser(x) => Ok(x),
+            _ => unreachable!(),
+        }
+    }
+
+    async fn wait_for_event_during<P, F, Fut, T>(&self, filter: P, action: F) -> ArcResult<Evt>
+    where
+        P: FnMut(&Evt) -> bool,
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = ArcResult<T>>,
+    {
+        let inner = upgrade(&self.inner)?;
+        let timeout = inner.default_timeout();
+        let rx = inner.subscribe_event();
+        let wait = async { wait_for_event(rx, timeout, filter).await.map_err(Arc::new) };
+        let (evt, _) = futures::future::try_join(wait, action()).await?;
+        Ok(evt)
+    }
+
     // coverage
     // expose_binding
-    // expose_function
+
+    /// Adds a function called `name` on the `window` object of every frame in the page. When called, the function executes
+    /// `callback` and returns a Promise which resolves to the return value of `callback`.
+    ///
+    /// The JavaScript arguments are deserialized into `A` as an array, so a tuple or a `Vec` matches the call's arguments.
+    /// Functions installed via [`Page::expose_function`] survive navigations.
+    ///
+    /// ```ignore
+    /// page.expose_function("add", |(a, b): (i32, i32)| async move { a + b })
+    ///     .await?;
+    /// let sum: i32 = page.eval("() => window.add(1, 2)").await?;
+    /// ```
+    pub async fn expose_function<F, Fut, A, R>(&self, name: &str, callback: F) -> ArcResult<()>
+    where
+        F: Fn(A) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = R> + Send + 'static,
+        A: DeserializeOwned + 'static,
+        R: Serialize + 'static,
+    {
+        upgrade(&self.inner)?
+            .expose_binding(name, binding_handler(callback))
+            .await
+    }
+
+    /// Removes a function added with [`Page::expose_function`]. `window[name]` is removed from the current documents and
+    /// from documents created afterwards until `name` is exposed again. Does nothing if `name` is not exposed on this
+    /// page.
+    pub async fn remove_exposed_function(&self, name: &str) -> ArcResult<()> {
+        upgrade(&self.inner)?.remove_binding(name).await
+    }
+
+    /// Registers `handler` to dismiss an overlay such as a cookie consent dialog. Whenever an action like a click is about
+    /// to run and `locator` is visible, the action waits for `handler` to finish, then checks actionability again.
+    /// The handler receives `locator` and is expected to make it hidden.
+    ///
+    /// ```ignore
+    /// page.add_locator_handler(&page.get_by_text("Accept all cookies", false), |banner| async move {
+    ///     let _ = banner.click_builder().click().await;
+    /// })
+    /// .await?;
+    /// ```
+    pub async fn add_locator_handler<F, Fut>(&self, locator: &Locator, handler: F) -> ArcResult<()>
+    where
+        F: Fn(Locator) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = ()> + Send + 'static,
+    {
+        let l = locator.clone();
+        upgrade(&self.inner)?
+            .register_locator_handler(
+                locator.selector(),
+                Arc::new(move || Box::pin(handler(l.clone()))),
+            )
+            .await
+    }
+
+    /// Removes all handlers added with [`Page::add_locator_handler`] for `locator`.
+    pub async fn remove_locator_handler(&self, locator: &Locator) -> ArcResult<()> {
+        upgrade(&self.inner)?
+            .unregister_locator_handlers(locator.selector())
+            .await
+    }
+
     /// Route network requests for this page only.
     pub async fn route<F, Fut>(&self, glob: &str, handler: F) -> ArcResult<()>
     where
@@ -449,6 +907,28 @@ impl Page {
             .await
     }
 
+    /// Aborts every request of this page whose [`Request::resource_type`] is one of `types`, e.g. to skip
+    /// images and fonts for faster tests. Other requests fall back to the remaining route handlers.
+    pub async fn block_resources(&self, types: &[ResourceType]) -> ArcResult<()> {
+        let types = types.to_vec();
+        self.route("**/*", move |route| {
+            let types = types.clone();
+            async move {
+                let blocked = route
+                    .request()
+                    .resource_type()
+                    .map(|t| types.iter().any(|x| x.as_str() == t))
+                    .unwrap_or(false);
+                let _ = if blocked {
+                    route.abort(Some("blockedbyclient")).await
+                } else {
+                    route.fallback().await
+                };
+            }
+        })
+        .await
+    }
+
     /// Add a regex-based route handler for this page.
     pub async fn route_regex<F, Fut>(&self, regex: &Regex, handler: F) -> ArcResult<()>
     where
@@ -522,6 +1002,79 @@ impl Page {
     }
 }
 
+/// Handler registered with [`Page::on`]. It is unsubscribed when this is dropped.
+#[must_use = "the handler is unsubscribed as soon as the subscription is dropped"]
+#[derive(Debug)]
+pub struct Subscription {
+    _cancel: oneshot::Sender<()>,
+}
+
+impl Subscription {
+    /// Same as dropping the subscription.
+    pub fn unsubscribe(self) {}
+}
+
+/// Options of [`Page::drag_and_drop`]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DragOptions {
+    /// A point to grab relative to the top-left corner of the source element's padding box. If not specified, uses some
+    /// visible point of the element.
+    pub source_position: Option<Position>,
+    /// A point to drop at relative to the top-left corner of the target element's padding box. If not specified, uses the
+    /// center of the element.
+    pub target_position: Option<Position>,
+    /// Whether to bypass the actionability checks on the source. Defaults to `false`.
+    pub force: Option<bool>,
+    /// Maximum time in milliseconds to wait for each element.
+    pub timeout: Option<f64>,
+}
+
+/// Former options of [`Page::expect_navigation_on`], now the same type as [`WaitForNavigationOptions`]
+pub type NavigationOptions = WaitForNavigationOptions;
+
+/// Selects a network event by its url or by an arbitrary predicate.
+///
+/// `&str` converts into [`UrlOrPredicate::Glob`] and `&Regex` into [`UrlOrPredicate::Regex`].
+pub enum UrlOrPredicate<T> {
+    /// A glob pattern matched against the whole url, e.g. `**/api/*.json`.
+    Glob(String),
+    /// A regular expression source. Unanchored, like `RegExp.test` in JavaScript.
+    Regex(String),
+    Predicate(Box<dyn Fn(&T) -> bool + Send + Sync>),
+}
+
+impl<T> From<&str> for UrlOrPredicate<T> {
+    fn from(glob: &str) -> Self {
+        Self::Glob(glob.to_owned())
+    }
+}
+
+impl<T> From<&Regex> for UrlOrPredicate<T> {
+    fn from(regex: &Regex) -> Self {
+        Self::Regex(regex.as_str().to_owned())
+    }
+}
+
+type UrlMatcher<T> = Box<dyn Fn(&str, &T) -> bool + Send + Sync>;
+
+impl<T: 'static> UrlOrPredicate<T> {
+    pub(crate) fn into_fn(self) -> Result<UrlMatcher<T>, Error> {
+        let regex =
+            |s: &str| Regex::new(s).map_err(|e| Error::InvalidParams(format!("url regex: {}", e)));
+        Ok(match self {
+            Self::Glob(g) => {
+                let re = regex(&glob_to_regex(&g))?;
+                Box::new(move |url, _| re.is_match(url))
+            }
+            Self::Regex(s) => {
+                let re = regex(&s)?;
+                Box::new(move |url, _| re.is_match(url))
+            }
+            Self::Predicate(f) => Box::new(move |_, x| f(x)),
+        })
+    }
+}
+
 #[derive(Clone)]
 pub enum Event {
     Close,
@@ -564,7 +1117,7 @@ pub enum Event {
     ///  await fileChooser.setFiles('/tmp/myfile.pdf');
     /// });
     /// ```
-    // FileChooser(FileChooser),
+    FileChooser(FileChooser),
     FrameAttached(Frame),
     FrameDetached(Frame),
     FrameNavigated(Frame),
@@ -615,7 +1168,7 @@ impl From<Evt> for Event {
             Evt::Console(x) => Event::Console(ConsoleMessage::new(x)),
             Evt::Dialog(x) => Event::Dialog(Dialog::new(x)),
             Evt::Download(x) => Event::Download(Download::new(x)),
-            // Evt::FileChooser(x) => Event::FileChooser(x),
+            Evt::FileChooser(x) => Event::FileChooser(x),
             Evt::DomContentLoaded => Event::DomContentLoaded,
             Evt::PageError(msg) => Event::PageError(msg),
             Evt::Request(x) => Event::Request(Request::new(x)),
@@ -644,7 +1197,7 @@ impl IsEvent for Event {
             Self::Console(_) => EventType::Console,
             Self::Dialog(_) => EventType::Dialog,
             Self::Download(_) => EventType::Download,
-            // Self::FileChooser(_) => EventType::FileChooser,
+            Self::FileChooser(_) => EventType::FileChooser,
             Self::DomContentLoaded => EventType::DomContentLoaded,
             Self::PageError(_) => EventType::PageError,
             Self::Request(_) => EventType::Request,
@@ -663,6 +1216,63 @@ impl IsEvent for Event {
     }
 }
 
+/// A kind of [`Event`] that [`Page::wait_for`] can wait for, returning its payload directly.
+pub trait TypedEvent {
+    type Payload;
+    const EVENT_TYPE: EventType;
+    /// `e` is always of [`TypedEvent::EVENT_TYPE`].
+    fn payload(e: Event) -> Self::Payload;
+}
+
+macro_rules! typed_event {
+    ($name:ident, $variant:ident) => {
+        #[doc = concat!("[`Event::", stringify!($variant), "`]")]
+        pub struct $name;
+
+        impl TypedEvent for $name {
+            type Payload = ();
+            const EVENT_TYPE: EventType = EventType::$variant;
+            fn payload(_: Event) {}
+        }
+    };
+    ($name:ident, $variant:ident($payload:ty)) => {
+        #[doc = concat!("[`Event::", stringify!($variant), "`]")]
+        pub struct $name;
+
+        impl TypedEvent for $name {
+            type Payload = $payload;
+            const EVENT_TYPE: EventType = EventType::$variant;
+            fn payload(e: Event) -> $payload {
+                match e {
+                    Event::$variant(x) => x,
+                    _ => unreachable!(),
+                }
+            }
+        }
+    };
+}
+
+typed_event!(CloseEvent, Close);
+typed_event!(CrashEvent, Crash);
+typed_event!(ConsoleEvent, Console(ConsoleMessage));
+typed_event!(DialogEvent, Dialog(Dialog));
+typed_event!(DownloadEvent, Download(Download));
+typed_event!(FileChooserEvent, FileChooser(FileChooser));
+typed_event!(DomContentLoadedEvent, DomContentLoaded);
+typed_event!(PageErrorEvent, PageError(String));
+typed_event!(RequestEvent, Request(Request));
+typed_event!(ResponseEvent, Response(Response));
+typed_event!(RequestFailedEvent, RequestFailed(Request));
+typed_event!(RequestFinishedEvent, RequestFinished(Request));
+typed_event!(FrameAttachedEvent, FrameAttached(Frame));
+typed_event!(FrameDetachedEvent, FrameDetached(Frame));
+typed_event!(FrameNavigatedEvent, FrameNavigated(Frame));
+typed_event!(LoadEvent, Load);
+typed_event!(PopupEvent, Popup(Page));
+typed_event!(WebSocketEvent, WebSocket(WebSocket));
+typed_event!(WorkerEvent, Worker(Worker));
+typed_event!(VideoEvent, Video(Video));
+
 macro_rules! is_checked {
     ($f: ident, $c: meta) => {
         #[$c]
@@ -686,6 +1296,91 @@ impl Page {
         self.main_frame().wait_for_selector_builder(selector)
     }
 
+    /// Polls [`Page::query_selector_all`] until at least `min_count` elements match `selector` and returns them.
+    /// Fails with [`Error::Timeout`] after `timeout` milliseconds, which defaults to the page's default timeout. Pass `0`
+    /// to wait forever.
+    pub async fn wait_for_selector_all(
+        &self,
+        selector: &str,
+        min_count: usize,
+        timeout: Option<f64>,
+    ) -> ArcResult<Vec<ElementHandle>> {
+        let timeout = match timeout {
+            Some(t) => t,
+            None => upgrade(&self.inner)?.default_timeout() as f64,
+        };
+        // 0 disables the timeout
+        let deadline = Some(timeout)
+            .filter(|t| *t > 0.)
+            .map(|t| std::time::Instant::now() + std::time::Duration::from_millis(t as u64));
+        loop {
+            let es = self.query_selector_all(selector).await?;
+            if es.len() >= min_count {
+                return Ok(es);
+            }
+            // Release the handles of this poll; otherwise each retry leaks them until navigation
+            for e in es {
+                let _ = e.dispose().await;
+            }
+            if deadline.is_some_and(|d| std::time::Instant::now() >= d) {
+                return Err(Error::Timeout.into());
+            }
+            sleep(std::time::Duration::from_millis(100)).await;
+        }
+    }
+
+    /// Drags the element matching `source` onto the element matching `target` with the mouse: hovers the source,
+    /// presses the left button, moves to the target in several steps and releases the button.
+    ///
+    /// Unlike [`Frame::drag_and_drop`] on [`Page::main_frame`], which lets the driver perform the drag, this goes through
+    /// [`Page::mouse`], so it fires intermediate `mousemove` events and leaves the mouse over the target.
+    pub async fn drag_and_drop(
+        &self,
+        source: &str,
+        target: &str,
+        options: DragOptions,
+    ) -> ArcResult<()> {
+        const STEPS: i32 = 5;
+        let DragOptions {
+            source_position,
+            target_position,
+            force,
+            timeout,
+        } = options;
+        let mut hover = self.hover_builder(source);
+        if let Some(x) = source_position {
+            hover = hover.position(x);
+        }
+        if let Some(x) = force {
+            hover = hover.force(x);
+        }
+        if let Some(x) = timeout {
+            hover = hover.timeout(x);
+        }
+        hover.goto().await?;
+        self.mouse.down(None, None).await?;
+        let dropped = async {
+            let mut wait = self.wait_for_selector_builder(target);
+            if let Some(x) = timeout {
+                wait = wait.timeout(x);
+            }
+            let element = wait
+                .wait_for_selector()
+                .await?
+                .ok_or(Error::ObjectNotFound)?;
+            element.scroll_into_view_if_needed(timeout).await?;
+            let rect = element.bounding_box().await?.ok_or(Error::ObjectNotFound)?;
+            let Position { x, y } = target_position.unwrap_or(Position {
+                x: rect.width / 2.,
+                y: rect.height / 2.,
+            });
+            self.mouse.r#move(rect.x + x, rect.y + y, Some(STEPS)).await
+        }
+        .await;
+        let released = self.mouse.up(None, None).await;
+        dropped.and(released)
+    }
+
     is_checked! {is_checked, doc = "Errors if the element is not a checkbox or radio input."}
     is_checked! {is_disabled, doc = ""}
     is_checked! {is_editable, doc = ""}
@@ -719,6 +1414,14 @@ impl Page {
         self.main_frame().evaluate_js_handle(expression, arg).await
     }
 
+    /// Shortcut for main frame's [`Frame::evaluate_handle`]
+    pub async fn evaluate_handle<T>(&self, expression: &str, arg: Option<T>) -> ArcResult<JsHandle>
+    where
+        T: Serialize,
+    {
+        self.main_frame().evaluate_handle(expression, arg).await
+    }
+
     pub async fn evaluate_element_handle<T>(
         &self,
         expression: &str,
@@ -739,6 +1442,21 @@ impl Page {
         self.main_frame().eval(expression).await
     }
 
+    /// Like [`Page::eval`] but fails with [`Error::Timeout`] if `expression` hasn't resolved within `timeout`
+    /// milliseconds, e.g. a promise that never settles.
+    ///
+    /// The script itself is not interrupted; it keeps running in the page and its result is discarded.
+    pub async fn eval_with_timeout<U>(&self, expression: &str, timeout: f64) -> ArcResult<U>
+    where
+        U: DeserializeOwned,
+    {
+        let sleep = sleep(std::time::Duration::from_millis(timeout as u64));
+        tokio::select! {
+            _ = sleep => Err(Error::Timeout.into()),
+            x = self.eval(expression) => x
+        }
+    }
+
     pub async fn evaluate<T, U>(&self, expression: &str, arg: T) -> ArcResult<U>
     where
         T: Serialize,
@@ -789,6 +1507,10 @@ impl Page {
         self.main_frame().add_style_tag(content, url).await
     }
 
+    pub fn add_style_tag_builder(&self) -> AddStyleTagBuilder<'_, '_> {
+        AddStyleTagBuilder::new(self.main_frame_weak())
+    }
+
     pub fn url(&self) -> Result<String, Error> {
         self.main_frame().url()
     }
@@ -887,6 +1609,8 @@ impl Page {
         self.main_frame().select_option_builder(selector)
     }
 
+    /// Shortcut for main frame's [`Frame::set_input_files_builder`]. Add more files with
+    /// [`SetInputFilesBuilder::add_file`], or clear the input with [`SetInputFilesBuilder::clear_files`].
     pub fn set_input_files_builder<'a>(
         &self,
         selector: &'a str,
@@ -895,6 +1619,27 @@ impl Page {
         self.main_frame().set_input_files_builder(selector, file)
     }
 
+    /// Shortcut for main frame's [`Frame::set_input_paths_builder`]
+    pub fn set_input_paths_builder<'a, P: Into<PathBuf>>(
+        &self,
+        selector: &'a str,
+        path: P,
+    ) -> SetInputFilesBuilder<'a> {
+        self.main_frame().set_input_paths_builder(selector, path)
+    }
+
+    /// Shortcut for main frame's [`Frame::set_input_files`]
+    pub async fn set_input_files(
+        &self,
+        selector: &str,
+        files: Vec<SetInputFilesSource>,
+        timeout: Option<f64>,
+    ) -> ArcResult<()> {
+        self.main_frame()
+            .set_input_files(selector, files, timeout)
+            .await
+    }
+
     pub fn type_builer<'a, 'b>(&self, selector: &'a str, text: &'b str) -> TypeBuilder<'a, 'b> {
         self.main_frame().type_builder(selector, text)
     }
@@ -911,10 +1656,77 @@ impl Page {
         self.main_frame().uncheck_builder(selector)
     }
 
+    /// Shortcut for main frame's [`Frame::set_checked_builder`]
+    pub fn set_checked_builder<'a>(
+        &self,
+        selector: &'a str,
+        checked: bool,
+    ) -> SetCheckedBuilder<'a> {
+        self.main_frame().set_checked_builder(selector, checked)
+    }
+
+    /// Shortcut for main frame's [`Frame::wait_for_function`]
+    pub async fn wait_for_function<T>(
+        &self,
+        expression: &str,
+        arg: Option<T>,
+        options: WaitForFunctionOptions,
+    ) -> ArcResult<JsHandle>
+    where
+        T: Serialize,
+    {
+        self.main_frame()
+            .wait_for_function(expression, arg, options)
+            .await
+    }
+
     pub fn wait_for_function_builder<'a>(&self, expression: &'a str) -> WaitForFunctionBuilder<'a> {
         self.main_frame().wait_for_function_builder(expression)
     }
-    // expect_navigation
+
+    /// Runs `action` and [`Page::wait_for_navigation`] concurrently, e.g. to click a link in a single-page app and wait for
+    /// its route. An error of `action` is returned in preference to a navigation timeout.
+    ///
+    /// ```ignore
+    /// let response = page
+    ///     .expect_navigation(|| link.click_builder().click(), WaitForNavigationOptions::default())
+    ///     .await?;
+    /// ```
+    pub async fn expect_navigation<F, Fut, T>(
+        &self,
+        action: F,
+        options: WaitForNavigationOptions,
+    ) -> ArcResult<Option<Response>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = ArcResult<T>>,
+    {
+        use futures::future::{select, Either};
+        let wait = Box::pin(self.wait_for_navigation(options));
+        match select(wait, Box::pin(action())).await {
+            Either::Left((navigation, action)) => {
+                action.await?;
+                navigation
+            }
+            Either::Right((action, wait)) => {
+                action?;
+                wait.await
+            }
+        }
+    }
+
+    #[deprecated(note = "renamed to `expect_navigation`")]
+    pub async fn expect_navigation_on<F, Fut, T>(
+        &self,
+        trigger: F,
+        options: NavigationOptions,
+    ) -> ArcResult<Option<Response>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = ArcResult<T>>,
+    {
+        self.expect_navigation(trigger, options).await
+    }
 }
 
 macro_rules! navigation {
@@ -942,6 +1754,7 @@ macro_rules! navigation {
                 /// - `'domcontentloaded'` - consider operation to be finished when the `DOMContentLoaded` event is fired.
                 /// - `'load'` - consider operation to be finished when the `load` event is fired.
                 /// - `'networkidle'` - consider operation to be finished when there are no network connections for at least `500` ms.
+                /// - `'commit'` - consider operation to be finished when the network response is received and the document starts loading.
                 wait_until: Option<DocumentLoadState>
             }
         }
@@ -989,6 +1802,7 @@ impl<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i, 'j> PdfBuilder<'a, 'b, 'c, 'd, 'e, 'f,
         /// Paper orientation. Defaults to `false`.
         landscape: Option<bool>,
         /// Paper ranges to print, e.g., '1-5, 8, 11-13'. Defaults to the empty string, which means print all pages.
+        /// Malformed ranges fail with [`Error::InvalidParams`] before anything is printed.
         page_ranges: Option<&'c str>,
         /// Paper format. If set, takes priority over `width` or `height` options. Defaults to 'Letter'.
         format: Option<&'d str>,
@@ -1018,6 +1832,7 @@ impl ScreenshotBuilder {
         Self { inner, args }
     }
 
+    /// Fails with [`Error::InvalidParams`] if both `clip` and `full_page` are set, or if `quality` is set for a `png` image.
     pub async fn screenshot(self) -> ArcResult<Vec<u8>> {
         let Self { inner, args } = self;
         upgrade(&inner)?.screenshot(args).await
@@ -1029,21 +1844,29 @@ impl ScreenshotBuilder {
     }
 
     setter! {
-        /// An object which specifies clipping of the resulting image. Should have the following fields:
+        /// An object which specifies clipping of the resulting image. Can't be combined with `full_page`.
         clip: Option<FloatRect>,
         /// When true, takes a screenshot of the full scrollable page, instead of the currently visible viewport. Defaults to
-        /// `false`.
+        /// `false`. Can't be combined with `clip`.
         full_page: Option<bool>,
         /// Hides default white background and allows capturing screenshots with transparency. Not applicable to `jpeg` images.
         /// Defaults to `false`.
         omit_background: Option<bool>,
+        /// The quality of the image, between 0-100. Not applicable to `png` images.
         quality: Option<i32>,
+        /// When set to `Css`, the screenshot has a single pixel per each css pixel on the page. For high-dpi devices this keeps
+        /// screenshots small and comparable across machines. `Device` produces a single pixel per each device pixel, so
+        /// screenshots of high-dpi devices are twice as large or even larger. Defaults to `Device`.
+        scale: Option<ScreenshotScale>,
+        /// When set to `Disabled`, stops CSS animations, CSS transitions and Web Animations so that animating content is
+        /// captured in a stable state. Defaults to `Allow`.
+        animations: Option<ScreenshotAnimations>,
         /// Maximum time in milliseconds, defaults to 30 seconds, pass `0` to disable timeout. The default value can be changed by
         /// using the [`method: BrowserContext.setDefaultTimeout`] or [`method: Page.setDefaultTimeout`] methods.
         timeout: Option<f64>,
-        /// The file path to save the image to. The screenshot type will be inferred from file extension. If `path` is a relative
-        /// path, then it is resolved relative to the current working directory. If no path is provided, the image won't be saved to
-        /// the disk.
+        /// The file path to save the image to. Unless `type` is set, the screenshot type will be inferred from file extension. If
+        /// `path` is a relative path, then it is resolved relative to the current working directory. If no path is provided, the
+        /// image won't be saved to the disk.
         path: Option<PathBuf>
     }
 
@@ -1051,6 +1874,14 @@ impl ScreenshotBuilder {
         self.args.r#type = None;
         self
     }
+
+    /// Waits for `document.fonts.ready` before capturing so that web fonts that are still loading don't pop in after
+    /// the shot. The wait and the capture share `timeout`: the capture only gets what the wait left over. Defaults to
+    /// `false`.
+    pub fn wait_for_fonts(mut self, x: bool) -> Self {
+        self.args.wait_for_fonts = x;
+        self
+    }
 }
 
 pub struct EmulateMediaBuilder {
@@ -1071,10 +1902,16 @@ impl EmulateMediaBuilder {
 
     setter! {
         /// Emulates `'prefers-colors-scheme'` media feature, supported values are `'light'`, `'dark'`, `'no-preference'`.
-        // NOTE: Not implemented passing `null` disables color scheme emulation
+        /// Passing `null` disables color scheme emulation.
         color_scheme: Option<ColorScheme>,
         /// Changes the CSS media type of the page. The only allowed values are `'screen'`, `'print'` and `null`. Passing `null`
         /// disables CSS media emulation.
-        media: Option<Media>
+        media: Option<Media>,
+        /// Emulates `'prefers-reduced-motion'` media feature, supported values are `'reduce'`, `'no-preference'`. Passing
+        /// `null` disables reduced motion emulation.
+        reduced_motion: Option<ReducedMotion>,
+        /// Emulates `'forced-colors'` media feature, supported values are `'active'` and `'none'`. Passing `null` disables
+        /// forced colors emulation.
+        forced_colors: Option<ForcedColors>
     }
 }
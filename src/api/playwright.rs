@@ -124,7 +124,7 @@ impl Playwright {
     }
 }
 
-async fn initialize_root(conn: &Connection) -> Result<(), Error> {
+pub(crate) async fn initialize_root(conn: &Connection) -> Result<(), Error> {
     let mut params = Map::new();
     // The Playwright driver validates against a fixed set of SDK labels; use
     // "javascript" for compatibility.
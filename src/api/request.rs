@@ -1,6 +1,11 @@
 use crate::{
-    api::{Frame, Response},
-    imp::{core::*, prelude::*, request::Request as Impl, utils::ResponseTiming},
+    api::{Frame, Page, Response},
+    imp::{
+        core::*,
+        prelude::*,
+        request::Request as Impl,
+        utils::{Header, RequestFailure, ResponseTiming},
+    },
 };
 
 /// Whenever the page sends a request for a network resource the following sequence of events are emitted by `Page`:
@@ -63,6 +68,14 @@ impl Request {
         Frame::new(inner)
     }
 
+    /// Returns the `Page` that issued this request. `None` when the request was not issued by a page,
+    /// e.g. requests from service workers.
+    ///
+    /// Useful to tell which page a request observed on the `BrowserContext` belongs to.
+    pub fn page(&self) -> Result<Option<Page>, Error> {
+        Ok(upgrade(&self.inner)?.page().map(Page::new))
+    }
+
     pub fn post_data(&self) -> Result<Option<Vec<u8>>, Error> {
         Ok(upgrade(&self.inner)?.post_data())
     }
@@ -76,6 +89,12 @@ impl Request {
         Ok(upgrade(&self.inner)?.headers().clone())
     }
 
+    /// An array with all the request HTTP headers in the order they were sent. Header names are not lower-cased.
+    /// Unlike [`Request::headers`], this includes headers added by the network stack, such as `cookie`.
+    pub async fn headers_array(&self) -> ArcResult<Vec<Header>> {
+        upgrade(&self.inner)?.headers_array().await
+    }
+
     /// Request that was redirected by the server to this one, if any.
     ///
     /// When the server responds with a redirect, Playwright creates a new `Request` object. The two requests are connected by
@@ -104,6 +123,7 @@ impl Request {
     }
 
     /// Returns the matching `Response` object, or `null` if the response was not received due to error.
+    /// Waits for the response if it has not been received yet.
     pub async fn response(&self) -> Result<Option<Response>, Arc<Error>> {
         Ok(upgrade(&self.inner)?.response().await?.map(Response::new))
     }
@@ -117,7 +137,9 @@ impl Request {
     ///  console.log(request.url() + ' ' + request.failure().errorText);
     /// });
     /// ```
-    pub fn failure(&self) -> Result<Option<String>, Error> {
+    ///
+    /// The browser's error text is classified into a [`RequestErrorCode`](crate::api::RequestErrorCode).
+    pub fn failure(&self) -> Result<Option<RequestFailure>, Error> {
         Ok(upgrade(&self.inner)?.failure())
     }
 
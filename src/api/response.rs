@@ -1,5 +1,5 @@
 use crate::{
-    api::{Frame, Request},
+    api::{Frame, Page, Request},
     imp::{core::*, prelude::*, response::Response as Impl, utils::Header},
 };
 
@@ -40,6 +40,14 @@ impl Response {
         Ok(upgrade(&self.inner)?.ok())
     }
 
+    /// Whether this response was fulfilled by a Service Worker's fetch handler.
+    ///
+    /// The driver doesn't report whether a response came from the HTTP cache, so that can't be told apart
+    /// from a network response here.
+    pub fn from_service_worker(&self) -> Result<bool, Error> {
+        Ok(upgrade(&self.inner)?.from_service_worker())
+    }
+
     pub fn request(&self) -> Request {
         let inner = weak_and_then(&self.inner, |rc| rc.request());
         Request::new(inner)
@@ -59,13 +67,37 @@ impl Response {
         upgrade(&self.inner)?.text().await
     }
 
-    /// Returns the object with HTTP headers associated with the response. All header names are lower-case.
-    pub async fn headers(&self) -> ArcResult<Vec<Header>> {
-        upgrade(&self.inner)?.headers().await
+    /// An object with the response HTTP headers. All header names are lower-case.
+    /// Duplicate headers such as `set-cookie` may be collapsed; use [`Response::headers_array`]
+    /// or [`Response::all_headers`] to see every value.
+    pub fn headers(&self) -> Result<HashMap<String, String>, Error> {
+        Ok(upgrade(&self.inner)?.headers().clone())
+    }
+
+    /// An object with all the response HTTP headers associated with this response. All header names are lower-case.
+    /// Values of headers that appear more than once are joined with `, ` (`\n` for `set-cookie`).
+    pub async fn all_headers(&self) -> ArcResult<HashMap<String, String>> {
+        upgrade(&self.inner)?.all_headers().await
+    }
+
+    /// An array with all the response HTTP headers associated with this response. Header names are not lower-cased.
+    /// Headers with multiple entries, such as `Set-Cookie`, appear in the array multiple times.
+    pub async fn headers_array(&self) -> ArcResult<Vec<Header>> {
+        upgrade(&self.inner)?.headers_array().await
+    }
+
+    /// Returns the value of the header matching the name. The name is case insensitive.
+    pub async fn header_value(&self, name: &str) -> ArcResult<Option<String>> {
+        upgrade(&self.inner)?.header_value(name).await
     }
 
     /// Shortcut for [`Response::request`]'s  [`Request::frame`]
     pub fn frame(&self) -> Frame {
         self.request().frame()
     }
+
+    /// Shortcut for [`Response::request`]'s  [`Request::page`]
+    pub fn page(&self) -> Result<Option<Page>, Error> {
+        self.request().page()
+    }
 }
@@ -53,18 +53,33 @@ impl Selectors {
     /// May only contain `[a-zA-Z0-9_]` characters.
     /// ## script
     /// Script that evaluates to a selector engine instance.
-    /// ## content_script
-    /// Whether to run this selector engine in isolated JavaScript environment. This environment
-    /// has access to the same DOM, but not any JavaScript objects from the frame's scripts.
-    /// Defaults to `false`. Note that running as a content script is not
-    /// guaranteed when this engine is used together with other registered engines.
+    /// ## options
+    /// See [`RegisterOptions`].
+    ///
+    /// Fails with [`Error::SelectorEngineAlreadyRegistered`] if an engine named `name` was registered before.
     pub async fn register(
         &self,
         name: &str,
         script: &str,
-        content_script: bool,
+        options: RegisterOptions,
     ) -> Result<(), Arc<Error>> {
         let inner = upgrade(&self.inner)?;
-        inner.register(name, script, content_script).await
+        inner.register(name, script, options.content_script).await
+    }
+
+    /// Defines custom attribute name to be used in [`Page::get_by_test_id`](crate::api::Page::get_by_test_id).
+    /// `data-testid` is used by default. Applies to the browsers of this [`Playwright`](crate::api::Playwright) only.
+    pub async fn set_test_id_attribute(&self, name: &str) -> Result<(), Arc<Error>> {
+        upgrade(&self.inner)?.set_test_id_attribute(name).await
     }
 }
+
+/// Options of [`Selectors::register`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RegisterOptions {
+    /// Whether to run this selector engine in isolated JavaScript environment. This environment
+    /// has access to the same DOM, but not any JavaScript objects from the frame's scripts.
+    /// Defaults to `false`. Note that running as a content script is not
+    /// guaranteed when this engine is used together with other registered engines.
+    pub content_script: bool,
+}
@@ -1,5 +1,3 @@
-use std::path::Path;
-
 use crate::imp::{
     core::*,
     prelude::*,
@@ -47,9 +45,83 @@ impl Tracing {
         upgrade(&self.inner)?.group(name, location).await
     }
 
+    /// Like [`Tracing::group`], with `url` as the group's location so the trace viewer links the group header to it.
+    pub async fn group_with_url(&self, name: &str, url: &str) -> ArcResult<()> {
+        upgrade(&self.inner)?.group(name, Some(url)).await
+    }
+
     pub async fn group_end(&self) -> ArcResult<()> {
         upgrade(&self.inner)?.group_end().await
     }
+
+    /// Starts tracing and returns a guard that stops it into `stop_path` when dropped.
+    ///
+    /// Dropping can't wait for the trace to be written, so it spawns the stop on the current runtime: the file shows up
+    /// shortly after the drop, errors are lost, and nothing is written if the runtime shuts down first. Dropped outside
+    /// of a runtime, the guard only logs a warning and tracing keeps running. Use [`TracingGuard::stop`] to wait for
+    /// the file and see errors.
+    ///
+    /// ```ignore
+    /// let _guard = context.tracing()?.scope(TracingStartOptions::default(), "trace.zip".into()).await?;
+    /// page.goto_builder(url).goto().await?;
+    /// // stopped here, even on an early `?` return
+    /// ```
+    pub async fn scope(
+        &self,
+        options: StartOptions<'_, '_>,
+        stop_path: PathBuf,
+    ) -> ArcResult<TracingGuard> {
+        self.start(options).await?;
+        Ok(TracingGuard {
+            tracing: Some(self.clone()),
+            path: stop_path,
+        })
+    }
+}
+
+/// Returned by [`Tracing::scope`]
+#[must_use]
+pub struct TracingGuard {
+    /// `None` once stopped
+    tracing: Option<Tracing>,
+    path: PathBuf,
+}
+
+impl TracingGuard {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Stops tracing and waits for the trace to be written.
+    pub async fn stop(mut self) -> ArcResult<()> {
+        let tracing = self.tracing.take().expect("stopped only once");
+        let path = Some(self.path.as_path());
+        tracing.stop(StopOptions { path }).await
+    }
+}
+
+impl Drop for TracingGuard {
+    fn drop(&mut self) {
+        if let Some(tracing) = self.tracing.take() {
+            let path = std::mem::take(&mut self.path);
+            #[cfg(any(feature = "rt-tokio", feature = "rt-actix"))]
+            let in_runtime = tokio::runtime::Handle::try_current().is_ok();
+            // tasks run on the global executor
+            #[cfg(feature = "rt-async-std")]
+            let in_runtime = true;
+            if !in_runtime {
+                log::warn!(
+                    "TracingGuard dropped outside of a runtime, {} is not written",
+                    path.display()
+                );
+                return;
+            }
+            spawn(async move {
+                let path = Some(path.as_path());
+                let _ = tracing.stop(StopOptions { path }).await;
+            });
+        }
+    }
 }
 
 #[derive(Default)]
@@ -10,17 +10,23 @@ impl Video {
         Self { inner }
     }
 
+    /// Returns the file system path this video will be recorded to. The video is guaranteed to be written to the filesystem
+    /// upon closing the browser context. Fails with [`Error::RemoteArtifact`] when the browser was obtained through
+    /// `BrowserType::connect`; use [`Video::save_as`] instead.
     pub fn path(&self) -> Result<PathBuf, Error> {
         self.inner.path()
     }
 
-    // doesn't work with this version
-    async fn save_as<P: AsRef<Path>>(&self, path: P) -> ArcResult<()> {
+    /// Saves the video to a user-specified path. It is safe to call this method while the video is still in progress, or after
+    /// the page has closed. This method waits until the page is closed and the video is fully saved.
+    ///
+    /// The file is streamed over the connection, so this also works with remote browsers.
+    pub async fn save_as<P: AsRef<Path>>(&self, path: P) -> ArcResult<()> {
         self.inner.save_as(path).await
     }
 
-    // doesn't work with this version
-    async fn delete(&self) -> ArcResult<()> {
+    /// Deletes the video file. Will wait for the video to finish if necessary.
+    pub async fn delete(&self) -> ArcResult<()> {
         self.inner.delete().await
     }
 }
@@ -38,7 +38,9 @@ impl WebSocketRoute {
 
     pub async fn connect_to_server(&self) -> ArcResult<WebSocketRoute> {
         if self.side == Side::Server {
-            return Err(Arc::new(Error::InvalidParams));
+            return Err(Arc::new(Error::InvalidParams(
+                "connect_to_server is called on the page side of a route".into(),
+            )));
         }
         upgrade(&self.inner)?.connect_to_server().await?;
         Ok(WebSocketRoute::new(self.inner.clone(), Side::Server))
@@ -1,3 +1,4 @@
+pub use crate::imp::worker::EventType;
 use crate::{
     api::JsHandle,
     imp::{
@@ -77,6 +78,7 @@ impl Worker {
         upgrade(&self.inner)?.evaluate(expression, arg).await
     }
 
+    // `close` is emitted when the worker is gone, e.g. after its page navigated away.
     subscribe_event! {}
 }
 
@@ -22,9 +22,19 @@ pub(crate) mod prelude {
     #[cfg(feature = "rt-async-std")]
     pub use async_std::{task::sleep, task::spawn};
     #[cfg(feature = "rt-tokio")]
-    pub use tokio::{task::spawn, task::JoinError, time::sleep};
+    pub use tokio::{task::spawn, task::spawn_blocking, task::JoinError, time::sleep};
     #[cfg(feature = "rt-actix")]
-    pub use tokio::{task::spawn, task::JoinError, time::sleep};
+    pub use tokio::{task::spawn, task::spawn_blocking, task::JoinError, time::sleep};
+
+    /// Runs `f` on a thread where blocking is allowed. Never fails, unlike the tokio counterpart.
+    #[cfg(feature = "rt-async-std")]
+    pub async fn spawn_blocking<F, T>(f: F) -> Result<T, JoinError>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        Ok(async_std::task::spawn_blocking(f).await)
+    }
 
     pub(crate) trait RemoveOne<T> {
         fn remove_one<F>(&mut self, f: F)
@@ -67,7 +77,7 @@ mod macros {
             let r = $r.channel().create_request(m).set_args($args)?;
             let fut = $r.channel().send_message(r).await?;
             let res = fut.await?;
-            let res = res.map_err(Error::ErrorResponded)?;
+            let res = res.map_err(Error::responded)?;
             res
         }};
     }
@@ -106,6 +116,7 @@ pub(crate) mod element_handle;
 pub(crate) mod file_hooser;
 pub(crate) mod frame;
 pub(crate) mod js_handle;
+pub(crate) mod local_utils;
 pub(crate) mod page;
 pub(crate) mod request;
 pub(crate) mod response;
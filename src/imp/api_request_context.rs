@@ -15,7 +15,7 @@ impl APIRequestContext {
     /// Low-level fetch that mirrors the driver API.
     pub(crate) async fn fetch(&self, args: FetchArgs) -> ArcResult<APIResponsePayload> {
         let v = send_message!(self, "fetch", args);
-        let response = v.get("response").ok_or(Error::InvalidParams)?.clone();
+        let response = v.get("response").ok_or(Error::InvalidMessage)?.clone();
         let payload: APIResponsePayload = serde_json::from_value(response).map_err(Error::Serde)?;
         Ok(payload)
     }
@@ -30,7 +30,7 @@ impl APIRequestContext {
         let b64 = v
             .get("binary")
             .and_then(|v| v.as_str())
-            .ok_or(Error::InvalidParams)?;
+            .ok_or(Error::InvalidMessage)?;
         let data = general_purpose::STANDARD
             .decode(b64)
             .map_err(|e| Arc::new(Error::InvalidBase64(e)))?;
@@ -47,7 +47,7 @@ impl APIRequestContext {
         let entries = v
             .get("log")
             .and_then(|v| v.as_array())
-            .ok_or(Error::InvalidParams)?;
+            .ok_or(Error::InvalidMessage)?;
         Ok(entries
             .iter()
             .filter_map(|v| v.as_str().map(|s| s.to_owned()))
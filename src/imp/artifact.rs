@@ -36,8 +36,18 @@ impl Artifact {
         Ok(())
     }
 
+    /// Local artifacts are copied by the driver. Artifacts of a remote browser are
+    /// pumped over the connection in chunks since the driver's file system is not ours.
     pub(crate) async fn save_as<P: AsRef<Path>>(&self, path: P) -> ArcResult<()> {
         let path = path.as_ref();
+        if !self.is_remote() {
+            #[derive(Serialize)]
+            struct Args<'a> {
+                path: &'a Path,
+            }
+            let _ = send_message!(self, "saveAs", Args { path });
+            return Ok(());
+        }
         let dir = path
             .parent()
             .ok_or_else(|| Error::ResolvePath(path.into()))?;
@@ -49,6 +59,19 @@ impl Artifact {
         Ok(())
     }
 
+    pub(crate) async fn cancel(&self) -> ArcResult<()> {
+        let _ = send_message!(self, "cancel", Map::new());
+        Ok(())
+    }
+
+    /// Reads the whole artifact over the connection without touching the local file system.
+    pub(crate) async fn read_all(&self) -> ArcResult<Vec<u8>> {
+        let res = send_message!(self, "stream", Map::new());
+        let guid = only_guid(&res)?;
+        let stream = get_object!(self.context()?.lock().unwrap(), guid, Stream)?;
+        upgrade(&stream)?.read_to_end().await
+    }
+
     pub(crate) async fn failure(&self) -> ArcResult<Option<String>> {
         let v = send_message!(self, "failure", Map::new());
         let msg = maybe_only_str(&v)?;
@@ -58,11 +81,11 @@ impl Artifact {
 
 // mutable
 impl Artifact {
-    fn set_is_remote(&self, x: bool) {
+    pub(crate) fn set_is_remote(&self, x: bool) {
         self.var.lock().unwrap().is_remote = x;
     }
 
-    fn is_remote(&self) -> bool {
+    pub(crate) fn is_remote(&self) -> bool {
         self.var.lock().unwrap().is_remote
     }
 }
@@ -1,13 +1,130 @@
-use crate::imp::core::*;
+use crate::imp::{core::*, prelude::*};
+use futures::future::{BoxFuture, FutureExt};
+
+/// Receives the call arguments as a serialized array and returns a serialized argument
+/// to resolve the call with, or an error message to reject it with.
+pub(crate) type BindingHandler =
+    Arc<dyn Fn(Value) -> BoxFuture<'static, Result<Value, String>> + Send + Sync + 'static>;
+
+pub(crate) fn binding_handler<F, Fut, A, R>(f: F) -> BindingHandler
+where
+    F: Fn(A) -> Fut + Send + Sync + 'static,
+    Fut: std::future::Future<Output = R> + Send + 'static,
+    A: DeserializeOwned + 'static,
+    R: Serialize + 'static,
+{
+    Arc::new(move |args: Value| match de::from_value::<A>(&args) {
+        Ok(a) => {
+            let fut = f(a);
+            async move { ser::to_value(&fut.await).map_err(|e| e.to_string()) }.boxed()
+        }
+        Err(e) => {
+            let msg = e.to_string();
+            async move { Err(msg) }.boxed()
+        }
+    })
+}
+
+/// Returns a script that moves `window[name]` aside so that [`restore_script`] can put it back.
+/// The driver can't uninstall a binding, so removing one hides it instead.
+pub(crate) fn hide_script(name: &str) -> Result<String, Error> {
+    let name = serde_json::to_string(name)?;
+    Ok(format!(
+        "(() => {{ const s = globalThis[Symbol.for('playwright-rust.bindings')] ??= {{}}; \
+         if ({0} in globalThis) {{ s[{0}] = globalThis[{0}]; delete globalThis[{0}]; }} }})();",
+        name
+    ))
+}
+
+/// Returns a script that undoes [`hide_script`].
+pub(crate) fn restore_script(name: &str) -> Result<String, Error> {
+    let name = serde_json::to_string(name)?;
+    Ok(format!(
+        "(() => {{ const s = globalThis[Symbol.for('playwright-rust.bindings')] ?? {{}}; \
+         if ({0} in s) {{ globalThis[{0}] = s[{0}]; delete s[{0}]; }} }})();",
+        name
+    ))
+}
+
+/// Runs `handler` with the arguments of `call` and settles the call with its result.
+pub(crate) fn dispatch(call: Weak<BindingCall>, handler: BindingHandler) {
+    tokio::spawn(async move {
+        let call = match call.upgrade() {
+            Some(c) => c,
+            None => return,
+        };
+        let _ = match handler(call.args()).await {
+            Ok(result) => call.resolve(result).await,
+            Err(msg) => call.reject(&msg).await,
+        };
+    });
+}
 
 #[derive(Debug)]
 pub(crate) struct BindingCall {
     channel: ChannelOwner,
+    name: String,
+    args: Vec<Value>,
 }
 
 impl BindingCall {
-    pub(crate) fn new(channel: ChannelOwner) -> Self {
-        Self { channel }
+    pub(crate) fn try_new(channel: ChannelOwner) -> Result<Self, Error> {
+        let Initializer { name, args } = serde_json::from_value(channel.initializer.clone())?;
+        Ok(Self {
+            channel,
+            name,
+            args: args.unwrap_or_default(),
+        })
+    }
+
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Arguments as serialized values. Wrapped in an array value so that they can be
+    /// deserialized at once.
+    pub(crate) fn args(&self) -> Value {
+        let mut m = Map::new();
+        m.insert("a".into(), Value::Array(self.args.clone()));
+        m.into()
+    }
+
+    /// `result` is a serialized argument as produced by `ser::to_value`.
+    pub(crate) async fn resolve(&self, result: Value) -> ArcResult<()> {
+        #[derive(Serialize)]
+        struct Args {
+            result: Value,
+        }
+        let _ = send_message!(self, "resolve", Args { result });
+        Ok(())
+    }
+
+    pub(crate) async fn reject(&self, message: &str) -> ArcResult<()> {
+        #[derive(Serialize)]
+        struct Args<'a> {
+            error: SerializedError<'a>,
+        }
+        #[derive(Serialize)]
+        struct SerializedError<'a> {
+            error: ErrorBody<'a>,
+        }
+        #[derive(Serialize)]
+        struct ErrorBody<'a> {
+            name: &'a str,
+            message: &'a str,
+            stack: &'a str,
+        }
+        let args = Args {
+            error: SerializedError {
+                error: ErrorBody {
+                    name: "Error",
+                    message,
+                    stack: "",
+                },
+            },
+        };
+        let _ = send_message!(self, "reject", args);
+        Ok(())
     }
 }
 
@@ -19,3 +136,10 @@ impl RemoteObject for BindingCall {
         &mut self.channel
     }
 }
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Initializer {
+    name: String,
+    args: Option<Vec<Value>>,
+}
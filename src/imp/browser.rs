@@ -4,13 +4,17 @@ use crate::imp::{
     browser_type::{RecordHar, RecordVideo},
     core::*,
     prelude::*,
-    utils::{ColorScheme, Geolocation, HttpCredentials, ProxySettings, StorageState, Viewport},
+    utils::{
+        ColorScheme, Geolocation, HttpCredentials, ProxySettings, ServiceWorkerPolicy,
+        StorageState, Viewport,
+    },
 };
 use tokio::sync::oneshot;
 
 #[derive(Debug)]
 pub(crate) struct Browser {
     channel: ChannelOwner,
+    name: String,
     version: String,
     var: Mutex<Variable>,
 }
@@ -30,9 +34,10 @@ pub(crate) struct Variable {
 
 impl Browser {
     pub(crate) fn try_new(channel: ChannelOwner) -> Result<Self, Error> {
-        let Initializer { version } = serde_json::from_value(channel.initializer.clone())?;
+        let Initializer { name, version } = serde_json::from_value(channel.initializer.clone())?;
         Ok(Self {
             channel,
+            name,
             version,
             var: Mutex::new(Variable {
                 contexts: Vec::new(),
@@ -45,6 +50,11 @@ impl Browser {
         &self.version
     }
 
+    /// `chromium`, `firefox` or `webkit`
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+
     pub(crate) async fn close(&self) -> Result<(), Arc<Error>> {
         let _ = send_message!(self, "close", Map::new());
         Ok(())
@@ -129,7 +139,7 @@ impl Browser {
         match outcome {
             Ok(Either::Result(res)) => {
                 let res = res?;
-                let res = res.map_err(Error::ErrorResponded)?;
+                let res = res.map_err(Error::responded)?;
                 let guid = only_guid(&*res)?;
                 let c = get_object!(self.context()?.lock().unwrap(), guid, BrowserContext)?;
                 self.register_new_context(c.clone())?;
@@ -230,6 +240,8 @@ impl RemoteObject for Browser {
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct Initializer {
+    #[serde(default)]
+    name: String,
     version: String,
 }
 
@@ -267,6 +279,7 @@ pub(crate) struct NewContextArgs<'e, 'f, 'g, 'h, 'i, 'j, 'k> {
     pub(crate) chromium_sandbox: Option<bool>,
     pub(crate) record_video: Option<RecordVideo<'j>>,
     pub(crate) record_har: Option<RecordHar<'k>>,
+    pub(crate) service_workers: Option<ServiceWorkerPolicy>,
 
     pub(crate) storage_state: Option<StorageState>,
 }
@@ -298,6 +311,7 @@ impl<'e, 'f, 'g, 'h, 'i, 'j, 'k> Default for NewContextArgs<'e, 'f, 'g, 'h, 'i,
             chromium_sandbox: None,
             record_video: None,
             record_har: None,
+            service_workers: None,
             storage_state: None,
         }
     }
@@ -1,19 +1,22 @@
 use crate::imp::{
     api_request_context::APIRequestContext,
+    binding_call::{self, BindingCall, BindingHandler},
     browser::Browser,
     cdp_session::CDPSession,
     console_message::ConsoleMessage,
     core::*,
     frame::Frame,
+    local_utils::{HarAction, HarLookupArgs, LocalUtils},
     page::Page,
     prelude::*,
     request::Request,
     response::Response,
-    route::Route,
+    route::{FulfillArgs, Route},
     tracing::Tracing,
     utils::{Cookie, Geolocation, Header, StorageState},
     web_error::WebError,
     websocket_route::WebSocketRoute,
+    worker::Worker,
 };
 use futures::future::BoxFuture;
 use regex::Regex;
@@ -59,12 +62,39 @@ pub(crate) struct BrowserContext {
 pub(crate) struct Variable {
     browser: Option<Weak<Browser>>,
     pages: Vec<Weak<Page>>,
+    service_workers: Vec<Weak<Worker>>,
     timeout: Option<u32>,
     navigation_timeout: Option<u32>,
     routes: Vec<RouteEntry>,
     websocket_routes: Vec<WebSocketRouteEntry>,
     tracing: Option<Weak<Tracing>>,
     request_context: Option<Weak<APIRequestContext>>,
+    bindings: HashMap<String, BindingHandler>,
+    /// Exposed to the driver but hidden by `remove_binding`
+    hidden_bindings: Vec<String>,
+    har_routers: Vec<String>,
+    har_recorders: Vec<(String, PathBuf)>,
+    /// `None` if the driver doesn't report the context options.
+    has_touch: Option<bool>,
+    /// Set for persistent contexts, which have no browser
+    browser_type_name: Option<String>,
+}
+
+/// What to do with requests that match the url pattern but have no entry in the HAR.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HarNotFound {
+    #[default]
+    Abort,
+    Fallback,
+}
+
+impl HarNotFound {
+    async fn handle(self, route: &Route) -> ArcResult<()> {
+        match self {
+            Self::Abort => route.abort(None).await,
+            Self::Fallback => route.fallback().await,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -84,17 +114,17 @@ struct InnerError {
 fn guid_from_keys(params: &Map<String, Value>, keys: &[&str]) -> Result<OnlyGuid, Error> {
     for key in keys {
         if let Some(v) = params.get(*key) {
-            return serde_json::from_value(v.clone()).map_err(|_| Error::InvalidParams);
+            return serde_json::from_value(v.clone()).map_err(|_| Error::InvalidMessage);
         }
     }
 
     if params.len() == 1 {
         if let Some(first) = first_object(params) {
-            return serde_json::from_value((*first).clone()).map_err(|_| Error::InvalidParams);
+            return serde_json::from_value((*first).clone()).map_err(|_| Error::InvalidMessage);
         }
     }
 
-    Err(Error::InvalidParams)
+    Err(Error::InvalidMessage)
 }
 
 fn format_error_value(v: &Value) -> Result<String, Error> {
@@ -127,6 +157,47 @@ fn format_error_value(v: &Value) -> Result<String, Error> {
     }
 }
 
+async fn serve_from_har(
+    utils: &Weak<LocalUtils>,
+    har_id: &str,
+    not_found: HarNotFound,
+    route: &Route,
+) -> ArcResult<()> {
+    let utils = upgrade(utils)?;
+    let request = upgrade(&route.request())?;
+    let args = HarLookupArgs {
+        har_id,
+        url: request.url(),
+        method: request.method(),
+        headers: request.headers_array().await?,
+        post_data: request.post_data_base64().map(str::to_owned),
+        is_navigation_request: request.is_navigation_request(),
+    };
+    let res = utils.har_lookup(args).await?;
+    match res.action {
+        HarAction::Redirect => {
+            let url = res.redirect_url.as_deref().unwrap_or_default();
+            return route.redirect_navigation_request(url).await;
+        }
+        HarAction::Fulfill => {
+            let body = res.body.unwrap_or_default();
+            let mut args = FulfillArgs::new(&body, true);
+            args.status = res.status;
+            args.headers = res.headers;
+            return route.fulfill(args).await;
+        }
+        HarAction::Error => {
+            log::debug!(
+                "HAR lookup failed for {}: {}",
+                request.url(),
+                res.message.as_deref().unwrap_or_default()
+            );
+        }
+        HarAction::Noentry => {}
+    }
+    not_found.handle(route).await
+}
+
 impl BrowserContext {
     const DEFAULT_TIMEOUT: u32 = 30000;
 
@@ -139,6 +210,7 @@ impl BrowserContext {
         let Initializer {
             tracing,
             request_context,
+            options,
         } = serde_json::from_value(channel.initializer.clone())?;
         let browser = match &channel.parent {
             Some(RemoteWeak::Browser(b)) => Some(b.clone()),
@@ -162,6 +234,7 @@ impl BrowserContext {
                 };
                 get_object!(ctx_locked, &guid, APIRequestContext).ok()
             }),
+            has_touch: options.map(|o| o.has_touch),
             ..Variable::default()
         });
         let ctx = Self {
@@ -183,6 +256,18 @@ impl BrowserContext {
         Ok(p)
     }
 
+    /// CDP is only spoken by Chromium. A persistent context has no browser to ask, so the driver reports it then.
+    fn ensure_chromium(&self) -> Result<(), Error> {
+        let name = match self.browser().and_then(|b| b.upgrade()) {
+            Some(browser) => Some(browser.name().to_owned()),
+            None => self.var.lock().unwrap().browser_type_name.clone(),
+        };
+        match name {
+            Some(name) if name != "chromium" => Err(Error::ChromiumOnly("CDP session")),
+            _ => Ok(()),
+        }
+    }
+
     pub(crate) async fn new_cdp_session_with_page(
         &self,
         page: Weak<Page>,
@@ -192,6 +277,7 @@ impl BrowserContext {
         struct Args {
             page: OnlyGuid,
         }
+        self.ensure_chromium()?;
         let page = upgrade(&page)?;
         let args = Args {
             page: OnlyGuid {
@@ -199,7 +285,7 @@ impl BrowserContext {
             },
         };
         let res = send_message!(self, "newCDPSession", args);
-        let session = res.get("session").ok_or(Error::InvalidParams)?;
+        let session = res.get("session").ok_or(Error::InvalidMessage)?;
         let guid = only_guid(session)?;
         let session = get_object!(self.context()?.lock().unwrap(), guid, CDPSession)?;
         Ok(session)
@@ -214,6 +300,7 @@ impl BrowserContext {
         struct Args {
             frame: OnlyGuid,
         }
+        self.ensure_chromium()?;
         let frame = upgrade(&frame)?;
         let args = Args {
             frame: OnlyGuid {
@@ -221,20 +308,41 @@ impl BrowserContext {
             },
         };
         let res = send_message!(self, "newCDPSession", args);
-        let session = res.get("session").ok_or(Error::InvalidParams)?;
+        let session = res.get("session").ok_or(Error::InvalidMessage)?;
         let guid = only_guid(session)?;
         let session = get_object!(self.context()?.lock().unwrap(), guid, CDPSession)?;
         Ok(session)
     }
 
     pub(crate) async fn close(&self) -> Result<(), Arc<Error>> {
+        let (routers, recorders) = {
+            let mut var = self.var.lock().unwrap();
+            (
+                std::mem::take(&mut var.har_routers),
+                std::mem::take(&mut var.har_recorders),
+            )
+        };
+        let mut export_error = None;
+        for (har_id, path) in recorders {
+            if let Err(e) = self.export_har(&har_id, &path).await {
+                export_error.get_or_insert(e);
+            }
+        }
+        if let Some(utils) = self.local_utils().ok().and_then(|u| u.upgrade()) {
+            for har_id in routers {
+                let _ = utils.har_close(&har_id).await;
+            }
+        }
         if let Some(rc) = self.request_context() {
             if let Some(rc) = rc.upgrade() {
                 let _ = rc.dispose(None).await;
             }
         }
         let _ = send_message!(self, "close", Map::new());
-        Ok(())
+        match export_error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
     }
 
     pub(crate) async fn storage_state(&self) -> ArcResult<StorageState> {
@@ -256,7 +364,7 @@ impl BrowserContext {
         }
         let args = Args { urls };
         let v = send_message!(self, "cookies", args);
-        let cookies = first(&v).ok_or(Error::InvalidParams)?;
+        let cookies = first(&v).ok_or(Error::InvalidMessage)?;
         let cs: Vec<Cookie> = serde_json::from_value((*cookies).clone()).map_err(Error::Serde)?;
         Ok(cs)
     }
@@ -267,6 +375,9 @@ impl BrowserContext {
         struct Args<'a> {
             cookies: &'a [Cookie],
         }
+        for cookie in cookies {
+            cookie.validate()?;
+        }
         let args = Args { cookies };
         let _ = send_message!(self, "addCookies", args);
         Ok(())
@@ -310,6 +421,101 @@ impl BrowserContext {
         self.set_network_interception_patterns(&patterns).await
     }
 
+    /// Serves requests matching `url` (all requests by default) from the HAR at `har`.
+    /// With `update`, requests hit the network instead and the HAR is rewritten from them
+    /// when the context is closed.
+    pub(crate) async fn route_from_har(
+        &self,
+        har: &Path,
+        url: Option<&str>,
+        not_found: HarNotFound,
+        update: bool,
+    ) -> ArcResult<()> {
+        if update {
+            return self.record_into_har(har, url).await;
+        }
+        if !har.is_file() {
+            let msg = format!("HAR file not found: {:?}", har);
+            return Err(Error::InvalidParams(msg).into());
+        }
+        let utils = self.local_utils()?;
+        let har_id = upgrade(&utils)?.har_open(har).await?;
+        self.var.lock().unwrap().har_routers.push(har_id.clone());
+        let handler: RouteHandler = Arc::new(move |route: Arc<Route>| {
+            let utils = utils.clone();
+            let har_id = har_id.clone();
+            Box::pin(async move {
+                if let Err(e) = serve_from_har(&utils, &har_id, not_found, &route).await {
+                    log::debug!("route_from_har: {}", e);
+                    // don't leave the request hanging
+                    let _ = not_found.handle(&route).await;
+                }
+            }) as BoxFuture<'static, ()>
+        });
+        self.route(url.unwrap_or("**/*"), handler).await
+    }
+
+    async fn record_into_har(&self, har: &Path, url: Option<&str>) -> ArcResult<()> {
+        #[skip_serializing_none]
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct RecordHarOptions<'a> {
+            path: &'a Path,
+            content: &'a str,
+            mode: &'a str,
+            url_glob: Option<&'a str>,
+        }
+        #[derive(Serialize)]
+        struct Args<'a> {
+            options: RecordHarOptions<'a>,
+        }
+        let options = RecordHarOptions {
+            path: har,
+            content: "embed",
+            mode: "minimal",
+            url_glob: url,
+        };
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct HarStartResult {
+            har_id: String,
+        }
+        let v = send_message!(self, "harStart", Args { options });
+        let HarStartResult { har_id } =
+            serde_json::from_value((*v).clone()).map_err(Error::Serde)?;
+        self.var
+            .lock()
+            .unwrap()
+            .har_recorders
+            .push((har_id, har.to_owned()));
+        Ok(())
+    }
+
+    async fn export_har(&self, har_id: &str, path: &Path) -> ArcResult<()> {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Args<'a> {
+            har_id: &'a str,
+        }
+        let v = send_message!(self, "harExport", Args { har_id });
+        let guid = only_guid(&v)?;
+        let artifact = get_object!(self.context()?.lock().unwrap(), guid, Artifact)?;
+        let artifact = upgrade(&artifact)?;
+        artifact.save_as(path).await?;
+        let _ = artifact.delete().await;
+        Ok(())
+    }
+
+    fn local_utils(&self) -> Result<Weak<LocalUtils>, Error> {
+        let objs = self.context()?.lock().unwrap().list_objects();
+        objs.into_iter()
+            .find_map(|o| match o {
+                RemoteArc::LocalUtils(u) => Some(Arc::downgrade(&u)),
+                _ => None,
+            })
+            .ok_or(Error::ObjectNotFound)
+    }
+
     pub(crate) async fn route_regex(
         &self,
         regex_source: &str,
@@ -456,15 +662,104 @@ impl BrowserContext {
         Ok(())
     }
 
-    // async def expose_binding(
-    // async def expose_function(self, name: str, callback: Callable) -> None:
+    /// Installs `window[name]` in every frame of every page in this context, including
+    /// pages opened later. The driver re-installs it after each navigation. A binding hidden
+    /// by `remove_binding` is still known to the driver and is only shown again.
+    pub(crate) async fn expose_binding(
+        &self,
+        name: &str,
+        handler: BindingHandler,
+    ) -> ArcResult<()> {
+        #[derive(Serialize)]
+        struct Args<'a> {
+            name: &'a str,
+        }
+        let hidden = {
+            let hidden_bindings = &mut self.var.lock().unwrap().hidden_bindings;
+            let i = hidden_bindings.iter().position(|n| n == name);
+            i.map(|i| hidden_bindings.remove(i)).is_some()
+        };
+        if !hidden {
+            let _ = send_message!(self, "exposeBinding", Args { name });
+        }
+        self.var
+            .lock()
+            .unwrap()
+            .bindings
+            .insert(name.to_owned(), handler);
+        if hidden {
+            self.run_in_documents(&binding_call::restore_script(name)?)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Same as `Page::remove_binding` for every page in this context.
+    pub(crate) async fn remove_binding(&self, name: &str) -> ArcResult<()> {
+        {
+            let var = &mut self.var.lock().unwrap();
+            if var.bindings.remove(name).is_none() {
+                return Ok(());
+            }
+            var.hidden_bindings.push(name.to_owned());
+        }
+        self.run_in_documents(&binding_call::hide_script(name)?)
+            .await
+    }
+
+    /// Runs `source` in the current documents of every page and, as an init script, in later ones.
+    async fn run_in_documents(&self, source: &str) -> ArcResult<()> {
+        self.add_init_script(source).await?;
+        let expression = format!("() => {{ {} }}", source);
+        let pages = self.pages();
+        let frames = pages
+            .iter()
+            .filter_map(Weak::upgrade)
+            .flat_map(|p| p.frames());
+        for frame in frames.filter_map(|f| f.upgrade()) {
+            let _ = frame.evaluate::<(), ()>(&expression, None).await;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn binding(&self, name: &str) -> Option<BindingHandler> {
+        self.var.lock().unwrap().bindings.get(name).cloned()
+    }
+
+    fn on_binding_call(&self, ctx: &Context, params: Map<String, Value>) -> Result<(), Error> {
+        let first = first_object(&params).ok_or(Error::InvalidMessage)?;
+        let OnlyGuid { guid } = serde_json::from_value((*first).clone())?;
+        let binding = get_object!(ctx, &guid, BindingCall)?;
+        let name = upgrade(&binding)?.name().to_owned();
+        if let Some(handler) = self.binding(&name) {
+            binding_call::dispatch(binding, handler);
+        }
+        Ok(())
+    }
+
     // async def route(self, url: URLMatch, handler: RouteHandler) -> None:
     // async def unroute(
 
-    // async fn pause(&self) -> ArcResult<()> {
-    //    let _ = send_message!(self, "pause", Map::new());
-    //    Ok(())
-    //}
+    /// Blocks until resumed from the Inspector. Timeouts are disabled meanwhile so that
+    /// pending actions don't fail while the user is debugging.
+    pub(crate) async fn pause(&self) -> ArcResult<()> {
+        let (timeout, navigation_timeout) = {
+            let var = self.var.lock().unwrap();
+            (var.timeout, var.navigation_timeout)
+        };
+        self.set_default_timeout(0).await?;
+        self.set_default_navigation_timeout(0).await?;
+        let paused = self.send_pause().await;
+        self.reset_default_timeout(timeout).await?;
+        self.reset_default_navigation_timeout(navigation_timeout)
+            .await?;
+        paused
+    }
+
+    async fn send_pause(&self) -> ArcResult<()> {
+        let _ = send_message!(self, "pause", Map::new());
+        Ok(())
+    }
 }
 
 // mutable
@@ -485,6 +780,10 @@ impl BrowserContext {
         self.var.lock().unwrap().browser = Some(browser);
     }
 
+    pub(crate) fn set_browser_type_name(&self, name: &str) {
+        self.var.lock().unwrap().browser_type_name = Some(name.to_owned());
+    }
+
     pub(crate) fn pages(&self) -> Vec<Weak<Page>> {
         self.var.lock().unwrap().pages.clone()
     }
@@ -498,6 +797,19 @@ impl BrowserContext {
         pages.remove_one(|p| p.ptr_eq(page));
     }
 
+    pub(crate) fn service_workers(&self) -> Vec<Weak<Worker>> {
+        self.var.lock().unwrap().service_workers.clone()
+    }
+
+    pub(crate) fn remove_service_worker(&self, worker: &Weak<Worker>) {
+        let workers = &mut self.var.lock().unwrap().service_workers;
+        workers.remove_one(|w| w.ptr_eq(worker));
+    }
+
+    pub(crate) fn has_touch(&self) -> Option<bool> {
+        self.var.lock().unwrap().has_touch
+    }
+
     pub(crate) fn default_timeout(&self) -> u32 {
         self.var
             .lock()
@@ -515,18 +827,31 @@ impl BrowserContext {
     }
 
     pub(crate) async fn set_default_timeout(&self, timeout: u32) -> ArcResult<()> {
+        self.reset_default_timeout(Some(timeout)).await
+    }
+
+    pub(crate) async fn set_default_navigation_timeout(&self, timeout: u32) -> ArcResult<()> {
+        self.reset_default_navigation_timeout(Some(timeout)).await
+    }
+
+    /// `None` goes back to the driver's default
+    async fn reset_default_timeout(&self, timeout: Option<u32>) -> ArcResult<()> {
         let mut args = Map::new();
-        args.insert("timeout".into(), timeout.into());
+        if let Some(timeout) = timeout {
+            args.insert("timeout".into(), timeout.into());
+        }
         let _ = send_message!(self, "setDefaultTimeoutNoReply", args);
-        self.var.lock().unwrap().timeout = Some(timeout);
+        self.var.lock().unwrap().timeout = timeout;
         Ok(())
     }
 
-    pub(crate) async fn set_default_navigation_timeout(&self, timeout: u32) -> ArcResult<()> {
+    async fn reset_default_navigation_timeout(&self, timeout: Option<u32>) -> ArcResult<()> {
         let mut args = Map::new();
-        args.insert("timeout".into(), timeout.into());
+        if let Some(timeout) = timeout {
+            args.insert("timeout".into(), timeout.into());
+        }
         let _ = send_message!(self, "setDefaultNavigationTimeoutNoReply", args);
-        self.var.lock().unwrap().navigation_timeout = Some(timeout);
+        self.var.lock().unwrap().navigation_timeout = timeout;
         Ok(())
     }
 
@@ -799,6 +1124,20 @@ impl BrowserContext {
         Ok(())
     }
 
+    fn on_service_worker(&self, ctx: &Context, params: Map<String, Value>) -> Result<(), Error> {
+        let OnlyGuid { guid } = guid_from_keys(&params, &["worker"])?;
+        let worker = get_object!(ctx, &guid, Worker)?;
+        let this = get_object!(ctx, self.guid(), BrowserContext)?;
+        upgrade(&worker)?.set_browser_context(this);
+        self.var
+            .lock()
+            .unwrap()
+            .service_workers
+            .push(worker.clone());
+        self.emit_event(Evt::ServiceWorker(worker));
+        Ok(())
+    }
+
     fn on_request(&self, ctx: &Context, params: Map<String, Value>) -> Result<(), Error> {
         let OnlyGuid { guid } = guid_from_keys(&params, &["request"])?;
         let request = get_object!(ctx, &guid, Request)?;
@@ -875,9 +1214,10 @@ impl RemoteObject for BrowserContext {
                 self.emit_event(Evt::Page(p));
             }
             "close" => self.on_close(ctx)?,
-            "bindingCall" => {}
+            "bindingCall" => self.on_binding_call(ctx, params)?,
             "route" => self.on_route(ctx, params)?,
             "console" => self.on_console(ctx, params)?,
+            "serviceWorker" => self.on_service_worker(ctx, params)?,
             "request" => self.on_request(ctx, params)?,
             "requestfailed" => self.on_request_failed(ctx, params)?,
             "requestfinished" => self.on_request_finished(ctx, params)?,
@@ -887,7 +1227,7 @@ impl RemoteObject for BrowserContext {
                     .get("page")
                     .and_then(|v| only_guid(v).ok())
                     .and_then(|guid| get_object!(ctx, guid, Page).ok());
-                let err_val = params.get("error").ok_or(Error::InvalidParams)?;
+                let err_val = params.get("error").ok_or(Error::InvalidMessage)?;
                 let error = format_error_value(err_val)?;
                 if let Some(page) = &page {
                     if let Some(p) = page.upgrade() {
@@ -909,6 +1249,7 @@ pub(crate) enum Evt {
     Page(Weak<Page>),
     Route(Weak<Route>),
     Console(Weak<ConsoleMessage>),
+    ServiceWorker(Weak<Worker>),
     Request(Weak<Request>),
     RequestFailed(Weak<Request>),
     RequestFinished(Weak<Request>),
@@ -934,6 +1275,7 @@ pub enum EventType {
     Page,
     Route,
     Console,
+    ServiceWorker,
     Request,
     RequestFailed,
     RequestFinished,
@@ -950,6 +1292,7 @@ impl IsEvent for Evt {
             Self::Page(_) => EventType::Page,
             Self::Route(_) => EventType::Route,
             Self::Console(_) => EventType::Console,
+            Self::ServiceWorker(_) => EventType::ServiceWorker,
             Self::Request(_) => EventType::Request,
             Self::RequestFailed(_) => EventType::RequestFailed,
             Self::RequestFinished(_) => EventType::RequestFinished,
@@ -966,6 +1309,15 @@ struct Initializer {
     tracing: Option<OnlyGuid>,
     #[serde(default)]
     request_context: Option<OnlyGuid>,
+    #[serde(default)]
+    options: Option<ContextOptions>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ContextOptions {
+    #[serde(default)]
+    has_touch: bool,
 }
 
 impl fmt::Debug for BrowserContext {
@@ -2,8 +2,12 @@ use crate::imp::{
     browser::Browser,
     browser_context::BrowserContext,
     core::*,
+    playwright::Playwright,
     prelude::*,
-    utils::{BrowserChannel, ColorScheme, Geolocation, HttpCredentials, ProxySettings, Viewport},
+    utils::{
+        BrowserChannel, ColorScheme, Geolocation, HttpCredentials, ProxySettings,
+        ServiceWorkerPolicy, Viewport,
+    },
 };
 
 #[derive(Debug)]
@@ -48,6 +52,7 @@ impl BrowserType {
         let res = send_message!(self, "launchPersistentContext", args);
         let guid = only_guid(&res)?;
         let b = get_object!(self.context()?.lock().unwrap(), guid, BrowserContext)?;
+        upgrade(&b)?.set_browser_type_name(self.name());
         Ok(b)
     }
 
@@ -79,8 +84,31 @@ impl BrowserType {
         Ok(browser)
     }
 
-    pub(crate) async fn connect(&self, _args: ConnectArgs<'_>) -> ArcResult<Weak<Browser>> {
-        todo!()
+    /// The browser's objects live in the returned connection, which disconnects when dropped.
+    #[cfg(feature = "tungstenite")]
+    pub(crate) async fn connect(
+        &self,
+        args: ConnectArgs<'_>,
+    ) -> ArcResult<(Weak<Browser>, Connection)> {
+        let ConnectArgs {
+            ws_endpoint,
+            headers,
+            timeout,
+        } = args;
+        let mut headers = headers.unwrap_or_default();
+        headers
+            .entry("x-playwright-browser".into())
+            .or_insert_with(|| self.name.clone());
+        let timeout = Duration::from_millis(timeout.unwrap_or(30000.) as u64);
+        let conn = Connection::connect(ws_endpoint, &headers, timeout).await?;
+        crate::api::playwright::initialize_root(&conn).await?;
+        let playwright = Playwright::wait_initial_object(&conn).await?;
+        let browser = upgrade(&playwright)?
+            .pre_launched_browser()
+            .ok_or(Error::ObjectNotFound)?;
+        let arc_browser = upgrade(&browser)?;
+        arc_browser.set_is_remote_true();
+        Ok((browser, conn))
     }
 }
 
@@ -206,6 +234,7 @@ pub(crate) struct LaunchPersistentContextArgs<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i
     pub(crate) chromium_sandbox: Option<bool>,
     pub(crate) record_video: Option<RecordVideo<'j>>,
     pub(crate) record_har: Option<RecordHar<'k>>,
+    pub(crate) service_workers: Option<ServiceWorkerPolicy>,
 
     pub(crate) channel: Option<BrowserChannel>,
 }
@@ -267,27 +296,27 @@ impl<'a> LaunchPersistentContextArgs<'a, '_, '_, '_, '_, '_, '_, '_, '_, '_, '_>
             chromium_sandbox: None,
             record_video: None,
             record_har: None,
+            service_workers: None,
             channel: None,
         }
     }
 }
 
-#[skip_serializing_none]
-#[derive(Debug, Serialize)]
-#[serde(rename_all = "camelCase")]
+#[cfg(feature = "tungstenite")]
+#[derive(Debug)]
 pub(crate) struct ConnectArgs<'a> {
     ws_endpoint: &'a str,
+    pub(crate) headers: Option<HashMap<String, String>>,
     pub(crate) timeout: Option<f64>,
-    #[serde(rename = "slowMo")]
-    pub(crate) slowmo: Option<f64>,
 }
 
+#[cfg(feature = "tungstenite")]
 impl<'a> ConnectArgs<'a> {
     pub(crate) fn new(ws_endpoint: &'a str) -> Self {
         Self {
             ws_endpoint,
+            headers: None,
             timeout: None,
-            slowmo: None,
         }
     }
 }
@@ -320,7 +349,6 @@ impl<'a> ConnectOverCdpArgs<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::imp::playwright::Playwright;
 
     crate::runtime_test!(launch, {
         let driver = Driver::install().unwrap();
@@ -14,14 +14,15 @@ pub(crate) struct Context {
     ctx: Wm<Context>,
     id: i32,
     callbacks: HashMap<i32, WaitPlaces<WaitMessageResult>>,
-    writer: Writer,
+    writer: Box<dyn Transport>,
 }
 
 #[derive(Debug)]
 pub(crate) struct Connection {
-    _child: Child,
+    /// `None` when connected to a remote Playwright server
+    _child: Option<Child>,
     ctx: Am<Context>,
-    reader: Am<Reader>,
+    reader: Am<Box<dyn Transport>>,
     should_stop: Arc<AtomicBool>,
 }
 
@@ -34,7 +35,9 @@ pub enum Error {
     #[error("Disconnected")]
     ReceiverClosed,
     #[error("Invalid message")]
-    InvalidParams,
+    InvalidMessage,
+    #[error("Invalid params: {0}")]
+    InvalidParams(String),
     #[error("Object not found")]
     ObjectNotFound,
     #[error(transparent)]
@@ -65,16 +68,50 @@ pub enum Error {
     Event(#[from] broadcast::error::RecvError),
     #[error("Path is not available when using BrowserType.connect(). Use save_as() to save a local copy.")]
     RemoteArtifact,
+    #[error("Local paths can't be read by a browser obtained through BrowserType.connect(). Set in-memory files instead.")]
+    RemoteInputFiles,
     #[error("Failed to resolve path {0:?}")]
     ResolvePath(PathBuf),
     #[error("Timed out")]
     Timeout,
     #[error(transparent)]
     Join(#[from] JoinError),
+    #[error("{0} is only supported on Chromium")]
+    ChromiumOnly(&'static str),
+    #[error("Frame was detached")]
+    FrameDetached,
+    #[error("Navigation failed: {0}")]
+    NavigationFailed(String),
+    #[error("HAR error: {0}")]
+    Har(String),
+    #[error("Touchscreen requires a browser context created with has_touch")]
+    TouchNotEnabled,
+    #[error("Selector engine {0:?} is already registered")]
+    SelectorEngineAlreadyRegistered(String),
+    #[error("Strict mode violation: {selector} resolved to {matches} elements")]
+    StrictModeViolation { selector: String, matches: usize },
 }
 
 pub(crate) type ArcResult<T> = Result<T, Arc<Error>>;
 
+impl Error {
+    /// Converts an error returned by the driver, recognizing the ones with a dedicated variant.
+    pub(crate) fn responded(e: Arc<ErrorMessage>) -> Self {
+        match parse_strict_mode_violation(&e.message) {
+            Some((selector, matches)) => Self::StrictModeViolation { selector, matches },
+            None => Self::ErrorResponded(e),
+        }
+    }
+}
+
+// e.g. "strict mode violation: locator('li') resolved to 3 elements:\n    1) <li>..."
+fn parse_strict_mode_violation(message: &str) -> Option<(String, usize)> {
+    let rest = message.split("strict mode violation: ").nth(1)?;
+    let (selector, rest) = rest.split_once(" resolved to ")?;
+    let matches = rest.split(' ').next()?.parse().ok()?;
+    Some((selector.to_owned(), matches))
+}
+
 impl Drop for Connection {
     fn drop(&mut self) {
         self.notify_closed(Error::ReceiverClosed);
@@ -93,11 +130,11 @@ impl Connection {
         // TODO: env "NODE_OPTIONS"
         let stdin = child.stdin.take().unwrap();
         let stdout = child.stdout.take().unwrap();
-        let reader = Reader::new(stdout);
-        let writer = Writer::new(stdin);
-        let ctx = Context::new(writer);
+        let transport = PipeTransport::new(stdin, stdout);
+        let ctx = Context::new(Box::new(transport.clone()));
+        let reader: Box<dyn Transport> = Box::new(transport);
         Ok(Self {
-            _child: child,
+            _child: Some(child),
             ctx,
             should_stop: Arc::new(false.into()),
             reader: Arc::new(Mutex::new(reader)),
@@ -110,6 +147,31 @@ impl Connection {
         Ok(conn)
     }
 
+    /// Connects to a Playwright server listening on `ws_endpoint`. Once the socket is lost, pending and later
+    /// requests fail with [`Error::Transport`].
+    #[cfg(feature = "tungstenite")]
+    pub(crate) async fn connect(
+        ws_endpoint: &str,
+        headers: &HashMap<String, String>,
+        timeout: Duration,
+    ) -> Result<Connection, Error> {
+        let (ws_endpoint, headers) = (ws_endpoint.to_owned(), headers.clone());
+        // Resolving, connecting and the handshake block
+        let transport =
+            spawn_blocking(move || WebSocketTransport::connect(&ws_endpoint, &headers, timeout))
+                .await??;
+        let ctx = Context::new(Box::new(transport.clone()));
+        let reader: Box<dyn Transport> = Box::new(transport);
+        let conn = Self {
+            _child: None,
+            ctx,
+            should_stop: Arc::new(false.into()),
+            reader: Arc::new(Mutex::new(reader)),
+        };
+        conn.start();
+        Ok(conn)
+    }
+
     fn start(&self) {
         let c2 = Arc::downgrade(&self.ctx);
         let r2 = Arc::downgrade(&self.reader);
@@ -198,7 +260,7 @@ impl Connection {
 }
 
 impl Context {
-    fn new(writer: Writer) -> Am<Context> {
+    fn new(writer: Box<dyn Transport>) -> Am<Context> {
         let objects = {
             let mut d = HashMap::new();
             let root = RootObject::new();
@@ -397,6 +459,14 @@ impl Context {
         self.objects.get(k).map(|r| r.downgrade())
     }
 
+    /// Set by `Selectors::set_test_id_attribute` of this connection
+    pub(in crate::imp) fn test_id_attribute(&self) -> Option<String> {
+        self.objects.values().find_map(|o| match o {
+            RemoteArc::Selectors(s) => s.test_id_attribute(),
+            _ => None,
+        })
+    }
+
     pub(in crate::imp) fn list_objects(&self) -> Vec<RemoteArc> {
         self.objects.values().cloned().collect()
     }
@@ -429,8 +499,22 @@ impl Context {
 
 #[cfg(test)]
 mod tests {
+    use super::parse_strict_mode_violation;
     use crate::imp::core::*;
 
+    #[test]
+    fn strict_mode_violation() {
+        let message = "Error: strict mode violation: locator('li') resolved to 3 elements:\n    1) <li>a</li>";
+        assert_eq!(
+            parse_strict_mode_violation(message),
+            Some(("locator('li')".to_owned(), 3))
+        );
+        assert_eq!(
+            parse_strict_mode_violation("Timeout 30000ms exceeded."),
+            None
+        );
+    }
+
     crate::runtime_test!(start, {
         let driver = Driver::install().unwrap();
         let conn = Connection::try_new(&driver.executable()).unwrap();
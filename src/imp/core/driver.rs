@@ -7,16 +7,28 @@ use zip::{result::ZipError, ZipArchive};
 #[derive(Debug, Clone, PartialEq)]
 pub struct Driver {
     path: PathBuf,
+    cli: Option<PathBuf>,
 }
 
 impl Driver {
     const ZIP: &'static [u8] = include_bytes!(concat!(env!("OUT_DIR"), env!("SEP"), "driver.zip"));
     const PLATFORM: &'static str = include_str!(concat!(env!("OUT_DIR"), env!("SEP"), "platform"));
+    /// Written after a successful extraction. Holds the version and platform of the embedded
+    /// driver so that a different driver re-extracts.
+    const MARKER: &'static str = ".playwright-rust-extracted";
+    const VERSION: &'static str = env!("DRIVER_VERSION");
+    const LOCK: &'static str = ".playwright-rust.lock";
 
     pub fn install() -> io::Result<Self> {
+        if let Some(cli) = Self::cli_override()? {
+            return Ok(Self {
+                path: Self::default_dest(),
+                cli: Some(cli),
+            });
+        }
         let this = Self::new(Self::default_dest());
-        if !this.path.is_dir() {
-            this.prepare()?;
+        if !this.is_extracted() {
+            this.prepare_exclusive()?;
         }
         this.ensure_launcher_stub()?;
         Ok(this)
@@ -24,7 +36,56 @@ impl Driver {
 
     /// Without prepare
     pub fn new<P: Into<PathBuf>>(path: P) -> Self {
-        Self { path: path.into() }
+        Self {
+            path: path.into(),
+            cli: None,
+        }
+    }
+
+    /// Uses an externally installed Playwright CLI instead of the bundled driver.
+    /// Nothing is extracted.
+    pub fn with_cli<P: Into<PathBuf>>(cli: P) -> io::Result<Self> {
+        let cli = cli.into();
+        Self::validate_cli(&cli)?;
+        Ok(Self {
+            path: Self::default_dest(),
+            cli: Some(cli),
+        })
+    }
+
+    /// Reads `PLAYWRIGHT_CLI_PATH`. An empty value is treated as unset.
+    fn cli_override() -> io::Result<Option<PathBuf>> {
+        let cli = match env::var_os("PLAYWRIGHT_CLI_PATH") {
+            Some(x) if !x.is_empty() => PathBuf::from(x),
+            _ => return Ok(None),
+        };
+        Self::validate_cli(&cli)?;
+        Ok(Some(cli))
+    }
+
+    fn validate_cli(cli: &Path) -> io::Result<()> {
+        let meta = fs::metadata(cli).map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!("playwright cli {} is not accessible: {}", cli.display(), e),
+            )
+        })?;
+        if !meta.is_file() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("playwright cli {} is not a file", cli.display()),
+            ));
+        }
+        #[cfg(unix)]
+        {
+            if meta.permissions().mode() & 0o111 == 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::PermissionDenied,
+                    format!("playwright cli {} is not executable", cli.display()),
+                ));
+            }
+        }
+        Ok(())
     }
     ///
     pub fn prepare(&self) -> Result<(), ZipError> {
@@ -33,6 +94,85 @@ impl Driver {
         a.extract(&self.path)
     }
 
+    /// Extracts while holding a lockfile in the install dir so that concurrent processes
+    /// don't interleave writes. Processes that lose the race wait and reuse the result.
+    fn prepare_exclusive(&self) -> io::Result<()> {
+        fs::create_dir_all(&self.path)?;
+        let _lock = InstallLock::acquire(self.path.join(Self::LOCK))?;
+        if self.is_extracted() {
+            return Ok(());
+        }
+        let _ = fs::remove_file(self.path.join(Self::MARKER));
+        // An interrupted extraction can leave truncated files behind. Start over from an
+        // empty directory once before giving up.
+        let mut last_err = None;
+        for _ in 0..2 {
+            match self.prepare() {
+                Ok(()) if self.is_intact() => {
+                    self.ensure_launcher_stub()?;
+                    return fs::write(self.path.join(Self::MARKER), Self::stamp());
+                }
+                Ok(()) => {
+                    last_err = Some(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("driver extracted to {} is incomplete", self.path.display()),
+                    ))
+                }
+                Err(e) => last_err = Some(e.into()),
+            }
+            self.clear()?;
+        }
+        Err(last_err.unwrap())
+    }
+
+    fn is_extracted(&self) -> bool {
+        fs::read_to_string(self.path.join(Self::MARKER))
+            .map(|s| s.trim() == Self::stamp())
+            .unwrap_or_default()
+            && self.is_intact()
+    }
+
+    fn stamp() -> String {
+        format!("{} {}", Self::VERSION, Self::PLATFORM)
+    }
+
+    /// Checks the files the launcher depends on.
+    fn is_intact(&self) -> bool {
+        let node = self.path.join("node").is_file() || self.path.join("node.exe").is_file();
+        let package = self.path.join("package");
+        node && package.join("cli.js").is_file()
+            && package.join("lib").join("cli").join("driver.js").is_file()
+    }
+
+    /// Removes what an extraction writes: the top-level entries of the embedded zip and the
+    /// marker. Anything else is left alone, since `PLAYWRIGHT_DRIVER_DIR` may point to a
+    /// directory of the user.
+    fn clear(&self) -> io::Result<()> {
+        let a = ZipArchive::new(io::Cursor::new(Self::ZIP))?;
+        let mut names: Vec<PathBuf> = a
+            .file_names()
+            .filter_map(|n| match Path::new(n).components().next() {
+                Some(c @ std::path::Component::Normal(_)) => Some(self.path.join(c)),
+                _ => None,
+            })
+            .collect();
+        names.sort();
+        names.dedup();
+        names.push(self.path.join(Self::MARKER));
+        for p in names {
+            let removed = if p.is_dir() {
+                fs::remove_dir_all(&p)
+            } else {
+                fs::remove_file(&p)
+            };
+            match removed {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
     pub fn default_dest() -> PathBuf {
         if let Ok(dir) = env::var("PLAYWRIGHT_DRIVER_DIR") {
             return PathBuf::from(dir);
@@ -84,7 +224,10 @@ impl Driver {
     }
 
     pub fn executable(&self) -> PathBuf {
-        self.launcher_path()
+        match &self.cli {
+            Some(cli) => cli.clone(),
+            None => self.launcher_path(),
+        }
     }
 
     fn launcher_path(&self) -> PathBuf {
@@ -99,32 +242,28 @@ impl Driver {
     fn ensure_launcher_stub(&self) -> io::Result<()> {
         let launcher = self.launcher_path();
 
+        // Every path expansion is quoted so that install directories containing spaces
+        // (e.g. `C:\Users\First Last\AppData\Local`) still resolve to a single argument.
         #[cfg(windows)]
         let stub = {
-            let node = {
-                let exe = self.path.join("node.exe");
-                if exe.exists() {
-                    exe
-                } else {
-                    self.path.join("node")
-                }
+            let node = if self.path.join("node.exe").exists() {
+                "node.exe"
+            } else {
+                "node"
             };
             // Keep the driver process alive when invoked with `run-driver`
             // by delegating to a tiny inline script that starts the driver
             // and parks the event loop.
             format!(
-                "@echo off\r\nsetlocal\r\nset \"DIR=%~dp0\"\r\nset \"PW_DRIVER_DIR=%DIR%\"\r\nif \"%1\"==\"run-driver\" (\r\n  \"{node}\" -e \"const path=require('path');const dir=process.env.PW_DRIVER_DIR;const driver=require(path.join(dir,'package','lib','cli','driver'));driver.runDriver();setInterval(()=>{{}},2147483647);\" %*\r\n) else (\r\n  \"{node}\" \"%DIR%package\\cli.js\" %*\r\n)\r\n",
+                "@echo off\r\nsetlocal\r\nset \"DIR=%~dp0\"\r\nset \"PW_DRIVER_DIR=%~dp0\"\r\nset \"NODE=%~dp0{node}\"\r\nif \"%~1\"==\"run-driver\" goto run_driver\r\n\"%NODE%\" \"%DIR%package\\cli.js\" %*\r\nexit /b %ERRORLEVEL%\r\n:run_driver\r\n\"%NODE%\" -e \"const path=require('path');const dir=process.env.PW_DRIVER_DIR;const driver=require(path.join(dir,'package','lib','cli','driver'));driver.runDriver();setInterval(()=>{{}},2147483647);\" %*\r\nexit /b %ERRORLEVEL%\r\n",
                 node = node
-                    .file_name()
-                    .map(|s| s.to_string_lossy())
-                    .unwrap_or_else(|| "node".into())
             )
         };
 
         #[cfg(not(windows))]
         let stub = r#"#!/bin/sh
 set -e
-DIR="$(CDPATH= cd -- "$(dirname "$0")" && pwd)"
+DIR="$(CDPATH= cd -- "$(dirname -- "$0")" && pwd)"
 export PW_DRIVER_DIR="$DIR"
 if [ "${1-}" = "run-driver" ]; then
   exec "$DIR/node" -e "const path=require('path');const dir=process.env.PW_DRIVER_DIR;const driver=require(path.join(dir,'package','lib','cli','driver'));driver.runDriver();setInterval(()=>{},2147483647);" "$@"
@@ -133,6 +272,9 @@ else
 fi
 "#;
 
+        if fs::read(&launcher).is_ok_and(|x| x == stub.as_bytes()) {
+            return Ok(());
+        }
         fs::write(&launcher, stub)?;
 
         #[cfg(unix)]
@@ -145,6 +287,68 @@ fi
     }
 }
 
+struct InstallLock {
+    path: PathBuf,
+    /// Dropping it stops the thread that keeps the lockfile fresh
+    _refresh: std::sync::mpsc::Sender<()>,
+}
+
+impl InstallLock {
+    const POLL: Duration = Duration::from_millis(100);
+    const TIMEOUT: Duration = Duration::from_secs(300);
+    /// The holder touches the lockfile this often while extracting.
+    const REFRESH: Duration = Duration::from_secs(5);
+    /// A lock not touched for this long is assumed to be left behind by a killed process.
+    const STALE: Duration = Duration::from_secs(30);
+
+    fn acquire(path: PathBuf) -> io::Result<Self> {
+        let started = std::time::Instant::now();
+        loop {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(file) => {
+                    let (tx, rx) = std::sync::mpsc::channel::<()>();
+                    std::thread::spawn(move || {
+                        while let Err(std::sync::mpsc::RecvTimeoutError::Timeout) =
+                            rx.recv_timeout(Self::REFRESH)
+                        {
+                            let _ = file.set_modified(std::time::SystemTime::now());
+                        }
+                    });
+                    return Ok(Self { path, _refresh: tx });
+                }
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                    if Self::is_stale(&path) {
+                        let _ = fs::remove_file(&path);
+                        continue;
+                    }
+                    if started.elapsed() > Self::TIMEOUT {
+                        return Err(io::Error::new(
+                            io::ErrorKind::TimedOut,
+                            format!("timed out waiting for {}", path.display()),
+                        ));
+                    }
+                    std::thread::sleep(Self::POLL);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    fn is_stale(path: &Path) -> bool {
+        fs::metadata(path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.elapsed().ok())
+            .is_some_and(|d| d > Self::STALE)
+    }
+}
+
+impl Drop for InstallLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Platform {
     Linux,
@@ -163,4 +367,87 @@ mod tests {
     fn install() {
         let _driver = Driver::install().unwrap();
     }
+
+    #[test]
+    fn concurrent_install() {
+        let dir = env::temp_dir().join("playwright-rust-concurrent-install");
+        let _ = fs::remove_dir_all(&dir);
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let dir = dir.clone();
+                std::thread::spawn(move || {
+                    let driver = Driver::new(dir);
+                    driver.prepare_exclusive().unwrap();
+                    driver
+                })
+            })
+            .collect();
+        for h in handles {
+            let driver = h.join().unwrap();
+            assert!(driver.is_extracted());
+            assert!(driver.executable().is_file());
+        }
+        assert!(!dir.join(Driver::LOCK).exists());
+    }
+
+    #[test]
+    fn repair_partial_extraction() {
+        let dir = env::temp_dir().join("playwright-rust-partial-install");
+        let driver = Driver::new(&dir);
+        driver.prepare_exclusive().unwrap();
+        assert!(driver.is_extracted());
+        fs::remove_file(dir.join("package").join("cli.js")).unwrap();
+        assert!(!driver.is_extracted());
+        driver.prepare_exclusive().unwrap();
+        assert!(driver.is_extracted());
+    }
+
+    #[test]
+    fn clear_keeps_unrelated_files() {
+        let dir = env::temp_dir().join("playwright-rust-clear");
+        let driver = Driver::new(&dir);
+        driver.prepare_exclusive().unwrap();
+        let mine = dir.join("mine.txt");
+        fs::write(&mine, "keep").unwrap();
+        driver.clear().unwrap();
+        assert!(mine.is_file());
+        assert!(!driver.is_extracted());
+    }
+
+    #[test]
+    fn install_to_path_with_space() {
+        let dir = env::temp_dir()
+            .join("playwright rust driver")
+            .join("with space");
+        let driver = Driver::new(&dir);
+        if !dir.is_dir() {
+            driver.prepare().unwrap();
+        }
+        driver.ensure_launcher_stub().unwrap();
+        let status = std::process::Command::new(driver.executable())
+            .arg("--version")
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    #[test]
+    fn with_cli_rejects_missing_file() {
+        let missing = env::temp_dir().join("playwright-rust-no-such-cli");
+        assert!(Driver::with_cli(missing).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn with_cli_rejects_non_executable() {
+        let dir = env::temp_dir().join("playwright-rust-cli-test");
+        fs::create_dir_all(&dir).unwrap();
+        let cli = dir.join("playwright");
+        fs::write(&cli, "#!/bin/sh\n").unwrap();
+        fs::set_permissions(&cli, fs::Permissions::from_mode(0o644)).unwrap();
+        assert!(Driver::with_cli(&cli).is_err());
+        fs::set_permissions(&cli, fs::Permissions::from_mode(0o755)).unwrap();
+        let driver = Driver::with_cli(&cli).unwrap();
+        assert_eq!(driver.executable(), cli);
+    }
 }
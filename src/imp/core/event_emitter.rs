@@ -1,5 +1,8 @@
 use crate::imp::{core::*, prelude::*};
-pub(crate) use tokio::sync::{broadcast, broadcast::error::TryRecvError};
+pub(crate) use tokio::sync::{
+    broadcast,
+    broadcast::error::{RecvError, TryRecvError},
+};
 
 pub trait EventEmitter {
     type Event: Clone;
@@ -49,7 +52,7 @@ where
     <E as event_emitter::IsEvent>::EventType: Send + Sync,
 {
     consume(&mut rx).await?;
-    let sleep = sleep(Duration::from_millis(timeout as u64));
+    let sleep = timer(timeout);
     let event = spawn(async move {
         loop {
             match rx.recv().await {
@@ -76,7 +79,7 @@ where
     <E as event_emitter::IsEvent>::EventType: Send + Sync,
 {
     consume(&mut rx).await?;
-    let sleep = sleep(Duration::from_millis(timeout as u64));
+    let sleep = timer(timeout);
     let event = spawn(async move {
         loop {
             match rx.recv().await {
@@ -92,6 +95,43 @@ where
     }
 }
 
+/// Like [`expect_event`] but resolves with the first event accepted by `filter`.
+/// The filter runs on the calling task so it doesn't need to be `'static`.
+pub(crate) async fn wait_for_event<E, F>(
+    mut rx: broadcast::Receiver<E>,
+    timeout: u32,
+    mut filter: F,
+) -> Result<E, Error>
+where
+    E: IsEvent,
+    F: FnMut(&E) -> bool,
+{
+    consume(&mut rx).await?;
+    let sleep = timer(timeout);
+    let event = async move {
+        loop {
+            match rx.recv().await {
+                Ok(x) if filter(&x) => break Ok(x),
+                Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                Err(e) => break Err(e),
+            }
+        }
+    };
+    tokio::select! {
+        _ = sleep => Err(Error::Timeout),
+        x = event => x.map_err(Error::Event)
+    }
+}
+
+/// Completes after `timeout` milliseconds. Like Playwright, `0` means no timeout.
+async fn timer(timeout: u32) {
+    if timeout == 0 {
+        futures::future::pending::<()>().await
+    } else {
+        sleep(Duration::from_millis(timeout as u64)).await
+    }
+}
+
 async fn consume<E>(rx: &mut broadcast::Receiver<E>) -> Result<(), Error>
 where
     E: IsEvent,
@@ -116,4 +156,22 @@ mod tests {
             _ = second => unreachable!()
         }
     });
+
+    crate::runtime_test!(zero_timeout, {
+        use super::*;
+        #[derive(Clone)]
+        struct Ping;
+        impl IsEvent for Ping {
+            type EventType = ();
+            fn event_type(&self) {}
+        }
+        let (tx, rx) = broadcast::channel(4);
+        let (res, _) = tokio::join!(wait_for_event(rx, 0, |_: &Ping| true), async {
+            sleep(Duration::from_millis(50)).await;
+            tx.send(Ping).ok();
+        });
+        assert!(res.is_ok());
+        let res = wait_for_event(tx.subscribe(), 10, |_: &Ping| true).await;
+        assert!(matches!(res, Err(Error::Timeout)));
+    });
 }
@@ -164,15 +164,15 @@ pub(crate) fn only_guid(v: &Value) -> Result<&S<Guid>, Error> {
 
 pub(crate) fn only_str(v: &Value) -> Result<&str, Error> {
     let s = first(v)
-        .ok_or(Error::InvalidParams)?
+        .ok_or(Error::InvalidMessage)?
         .as_str()
-        .ok_or(Error::InvalidParams)?;
+        .ok_or(Error::InvalidMessage)?;
     Ok(s)
 }
 
 pub(crate) fn maybe_only_str(v: &Value) -> Result<Option<&str>, Error> {
     let s = match first(v) {
-        Some(s) => s.as_str().ok_or(Error::InvalidParams)?,
+        Some(s) => s.as_str().ok_or(Error::InvalidMessage)?,
         None => return Ok(None),
     };
     Ok(Some(s))
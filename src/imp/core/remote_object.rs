@@ -71,6 +71,26 @@ impl ChannelOwner {
         Ok(wait)
     }
 
+    /// Whether this object belongs to a browser obtained through `BrowserType::connect`, found by walking up the
+    /// parents to the nearest browser or browser context.
+    pub(crate) fn is_remote(&self) -> bool {
+        let mut parent = self.parent.as_ref().and_then(|p| p.upgrade());
+        while let Some(p) = parent {
+            match &p {
+                RemoteArc::Browser(b) => return b.is_remote(),
+                RemoteArc::BrowserContext(c) => {
+                    return c
+                        .browser()
+                        .and_then(|b| b.upgrade())
+                        .is_some_and(|b| b.is_remote())
+                }
+                _ => {}
+            }
+            parent = p.channel().parent.as_ref().and_then(|p| p.upgrade());
+        }
+        false
+    }
+
     pub(crate) fn children(&self) -> Vec<RemoteWeak> {
         self.children.lock().unwrap().to_vec()
     }
@@ -156,9 +176,10 @@ mod remote_enum {
         artifact::Artifact, binding_call::BindingCall, browser::Browser,
         browser_context::BrowserContext, browser_type::BrowserType, cdp_session::CDPSession,
         console_message::ConsoleMessage, dialog::Dialog, element_handle::ElementHandle,
-        frame::Frame, js_handle::JsHandle, page::Page, playwright::Playwright, request::Request,
-        response::Response, route::Route, selectors::Selectors, stream::Stream, tracing::Tracing,
-        websocket::WebSocket, websocket_route::WebSocketRoute, worker::Worker,
+        frame::Frame, js_handle::JsHandle, local_utils::LocalUtils, page::Page,
+        playwright::Playwright, request::Request, response::Response, route::Route,
+        selectors::Selectors, stream::Stream, tracing::Tracing, websocket::WebSocket,
+        websocket_route::WebSocketRoute, worker::Worker,
     };
 
     macro_rules! upgrade {
@@ -254,6 +275,7 @@ mod remote_enum {
         ElementHandle,
         Frame,
         JsHandle,
+        LocalUtils,
         Page,
         Playwright,
         APIRequestContext,
@@ -276,7 +298,7 @@ mod remote_enum {
         ) -> Result<RemoteArc, Error> {
             let r = match typ.as_str() {
                 "Artifact" => RemoteArc::Artifact(Arc::new(Artifact::try_new(c)?)),
-                "BindingCall" => RemoteArc::BindingCall(Arc::new(BindingCall::new(c))),
+                "BindingCall" => RemoteArc::BindingCall(Arc::new(BindingCall::try_new(c)?)),
                 "Browser" => RemoteArc::Browser(Arc::new(Browser::try_new(c)?)),
                 "BrowserContext" => {
                     RemoteArc::BrowserContext(Arc::new(BrowserContext::try_new(c)?))
@@ -290,6 +312,7 @@ mod remote_enum {
                 "ElementHandle" => RemoteArc::ElementHandle(Arc::new(ElementHandle::new(c))),
                 "Frame" => RemoteArc::Frame(Arc::new(Frame::try_new(ctx, c)?)),
                 "JSHandle" => RemoteArc::JsHandle(Arc::new(JsHandle::try_new(c)?)),
+                "LocalUtils" => RemoteArc::LocalUtils(Arc::new(LocalUtils::new(c))),
                 "Page" => RemoteArc::Page(Arc::new(Page::try_new(ctx, c)?)),
                 "Playwright" => RemoteArc::Playwright(Arc::new(Playwright::try_new(ctx, c)?)),
                 "APIRequestContext" => {
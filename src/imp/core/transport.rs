@@ -1,21 +1,72 @@
 use crate::imp::core::*;
+#[cfg(feature = "tungstenite")]
+use std::{
+    collections::HashMap,
+    net::{Shutdown, TcpStream, ToSocketAddrs},
+    sync::mpsc::{self, RecvTimeoutError},
+    time::{Duration, Instant},
+};
 use std::{
     convert::TryInto,
-    io,
+    fmt, io,
     io::{Read, Write},
     process::{ChildStdin, ChildStdout},
+    sync::{Arc, Mutex},
 };
 use thiserror::Error;
+#[cfg(feature = "tungstenite")]
+use tungstenite::{
+    client::IntoClientRequest,
+    handshake::HandshakeError,
+    http::{HeaderName, HeaderValue},
+    stream::MaybeTlsStream,
+    Message, WebSocket,
+};
+
+/// A channel to a Playwright driver or server carrying one JSON message at a time.
+///
+/// Implementors are cheap handles to the same channel: the connection thread reads through one clone while requests
+/// are written through another, so `try_read` must not block writes for long.
+pub(crate) trait Transport: Send + fmt::Debug {
+    /// Returns `Ok(None)` until a whole message has been received.
+    fn try_read(&mut self) -> Result<Option<Res>, TransportError>;
+    fn send(&mut self, req: &Req<'_, '_>) -> Result<(), TransportError>;
+}
+
+/// Stdio of the driver process. Messages are prefixed with their length as u32 LE.
+#[derive(Debug, Clone)]
+pub(crate) struct PipeTransport {
+    stdin: Arc<Mutex<ChildStdin>>,
+    stdout: Arc<Mutex<Incoming>>,
+}
 
 #[derive(Debug)]
-pub(super) struct Reader {
+struct Incoming {
     stdout: ChildStdout,
     buf: Vec<u8>,
 }
 
-#[derive(Debug)]
-pub(super) struct Writer {
-    stdin: ChildStdin,
+/// A Playwright server. Messages are sent as one JSON text frame each.
+///
+/// Writers send through the shared socket. A thread blocks until data arrives without holding the socket, so that
+/// writers only wait while messages are being read, and passes them on through a channel. The socket is closed once
+/// every handle is dropped.
+#[cfg(feature = "tungstenite")]
+#[derive(Clone)]
+pub(crate) struct WebSocketTransport {
+    socket: Arc<Mutex<Socket>>,
+    incoming: Arc<Mutex<mpsc::Receiver<Result<String, TransportError>>>>,
+    _closer: Arc<Closer>,
+}
+
+#[cfg(feature = "tungstenite")]
+type Socket = WebSocket<MaybeTlsStream<TcpStream>>;
+
+/// Closes the socket when the last [`WebSocketTransport`] is dropped.
+#[cfg(feature = "tungstenite")]
+struct Closer {
+    socket: Arc<Mutex<Socket>>,
+    stream: TcpStream,
 }
 
 #[derive(Error, Debug)]
@@ -24,52 +75,234 @@ pub enum TransportError {
     Serde(#[from] serde_json::error::Error),
     #[error(transparent)]
     Io(#[from] io::Error),
+    #[cfg(feature = "tungstenite")]
+    #[error(transparent)]
+    WebSocket(#[from] tungstenite::Error),
+    #[error("Connection closed by the server")]
+    Closed,
 }
 
-impl Reader {
+impl PipeTransport {
     const BUFSIZE: usize = 30000;
 
-    pub(super) fn new(stdout: ChildStdout) -> Self {
-        Self {
+    pub(crate) fn new(stdin: ChildStdin, stdout: ChildStdout) -> Self {
+        let incoming = Incoming {
             stdout,
             buf: Vec::with_capacity(Self::BUFSIZE),
+        };
+        Self {
+            stdin: Arc::new(Mutex::new(stdin)),
+            stdout: Arc::new(Mutex::new(incoming)),
         }
     }
+}
 
+impl Transport for PipeTransport {
     // TODO: heap efficiency
-    pub(super) fn try_read(&mut self) -> Result<Option<Res>, TransportError> {
+    fn try_read(&mut self) -> Result<Option<Res>, TransportError> {
+        let Incoming { stdout, buf } = &mut *self.stdout.lock().unwrap();
         // Read length-prefixed (u32 LE) JSON string.
         {
-            if self.buf.len() >= 4 {
-                let len = u32::from_le_bytes(self.buf[..4].try_into().unwrap()) as usize;
-                if self.buf.len() >= 4 + len {
-                    let bytes = self.buf[4..4 + len].to_vec();
-                    self.buf = self.buf[4 + len..].to_vec();
+            if buf.len() >= 4 {
+                let len = u32::from_le_bytes(buf[..4].try_into().unwrap()) as usize;
+                if buf.len() >= 4 + len {
+                    let bytes = buf[4..4 + len].to_vec();
+                    *buf = buf[4 + len..].to_vec();
                     log::debug!("RECV {}", unsafe { std::str::from_utf8_unchecked(&bytes) });
                     let msg: Res = serde_json::from_slice(&bytes)?;
                     return Ok(Some(msg));
                 }
             }
         }
-        let mut buf = [0; Self::BUFSIZE];
-        let n = self.stdout.read(&mut buf)?;
-        self.buf.extend(&buf[..n]);
+        let mut chunk = [0; Self::BUFSIZE];
+        let n = stdout.read(&mut chunk)?;
+        buf.extend(&chunk[..n]);
         Ok(None)
     }
-}
 
-impl Writer {
-    pub(super) fn new(stdin: ChildStdin) -> Self {
-        Self { stdin }
-    }
-
-    pub(super) fn send(&mut self, req: &Req<'_, '_>) -> Result<(), TransportError> {
+    fn send(&mut self, req: &Req<'_, '_>) -> Result<(), TransportError> {
         log::debug!("SEND {:?}", &req);
         let serialized = serde_json::to_vec(&req)?;
         let length = serialized.len() as u32;
         let mut bytes = length.to_le_bytes().to_vec();
         bytes.extend(serialized);
-        self.stdin.write_all(&bytes)?;
+        self.stdin.lock().unwrap().write_all(&bytes)?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "tungstenite")]
+impl WebSocketTransport {
+    // The connection thread gives up the reader this often to notice that the connection was dropped.
+    const POLL_INTERVAL: Duration = Duration::from_millis(100);
+    // The handshake checks its deadline this often.
+    const HANDSHAKE_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+    /// Opens a WebSocket to `ws_endpoint`. A `timeout` of zero waits forever. Blocks until the handshake is done.
+    pub(crate) fn connect(
+        ws_endpoint: &str,
+        headers: &HashMap<String, String>,
+        timeout: Duration,
+    ) -> Result<Self, TransportError> {
+        let deadline = Some(timeout)
+            .filter(|t| !t.is_zero())
+            .map(|t| Instant::now() + t);
+        let mut request = ws_endpoint.into_client_request()?;
+        for (k, v) in headers {
+            let name = HeaderName::from_bytes(k.as_bytes()).map_err(tungstenite::Error::from)?;
+            let value = HeaderValue::from_str(v).map_err(tungstenite::Error::from)?;
+            request.headers_mut().insert(name, value);
+        }
+        let uri = request.uri();
+        let host = uri.host().unwrap_or_default().to_owned();
+        let default_port = if uri.scheme_str() == Some("wss") {
+            443
+        } else {
+            80
+        };
+        let port = uri.port_u16().unwrap_or(default_port);
+        let mut last_error = io::Error::new(io::ErrorKind::NotFound, host.clone());
+        let mut stream = None;
+        for addr in (host.as_str(), port).to_socket_addrs()? {
+            let connected = match deadline {
+                Some(d) => {
+                    TcpStream::connect_timeout(&addr, d.saturating_duration_since(Instant::now()))
+                }
+                None => TcpStream::connect(addr),
+            };
+            match connected {
+                Ok(s) => {
+                    stream = Some(s);
+                    break;
+                }
+                Err(e) => last_error = e,
+            }
+        }
+        let stream = stream.ok_or(last_error)?;
+        let peeker = stream.try_clone()?;
+        stream.set_read_timeout(Some(Self::HANDSHAKE_POLL_INTERVAL))?;
+        let mut handshake = tungstenite::client_tls(request, stream);
+        let socket = loop {
+            match handshake {
+                Ok((socket, _)) => break socket,
+                Err(HandshakeError::Interrupted(mid)) => {
+                    if deadline.is_some_and(|d| Instant::now() >= d) {
+                        let e = io::Error::new(
+                            io::ErrorKind::TimedOut,
+                            "WebSocket handshake timed out",
+                        );
+                        return Err(e.into());
+                    }
+                    handshake = mid.handshake();
+                }
+                Err(HandshakeError::Failure(e)) => return Err(e.into()),
+            }
+        };
+        peeker.set_read_timeout(None)?;
+        let socket = Arc::new(Mutex::new(socket));
+        let (incoming_tx, incoming) = mpsc::channel();
+        let closer = Closer {
+            socket: socket.clone(),
+            stream: peeker.try_clone()?,
+        };
+        let s2 = socket.clone();
+        std::thread::spawn(move || Self::run(s2, peeker, incoming_tx));
+        Ok(Self {
+            socket,
+            incoming: Arc::new(Mutex::new(incoming)),
+            _closer: Arc::new(closer),
+        })
+    }
+
+    /// Waits for data on `peeker` without holding the socket, then reads every message that has arrived.
+    fn run(
+        socket: Arc<Mutex<Socket>>,
+        peeker: TcpStream,
+        incoming: mpsc::Sender<Result<String, TransportError>>,
+    ) {
+        loop {
+            // Returns 0 at the end of the stream, which the read below reports
+            if let Err(e) = peeker.peek(&mut [0]) {
+                if e.kind() == io::ErrorKind::Interrupted {
+                    continue;
+                }
+                let _ = incoming.send(Err(e.into()));
+                return;
+            }
+            let mut ws = socket.lock().unwrap();
+            if let Err(e) = peeker.set_nonblocking(true) {
+                let _ = incoming.send(Err(e.into()));
+                return;
+            }
+            // Messages may already be buffered by the socket, so read until it would block
+            loop {
+                let received = match ws.read() {
+                    Ok(Message::Text(text)) => Ok(text.as_str().to_owned()),
+                    Ok(Message::Close(_)) | Err(tungstenite::Error::ConnectionClosed) => {
+                        Err(TransportError::Closed)
+                    }
+                    Ok(_) => continue,
+                    Err(tungstenite::Error::Io(e)) if e.kind() == io::ErrorKind::WouldBlock => {
+                        break
+                    }
+                    Err(e) => Err(e.into()),
+                };
+                let failed = received.is_err();
+                if incoming.send(received).is_err() || failed {
+                    return;
+                }
+            }
+            if let Err(e) = peeker.set_nonblocking(false) {
+                let _ = incoming.send(Err(e.into()));
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "tungstenite")]
+impl Drop for Closer {
+    fn drop(&mut self) {
+        if let Ok(mut socket) = self.socket.lock() {
+            let _ = socket.close(None);
+            let _ = socket.flush();
+        }
+        // Wakes the socket thread, which then fails to read and exits
+        let _ = self.stream.shutdown(Shutdown::Both);
+    }
+}
+
+#[cfg(feature = "tungstenite")]
+impl fmt::Debug for WebSocketTransport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebSocketTransport").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "tungstenite")]
+impl Transport for WebSocketTransport {
+    fn try_read(&mut self) -> Result<Option<Res>, TransportError> {
+        let received = self
+            .incoming
+            .lock()
+            .unwrap()
+            .recv_timeout(Self::POLL_INTERVAL);
+        let text = match received {
+            Ok(res) => res?,
+            Err(RecvTimeoutError::Timeout) => return Ok(None),
+            Err(RecvTimeoutError::Disconnected) => return Err(TransportError::Closed),
+        };
+        log::debug!("RECV {}", text);
+        Ok(Some(serde_json::from_str(&text)?))
+    }
+
+    fn send(&mut self, req: &Req<'_, '_>) -> Result<(), TransportError> {
+        log::debug!("SEND {:?}", &req);
+        let serialized = serde_json::to_string(&req)?;
+        self.socket
+            .lock()
+            .unwrap()
+            .send(Message::text(serialized))?;
         Ok(())
     }
 }
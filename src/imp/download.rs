@@ -36,6 +36,14 @@ impl Download {
         upgrade(&self.artifact)?.save_as(path).await
     }
 
+    pub(crate) async fn read(&self) -> ArcResult<Vec<u8>> {
+        upgrade(&self.artifact)?.read_all().await
+    }
+
+    pub(crate) async fn cancel(&self) -> ArcResult<()> {
+        upgrade(&self.artifact)?.cancel().await
+    }
+
     pub(crate) async fn failure(&self) -> ArcResult<Option<String>> {
         upgrade(&self.artifact)?.failure().await
     }
@@ -1,9 +1,11 @@
 use crate::imp::{
     core::*,
     frame::Frame,
+    js_handle::{self, JsHandle},
     prelude::*,
     utils::{
-        ElementState, File, FloatRect, KeyboardModifier, MouseButton, Position, ScreenshotType,
+        ElementState, File, FloatRect, KeyboardModifier, MouseButton, Position,
+        ScreenshotAnimations, ScreenshotScale, ScreenshotType, SetInputFilesSource,
         WaitForSelectorState,
     },
 };
@@ -19,9 +21,9 @@ macro_rules! is_checked {
         pub(crate) async fn $f(&self) -> ArcResult<bool> {
             let v = send_message!(self, $m, Map::new());
             let b = first(&v)
-                .ok_or(Error::InvalidParams)?
+                .ok_or(Error::InvalidMessage)?
                 .as_bool()
-                .ok_or(Error::InvalidParams)?;
+                .ok_or(Error::InvalidMessage)?;
             Ok(b)
         }
     };
@@ -32,6 +34,26 @@ impl ElementHandle {
         Self { channel }
     }
 
+    pub(crate) async fn dispose(&self) -> ArcResult<()> {
+        let _ = send_message!(self, "dispose", Map::new());
+        Ok(())
+    }
+
+    pub(crate) async fn get_property(&self, name: &str) -> ArcResult<Weak<JsHandle>> {
+        js_handle::get_property(self, name).await
+    }
+
+    pub(crate) async fn get_properties(&self) -> ArcResult<HashMap<String, Weak<JsHandle>>> {
+        js_handle::get_properties(self).await
+    }
+
+    pub(crate) async fn json_value<U>(&self) -> ArcResult<U>
+    where
+        U: DeserializeOwned,
+    {
+        js_handle::json_value(self).await
+    }
+
     pub(crate) async fn query_selector(
         &self,
         selector: &str,
@@ -54,7 +76,7 @@ impl ElementHandle {
         let mut args = HashMap::new();
         args.insert("selector", selector);
         let v = send_message!(self, "querySelectorAll", args);
-        let first = first(&v).ok_or(Error::InvalidParams)?;
+        let first = first(&v).ok_or(Error::InvalidMessage)?;
         let elements: Vec<OnlyGuid> =
             serde_json::from_value((*first).clone()).map_err(Error::Serde)?;
         let es = elements
@@ -203,8 +225,15 @@ impl ElementHandle {
         Ok(Some(f))
     }
 
-    pub(crate) async fn screenshot(&self, args: ScreenshotArgs<'_>) -> ArcResult<Vec<u8>> {
-        let path = args.path.clone();
+    pub(crate) async fn screenshot(&self, mut args: ScreenshotArgs<'_>) -> ArcResult<Vec<u8>> {
+        if args.r#type.is_none() {
+            args.r#type = args.path.and_then(ScreenshotType::from_path);
+        }
+        if args.quality.is_some() && args.r#type != Some(ScreenshotType::Jpeg) {
+            let msg = "quality is only supported for jpeg screenshots";
+            return Err(Error::InvalidParams(msg.into()).into());
+        }
+        let path = args.path;
         let v = send_message!(self, "screenshot", args);
         let b64 = only_str(&v)?;
         let bytes = general_purpose::STANDARD
@@ -266,10 +295,10 @@ impl ElementHandle {
 
     pub(crate) async fn select_option(&self, args: SelectOptionArgs) -> ArcResult<Vec<String>> {
         let v = send_message!(self, "selectOption", args);
-        let first = first(&v).ok_or(Error::InvalidParams)?;
+        let first = first(&v).ok_or(Error::InvalidMessage)?;
         let ss = first
             .as_array()
-            .ok_or(Error::InvalidParams)?
+            .ok_or(Error::InvalidMessage)?
             .iter()
             .filter_map(|v| v.as_str())
             .map(ToOwned::to_owned)
@@ -278,6 +307,9 @@ impl ElementHandle {
     }
 
     pub(crate) async fn set_input_files(&self, args: SetInputFilesArgs) -> ArcResult<()> {
+        if args.local_paths.is_some() && self.channel().is_remote() {
+            return Err(Error::RemoteInputFiles.into());
+        }
         let _ = send_message!(self, "setInputFiles", args);
         Ok(())
     }
@@ -400,6 +432,8 @@ pub(crate) struct ScreenshotArgs<'a> {
     pub(crate) r#type: Option<ScreenshotType>,
     pub(crate) quality: Option<i64>,
     pub(crate) omit_background: Option<bool>,
+    pub(crate) scale: Option<ScreenshotScale>,
+    pub(crate) animations: Option<ScreenshotAnimations>,
 }
 
 #[skip_serializing_none]
@@ -452,7 +486,51 @@ pub(crate) enum Opt {
 #[derive(Serialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct SetInputFilesArgs {
-    pub(crate) files: Vec<File>,
+    #[serde(rename = "payloads")]
+    pub(crate) files: Option<Vec<File>>,
+    pub(crate) local_paths: Option<Vec<PathBuf>>,
     pub(crate) timeout: Option<f64>,
     pub(crate) no_wait_after: Option<bool>,
 }
+
+impl SetInputFilesArgs {
+    pub(crate) fn from_sources(
+        sources: Vec<SetInputFilesSource>,
+        timeout: Option<f64>,
+    ) -> Result<Self, Error> {
+        let (files, local_paths) = split_input_files(sources)?;
+        Ok(Self {
+            files,
+            local_paths,
+            timeout,
+            ..Self::default()
+        })
+    }
+}
+
+/// Splits `sources` into payloads and absolute local paths, exactly one of which is `Some`.
+/// In-memory files are sent as payloads. Paths are passed as they are so that the driver reads them.
+pub(crate) fn split_input_files(
+    sources: Vec<SetInputFilesSource>,
+) -> Result<(Option<Vec<File>>, Option<Vec<PathBuf>>), Error> {
+    let mut files = Vec::new();
+    let mut paths = Vec::new();
+    for x in sources {
+        match x {
+            SetInputFilesSource::File(f) => files.push(f),
+            SetInputFilesSource::Path(p) => paths.push(p),
+        }
+    }
+    if paths.is_empty() {
+        Ok((Some(files), None))
+    } else if files.is_empty() {
+        let cwd = std::env::current_dir()?;
+        Ok((None, Some(paths.into_iter().map(|p| cwd.join(p)).collect())))
+    } else {
+        Err(mixed_input_files())
+    }
+}
+
+pub(crate) fn mixed_input_files() -> Error {
+    Error::InvalidParams("in-memory files and local paths can't be set at once".into())
+}
@@ -1,12 +1,15 @@
 pub(crate) use crate::imp::element_handle::Opt;
 use crate::imp::{
     core::*,
-    element_handle::ElementHandle,
+    element_handle::{split_input_files, ElementHandle},
     js_handle::JsHandle,
     page::Page,
     prelude::*,
+    request::Request,
     response::Response,
-    utils::{DocumentLoadState, File, KeyboardModifier, MouseButton, Position},
+    utils::{
+        DocumentLoadState, File, KeyboardModifier, MouseButton, Position, SetInputFilesSource,
+    },
 };
 use std::{collections::HashSet, iter::FromIterator};
 
@@ -25,6 +28,7 @@ struct Variable {
     page: Option<Weak<Page>>,
     child_frames: Vec<Weak<Frame>>,
     load_states: HashSet<DocumentLoadState>,
+    detached: bool,
 }
 
 macro_rules! is_checked {
@@ -40,9 +44,9 @@ macro_rules! is_checked {
             let args = Args { selector, timeout };
             let v = send_message!(self, $m, args);
             let b = first(&v)
-                .ok_or(Error::InvalidParams)?
+                .ok_or(Error::InvalidMessage)?
                 .as_bool()
-                .ok_or(Error::InvalidParams)?;
+                .ok_or(Error::InvalidMessage)?;
             Ok(b)
         }
     };
@@ -83,6 +87,7 @@ impl Frame {
             page: None,
             child_frames: Vec::new(),
             load_states: HashSet::from_iter(load_states),
+            detached: false,
         });
         Ok(Self {
             channel,
@@ -120,6 +125,13 @@ impl Frame {
     }
 
     pub(crate) async fn tap(&self, args: TapArgs<'_>) -> ArcResult<()> {
+        let has_touch = self
+            .page()
+            .and_then(|p| p.upgrade())
+            .and_then(|p| p.has_touch());
+        if has_touch == Some(false) {
+            return Err(Error::TouchNotEnabled.into());
+        }
         let _ = send_message!(self, "tap", args);
         Ok(())
     }
@@ -168,6 +180,17 @@ impl Frame {
         Ok(s.into())
     }
 
+    pub(crate) async fn aria_snapshot(
+        &self,
+        selector: &str,
+        timeout: Option<f64>,
+    ) -> ArcResult<String> {
+        let args = SelectorTimeout { selector, timeout };
+        let v = send_message!(self, "ariaSnapshot", args);
+        let s = only_str(&v)?;
+        Ok(s.into())
+    }
+
     pub(crate) async fn get_attribute(
         &self,
         selector: &str,
@@ -214,7 +237,7 @@ impl Frame {
         let mut args = HashMap::new();
         args.insert("selector", selector);
         let v = send_message!(self, "querySelectorAll", args);
-        let first = first(&v).ok_or(Error::InvalidParams)?;
+        let first = first(&v).ok_or(Error::InvalidMessage)?;
         let elements: Vec<OnlyGuid> =
             serde_json::from_value((*first).clone()).map_err(Error::Serde)?;
         let es = elements
@@ -295,6 +318,11 @@ impl Frame {
         Ok(())
     }
 
+    pub(crate) async fn drag_and_drop(&self, args: DragAndDropArgs<'_, '_>) -> ArcResult<()> {
+        let _ = send_message!(self, "dragAndDrop", args);
+        Ok(())
+    }
+
     pub(crate) async fn add_script_tag(
         &self,
         args: AddScriptTagArgs<'_, '_, '_>,
@@ -307,14 +335,8 @@ impl Frame {
 
     pub(crate) async fn add_style_tag(
         &self,
-        content: &str,
-        url: Option<&str>,
+        args: AddStyleTagArgs<'_>,
     ) -> ArcResult<Weak<ElementHandle>> {
-        let mut args = HashMap::new();
-        args.insert("content", content);
-        if let Some(url) = url {
-            args.insert("url", url);
-        }
         let v = send_message!(self, "addStyleTag", args);
         let guid = only_guid(&v)?;
         let e = get_object!(self.context()?.lock().unwrap(), guid, ElementHandle)?;
@@ -346,7 +368,11 @@ impl Frame {
         Ok(de::from_value(first).map_err(Error::DeserializationPwJson)?)
     }
 
-    async fn evaluate_handle<T>(&self, expression: &str, arg: Option<T>) -> ArcResult<Handle>
+    pub(crate) async fn evaluate_handle<T>(
+        &self,
+        expression: &str,
+        arg: Option<T>,
+    ) -> ArcResult<Handle>
     where
         T: Serialize,
     {
@@ -457,10 +483,10 @@ impl Frame {
 
     pub(crate) async fn select_option(&self, args: SelectOptionArgs<'_>) -> ArcResult<Vec<String>> {
         let v = send_message!(self, "selectOption", args);
-        let first = first(&v).ok_or(Error::InvalidParams)?;
+        let first = first(&v).ok_or(Error::InvalidMessage)?;
         let ss = first
             .as_array()
-            .ok_or(Error::InvalidParams)?
+            .ok_or(Error::InvalidMessage)?
             .iter()
             .filter_map(|v| v.as_str())
             .map(ToOwned::to_owned)
@@ -469,14 +495,21 @@ impl Frame {
     }
 
     pub(crate) async fn set_input_files(&self, args: SetInputFilesArgs<'_>) -> ArcResult<()> {
+        if args.local_paths.is_some() && self.channel().is_remote() {
+            return Err(Error::RemoteInputFiles.into());
+        }
         let _ = send_message!(self, "setInputFiles", args);
         Ok(())
     }
 
     pub(crate) async fn wait_for_function(
         &self,
-        args: WaitForFunctionArgs<'_>,
+        mut args: WaitForFunctionArgs<'_>,
     ) -> ArcResult<Weak<JsHandle>> {
+        if args.arg.is_none() {
+            let undefined = ser::to_value(&None::<()>).map_err(Error::SerializationPwJson)?;
+            args.arg = Some(undefined);
+        }
         let v = send_message!(self, "waitForFunction", args);
         let guid = only_guid(&v)?;
         let h = get_object!(self.context()?.lock().unwrap(), guid, JsHandle)?;
@@ -522,6 +555,47 @@ impl Frame {
         let _ = send_message!(self, "waitForURL", args);
         Ok(())
     }
+
+    /// Waits on `rx`, subscribed before the navigation was triggered, until this frame navigates to a url accepted by
+    /// `url` and the new document reaches `wait_until`. Returns the request of the new document, `None` for
+    /// same-document navigations.
+    pub(crate) async fn wait_for_navigation<M>(
+        &self,
+        rx: broadcast::Receiver<Evt>,
+        url: M,
+        wait_until: DocumentLoadState,
+        timeout: u32,
+    ) -> Result<Option<Weak<Request>>, Error>
+    where
+        M: Fn(&str) -> bool + Send,
+    {
+        let mut navigated: Option<FrameNavigatedEvent> = None;
+        wait_for_event(rx, timeout, |evt| match evt {
+            Evt::Navigated(e) if !url(&e.url) => false,
+            Evt::Navigated(e) => {
+                navigated = Some(e.clone());
+                e.error.is_some()
+                    || e.new_document.is_none()
+                    || wait_until == DocumentLoadState::Commit
+            }
+            Evt::LoadState(s) => navigated.is_some() && *s == wait_until,
+        })
+        .await?;
+        let FrameNavigatedEvent {
+            new_document,
+            error,
+            ..
+        } = navigated.ok_or(Error::ObjectNotFound)?;
+        if let Some(error) = error {
+            return Err(Error::NavigationFailed(error));
+        }
+        let guid = match new_document.as_ref().map(|d| only_guid(&d.request)) {
+            Some(Ok(guid)) => guid,
+            _ => return Ok(None),
+        };
+        let request = get_object!(self.context()?.lock().unwrap(), guid, Request)?;
+        Ok(Some(request))
+    }
 }
 
 // mutable
@@ -538,14 +612,47 @@ impl Frame {
         self.var.lock().unwrap().page.clone()
     }
 
+    /// `None` until changed with `Selectors::set_test_id_attribute`
+    pub(crate) fn test_id_attribute(&self) -> Option<String> {
+        self.context().ok()?.lock().unwrap().test_id_attribute()
+    }
+
+    pub(crate) fn default_navigation_timeout(&self) -> u32 {
+        self.page()
+            .and_then(|p| p.upgrade())
+            .map(|p| p.default_navigation_timeout())
+            .unwrap_or(Page::DEFAULT_TIMEOUT)
+    }
+
     pub(crate) fn set_page(&self, page: Weak<Page>) {
         self.var.lock().unwrap().page = Some(page);
     }
 
     pub(crate) fn parent_frame(&self) -> Option<Weak<Frame>> {
+        if self.is_detached() {
+            return None;
+        }
         self.parent_frame.clone()
     }
 
+    pub(crate) fn is_detached(&self) -> bool {
+        self.var.lock().unwrap().detached
+    }
+
+    /// Called on `frameDetached` of the page. The driver keeps the object alive until the page closes.
+    pub(crate) fn set_detached(&self) {
+        self.var.lock().unwrap().detached = true;
+        if let Some(parent) = self.parent_frame.as_ref().and_then(Weak::upgrade) {
+            let guid = self.guid();
+            parent
+                .var
+                .lock()
+                .unwrap()
+                .child_frames
+                .retain(|c| c.upgrade().map(|c| c.guid() != guid).unwrap_or(false));
+        }
+    }
+
     pub(crate) fn child_frames(&self) -> Vec<Weak<Frame>> {
         self.var.lock().unwrap().child_frames.clone()
     }
@@ -649,7 +756,7 @@ impl IsEvent for Evt {
     }
 }
 
-enum Handle {
+pub(crate) enum Handle {
     Js(Weak<JsHandle>),
     Element(Weak<ElementHandle>),
 }
@@ -669,7 +776,7 @@ impl<'a> GotoArgs<'a, '_> {
     pub(crate) fn new(url: &'a str) -> Self {
         Self {
             url,
-            timeout: Some(30000.0),
+            timeout: None,
             wait_until: None,
             referer: None,
         }
@@ -894,6 +1001,18 @@ impl<'a> CheckArgs<'a> {
     }
 }
 
+#[skip_serializing_none]
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DragAndDropArgs<'a, 'b> {
+    pub(crate) source: &'a str,
+    pub(crate) target: &'b str,
+    pub(crate) source_position: Option<Position>,
+    pub(crate) target_position: Option<Position>,
+    pub(crate) force: Option<bool>,
+    pub(crate) timeout: Option<f64>,
+}
+
 #[skip_serializing_none]
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -916,6 +1035,14 @@ impl<'a, 'b, 'c> AddScriptTagArgs<'a, 'b, 'c> {
     }
 }
 
+#[skip_serializing_none]
+#[derive(Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AddStyleTagArgs<'a> {
+    pub(crate) content: Option<String>,
+    pub(crate) url: Option<&'a str>,
+}
+
 #[skip_serializing_none]
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -947,7 +1074,9 @@ impl<'a> SelectOptionArgs<'a> {
 pub(crate) struct SetInputFilesArgs<'a> {
     selector: &'a str,
 
-    pub(crate) files: Vec<File>,
+    #[serde(rename = "payloads")]
+    pub(crate) files: Option<Vec<File>>,
+    pub(crate) local_paths: Option<Vec<PathBuf>>,
     pub(crate) timeout: Option<f64>,
     pub(crate) no_wait_after: Option<bool>,
 }
@@ -956,11 +1085,26 @@ impl<'a> SetInputFilesArgs<'a> {
     pub(crate) fn new(selector: &'a str) -> Self {
         Self {
             selector,
-            files: Vec::new(),
+            files: None,
+            local_paths: None,
             timeout: None,
             no_wait_after: None,
         }
     }
+
+    pub(crate) fn from_sources(
+        selector: &'a str,
+        sources: Vec<SetInputFilesSource>,
+        timeout: Option<f64>,
+    ) -> Result<Self, Error> {
+        let (files, local_paths) = split_input_files(sources)?;
+        Ok(Self {
+            files,
+            local_paths,
+            timeout,
+            ..Self::new(selector)
+        })
+    }
 }
 
 #[skip_serializing_none]
@@ -969,11 +1113,31 @@ impl<'a> SetInputFilesArgs<'a> {
 pub(crate) struct WaitForFunctionArgs<'a> {
     expression: &'a str,
     pub(crate) timeout: Option<f64>,
+    /// `raf` is the driver default and is sent as no interval.
+    #[serde(
+        rename = "pollingInterval",
+        skip_serializing_if = "polls_on_raf",
+        serialize_with = "polling_interval"
+    )]
     pub(crate) polling: Option<Polling>,
-    // XXX
     pub(crate) arg: Option<Value>,
 }
 
+fn polls_on_raf(polling: &Option<Polling>) -> bool {
+    !matches!(polling, Some(Polling::Millis(_)))
+}
+
+fn polling_interval<S>(polling: &Option<Polling>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::ser::Serializer,
+{
+    match polling {
+        Some(Polling::Millis(x)) => x.serialize(serializer),
+        _ => serializer.serialize_none(),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Polling {
     RequestAnimationFrame,
     Millis(u32),
@@ -1071,5 +1235,12 @@ mod tests {
         assert_eq!(s, "3");
         let s = serde_json::to_string(&Polling::RequestAnimationFrame).unwrap();
         assert_eq!(s, r#""raf""#);
+        let mut args = WaitForFunctionArgs::new("() => true");
+        args.polling = Some(Polling::Millis(3));
+        let v = serde_json::to_value(&args).unwrap();
+        assert_eq!(v["pollingInterval"], 3);
+        args.polling = Some(Polling::RequestAnimationFrame);
+        let v = serde_json::to_value(&args).unwrap();
+        assert!(v.get("pollingInterval").is_none());
     }
 }
@@ -20,31 +20,11 @@ impl JsHandle {
     }
 
     pub(crate) async fn get_property(&self, name: &str) -> ArcResult<Weak<JsHandle>> {
-        let mut args = HashMap::new();
-        args.insert("name", name);
-        let v = send_message!(self, "getProperty", args);
-        let guid = only_guid(&v)?;
-        let j = get_object!(self.context()?.lock().unwrap(), guid, JsHandle)?;
-        Ok(j)
+        get_property(self, name).await
     }
 
     pub(crate) async fn get_properties(&self) -> ArcResult<HashMap<String, Weak<JsHandle>>> {
-        let v = send_message!(self, "getPropertyList", Map::new());
-        let first = first(&v).ok_or(Error::InvalidParams)?;
-        let properties: Vec<Property> =
-            serde_json::from_value((*first).clone()).map_err(Error::Serde)?;
-        let ps = properties
-            .into_iter()
-            .map(
-                |Property {
-                     name,
-                     value: OnlyGuid { guid },
-                 }| {
-                    get_object!(self.context()?.lock().unwrap(), &guid, JsHandle).map(|o| (name, o))
-                },
-            )
-            .collect::<Result<HashMap<_, _>, Error>>()?;
-        Ok(ps)
+        get_properties(self).await
     }
 
     pub(crate) async fn dispose(&self) -> ArcResult<()> {
@@ -56,12 +36,56 @@ impl JsHandle {
     where
         U: DeserializeOwned,
     {
-        let v = send_message!(self, "jsonValue", Map::new());
-        let first = first(&v).ok_or(Error::ObjectNotFound)?;
-        Ok(de::from_value(first).map_err(Error::DeserializationPwJson)?)
+        json_value(self).await
     }
 }
 
+// An ElementHandle channel is also a JSHandle on the driver side, so both share these.
+
+pub(crate) async fn get_property<R>(r: &R, name: &str) -> ArcResult<Weak<JsHandle>>
+where
+    R: RemoteObject,
+{
+    let mut args = HashMap::new();
+    args.insert("name", name);
+    let v = send_message!(r, "getProperty", args);
+    let guid = only_guid(&v)?;
+    let j = get_object!(r.context()?.lock().unwrap(), guid, JsHandle)?;
+    Ok(j)
+}
+
+pub(crate) async fn get_properties<R>(r: &R) -> ArcResult<HashMap<String, Weak<JsHandle>>>
+where
+    R: RemoteObject,
+{
+    let v = send_message!(r, "getPropertyList", Map::new());
+    let first = first(&v).ok_or(Error::InvalidMessage)?;
+    let properties: Vec<Property> =
+        serde_json::from_value((*first).clone()).map_err(Error::Serde)?;
+    let ps = properties
+        .into_iter()
+        .map(
+            |Property {
+                 name,
+                 value: OnlyGuid { guid },
+             }| {
+                get_object!(r.context()?.lock().unwrap(), &guid, JsHandle).map(|o| (name, o))
+            },
+        )
+        .collect::<Result<HashMap<_, _>, Error>>()?;
+    Ok(ps)
+}
+
+pub(crate) async fn json_value<R, U>(r: &R) -> ArcResult<U>
+where
+    R: RemoteObject,
+    U: DeserializeOwned,
+{
+    let v = send_message!(r, "jsonValue", Map::new());
+    let first = first(&v).ok_or(Error::ObjectNotFound)?;
+    Ok(de::from_value(first).map_err(Error::DeserializationPwJson)?)
+}
+
 impl JsHandle {
     fn set_preview(&self, preview: String) {
         let var = &mut self.var.lock().unwrap();
@@ -0,0 +1,115 @@
+use crate::imp::{core::*, prelude::*, utils::Header};
+
+#[derive(Debug)]
+pub(crate) struct LocalUtils {
+    channel: ChannelOwner,
+}
+
+impl LocalUtils {
+    pub(crate) fn new(channel: ChannelOwner) -> Self {
+        Self { channel }
+    }
+
+    pub(crate) async fn har_open(&self, file: &Path) -> ArcResult<String> {
+        #[derive(Serialize)]
+        struct Args<'a> {
+            file: &'a Path,
+        }
+        let v = send_message!(self, "harOpen", Args { file });
+        let HarOpenResult { har_id, error } =
+            serde_json::from_value((*v).clone()).map_err(Error::Serde)?;
+        match (har_id, error) {
+            (Some(har_id), _) => Ok(har_id),
+            (None, error) => Err(Error::Har(error.unwrap_or_default()).into()),
+        }
+    }
+
+    pub(crate) async fn har_lookup(&self, args: HarLookupArgs<'_>) -> ArcResult<HarLookupResult> {
+        let v = send_message!(self, "harLookup", args);
+        let res = serde_json::from_value((*v).clone()).map_err(Error::Serde)?;
+        Ok(res)
+    }
+
+    pub(crate) async fn har_close(&self, har_id: &str) -> ArcResult<()> {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Args<'a> {
+            har_id: &'a str,
+        }
+        let _ = send_message!(self, "harClose", Args { har_id });
+        Ok(())
+    }
+}
+
+impl RemoteObject for LocalUtils {
+    fn channel(&self) -> &ChannelOwner {
+        &self.channel
+    }
+    fn channel_mut(&mut self) -> &mut ChannelOwner {
+        &mut self.channel
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HarOpenResult {
+    har_id: Option<String>,
+    error: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HarLookupArgs<'a> {
+    pub(crate) har_id: &'a str,
+    pub(crate) url: &'a str,
+    pub(crate) method: &'a str,
+    pub(crate) headers: Vec<Header>,
+    /// base64
+    pub(crate) post_data: Option<String>,
+    pub(crate) is_navigation_request: bool,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum HarAction {
+    Error,
+    Redirect,
+    Fulfill,
+    Noentry,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HarLookupResult {
+    pub(crate) action: HarAction,
+    pub(crate) message: Option<String>,
+    #[serde(rename = "redirectURL")]
+    pub(crate) redirect_url: Option<String>,
+    pub(crate) status: Option<i32>,
+    pub(crate) headers: Option<Vec<Header>>,
+    /// base64
+    pub(crate) body: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup_result() {
+        let v = serde_json::json!({
+            "action": "fulfill",
+            "status": 200,
+            "headers": [{"name": "content-type", "value": "text/html"}],
+            "body": "PGgxPmhhcjwvaDE+"
+        });
+        let res: HarLookupResult = serde_json::from_value(v).unwrap();
+        assert_eq!(res.action, HarAction::Fulfill);
+        assert_eq!(res.status, Some(200));
+        let v = serde_json::json!({"action": "redirect", "redirectURL": "https://a.test/"});
+        let res: HarLookupResult = serde_json::from_value(v).unwrap();
+        assert_eq!(res.action, HarAction::Redirect);
+        assert_eq!(res.redirect_url.as_deref(), Some("https://a.test/"));
+    }
+}
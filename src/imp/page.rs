@@ -1,19 +1,21 @@
 use crate::imp::{
+    binding_call::{self, BindingCall, BindingHandler},
     browser_context::BrowserContext,
+    cdp_session::CDPSession,
     console_message::ConsoleMessage,
     core::*,
+    dialog::Dialog,
     download::Download,
     element_handle::may_save,
     file_hooser::FileChooser,
     frame::Frame,
-    dialog::Dialog,
     prelude::*,
     request::Request,
     response::Response,
     route::Route,
     utils::{
         ColorScheme, DocumentLoadState, FloatRect, Header, Length, MouseButton, PdfMargins,
-        ScreenshotType, Viewport,
+        ScreenshotAnimations, ScreenshotScale, ScreenshotType, Viewport,
     },
     video::Video,
     websocket::WebSocket,
@@ -42,6 +44,12 @@ pub(crate) struct Variable {
     video: Option<Video>,
     routes: Vec<RouteEntry>,
     websocket_routes: Vec<WebSocketRouteEntry>,
+    cdp_session: Option<Weak<CDPSession>>,
+    bindings: HashMap<String, BindingEntry>,
+    /// Exposed to the driver but hidden by `remove_binding`
+    hidden_bindings: Vec<String>,
+    extra_http_headers: Vec<Header>,
+    locator_handlers: HashMap<u32, LocatorHandlerEntry>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -126,6 +134,35 @@ impl fmt::Debug for WebSocketRouteEntry {
     }
 }
 
+/// Runs while the driver waits in an action for an overlay to go away.
+pub(crate) type LocatorHandler =
+    Arc<dyn Fn() -> futures::future::BoxFuture<'static, ()> + Send + Sync + 'static>;
+
+#[derive(Clone)]
+struct LocatorHandlerEntry {
+    selector: String,
+    handler: LocatorHandler,
+}
+
+impl fmt::Debug for LocatorHandlerEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocatorHandlerEntry")
+            .field("selector", &self.selector)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Clone)]
+struct BindingEntry {
+    handler: BindingHandler,
+}
+
+impl fmt::Debug for BindingEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BindingEntry").finish_non_exhaustive()
+    }
+}
+
 impl fmt::Debug for RouteEntry {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("RouteEntry")
@@ -182,7 +219,7 @@ macro_rules! mouse_down {
 }
 
 impl Page {
-    const DEFAULT_TIMEOUT: u32 = 30000;
+    pub(crate) const DEFAULT_TIMEOUT: u32 = 30000;
 
     pub(crate) fn try_new(ctx: &Context, channel: ChannelOwner) -> Result<Self, Error> {
         let Initializer {
@@ -191,7 +228,7 @@ impl Page {
         } = serde_json::from_value(channel.initializer.clone())?;
         let browser_context = match &channel.parent {
             Some(RemoteWeak::BrowserContext(c)) => c.clone(),
-            _ => return Err(Error::InvalidParams),
+            _ => return Err(Error::InvalidMessage),
         };
         let main_frame = get_object!(ctx, &guid, Frame)?;
         let var = Mutex::new(Variable {
@@ -217,6 +254,11 @@ impl Page {
         self.browser_context.clone()
     }
 
+    /// `None` when the context's options are unknown
+    pub(crate) fn has_touch(&self) -> Option<bool> {
+        self.browser_context.upgrade().and_then(|c| c.has_touch())
+    }
+
     pub(crate) fn main_frame(&self) -> Weak<Frame> {
         self.main_frame.clone()
     }
@@ -239,7 +281,7 @@ impl Page {
         Ok(())
     }
 
-    pub(crate) async fn key_input_text(&self, text: &str) -> Result<(), Arc<Error>> {
+    pub(crate) async fn key_insert_text(&self, text: &str) -> Result<(), Arc<Error>> {
         let mut args = HashMap::new();
         args.insert("text", text);
         let _ = send_message!(self, "keyboardInsertText", args);
@@ -255,7 +297,7 @@ impl Page {
             delay: Option<f64>,
         }
         let args = Args { text, delay };
-        let _ = send_message!(self, "keyboardInsertText", args);
+        let _ = send_message!(self, "keyboardType", args);
         Ok(())
     }
 
@@ -273,6 +315,9 @@ impl Page {
     }
 
     pub(crate) async fn screen_tap(&self, x: f64, y: f64) -> Result<(), Arc<Error>> {
+        if self.has_touch() == Some(false) {
+            return Err(Error::TouchNotEnabled.into());
+        }
         #[derive(Serialize)]
         #[serde(rename_all = "camelCase")]
         struct Args {
@@ -303,6 +348,18 @@ impl Page {
         Ok(())
     }
 
+    pub(crate) async fn mouse_wheel(&self, delta_x: f64, delta_y: f64) -> Result<(), Arc<Error>> {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Args {
+            delta_x: f64,
+            delta_y: f64,
+        }
+        let args = Args { delta_x, delta_y };
+        let _ = send_message!(self, "mouseWheel", args);
+        Ok(())
+    }
+
     mouse_down! {mouse_down, "mouseDown"}
     mouse_down! {mouse_up, "mouseUp"}
 
@@ -319,6 +376,158 @@ impl Page {
         self.mouse_click(args).await
     }
 
+    /// Installs `window[name]` in every frame of this page. The driver re-installs it after
+    /// each navigation, so it survives `goto` and `reload`. A binding hidden by
+    /// `remove_binding` is still known to the driver and is only shown again.
+    pub(crate) async fn expose_binding(
+        &self,
+        name: &str,
+        handler: BindingHandler,
+    ) -> ArcResult<()> {
+        #[derive(Serialize)]
+        struct Args<'a> {
+            name: &'a str,
+        }
+        let hidden = {
+            let hidden_bindings = &mut self.var.lock().unwrap().hidden_bindings;
+            let i = hidden_bindings.iter().position(|n| n == name);
+            i.map(|i| hidden_bindings.remove(i)).is_some()
+        };
+        if !hidden {
+            let _ = send_message!(self, "exposeBinding", Args { name });
+        }
+        self.var
+            .lock()
+            .unwrap()
+            .bindings
+            .insert(name.to_owned(), BindingEntry { handler });
+        if hidden {
+            self.run_in_documents(&binding_call::restore_script(name)?)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// The driver has no way to uninstall a binding, so the handler is dropped and
+    /// `window[name]` is hidden from the current documents and, through an init script,
+    /// from every document created afterwards until it is exposed again.
+    pub(crate) async fn remove_binding(&self, name: &str) -> ArcResult<()> {
+        {
+            let var = &mut self.var.lock().unwrap();
+            if var.bindings.remove(name).is_none() {
+                return Ok(());
+            }
+            var.hidden_bindings.push(name.to_owned());
+        }
+        self.run_in_documents(&binding_call::hide_script(name)?)
+            .await
+    }
+
+    /// Runs `source` in the current documents and, as an init script, in later ones.
+    async fn run_in_documents(&self, source: &str) -> ArcResult<()> {
+        self.add_init_script(source).await?;
+        let expression = format!("() => {{ {} }}", source);
+        for frame in self.frames().iter().filter_map(Weak::upgrade) {
+            let _ = frame.evaluate::<(), ()>(&expression, None).await;
+        }
+        Ok(())
+    }
+
+    fn on_binding_call(&self, ctx: &Context, params: Map<String, Value>) -> Result<(), Error> {
+        let first = first_object(&params).ok_or(Error::InvalidMessage)?;
+        let OnlyGuid { guid } = serde_json::from_value((*first).clone())?;
+        let binding = get_object!(ctx, &guid, BindingCall)?;
+        let name = upgrade(&binding)?.name().to_owned();
+        let entry = self.var.lock().unwrap().bindings.get(&name).cloned();
+        let handler = match entry {
+            Some(BindingEntry { handler }) => Some(handler),
+            None => self
+                .browser_context()
+                .upgrade()
+                .and_then(|c| c.binding(&name)),
+        };
+        if let Some(handler) = handler {
+            binding_call::dispatch(binding, handler);
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn register_locator_handler(
+        &self,
+        selector: &str,
+        handler: LocatorHandler,
+    ) -> ArcResult<()> {
+        #[derive(Serialize)]
+        struct Args<'a> {
+            selector: &'a str,
+        }
+        let v = send_message!(self, "registerLocatorHandler", Args { selector });
+        let uid = first(&v)
+            .and_then(Value::as_u64)
+            .ok_or(Error::InvalidMessage)? as u32;
+        let entry = LocatorHandlerEntry {
+            selector: selector.to_owned(),
+            handler,
+        };
+        self.var.lock().unwrap().locator_handlers.insert(uid, entry);
+        Ok(())
+    }
+
+    /// Unregisters every handler added for `selector`.
+    pub(crate) async fn unregister_locator_handlers(&self, selector: &str) -> ArcResult<()> {
+        #[derive(Serialize)]
+        struct Args {
+            uid: u32,
+        }
+        let uids: Vec<u32> = {
+            let handlers = &mut self.var.lock().unwrap().locator_handlers;
+            let uids = handlers
+                .iter()
+                .filter(|(_, e)| e.selector == selector)
+                .map(|(uid, _)| *uid)
+                .collect();
+            handlers.retain(|_, e| e.selector != selector);
+            uids
+        };
+        for uid in uids {
+            let _ = send_message!(self, "unregisterLocatorHandler", Args { uid });
+        }
+        Ok(())
+    }
+
+    async fn resolve_locator_handler(&self, uid: u32) -> ArcResult<()> {
+        #[derive(Serialize)]
+        struct Args {
+            uid: u32,
+        }
+        let _ = send_message!(self, "resolveLocatorHandlerNoReply", Args { uid });
+        Ok(())
+    }
+
+    fn on_locator_handler_triggered(
+        &self,
+        ctx: &Context,
+        params: Map<String, Value>,
+    ) -> Result<(), Error> {
+        #[derive(Deserialize)]
+        struct De {
+            uid: u32,
+        }
+        let De { uid } = serde_json::from_value(params.into())?;
+        let entry = self.var.lock().unwrap().locator_handlers.get(&uid).cloned();
+        let this = get_object!(ctx, self.guid(), Page)?;
+        tokio::spawn(async move {
+            if let Some(LocatorHandlerEntry { handler, .. }) = entry {
+                handler().await;
+            }
+            // The action stays blocked until the handler is resolved
+            if let Some(this) = this.upgrade() {
+                let _ = this.resolve_locator_handler(uid).await;
+            }
+        });
+        Ok(())
+    }
+
     pub(crate) async fn route(
         &self,
         glob: &str,
@@ -568,7 +777,7 @@ impl Page {
     }
 
     fn on_route(&self, ctx: &Context, params: Map<String, Value>) -> Result<(), Error> {
-        let first = first_object(&params).ok_or(Error::InvalidParams)?;
+        let first = first_object(&params).ok_or(Error::InvalidMessage)?;
         let OnlyGuid { guid } = serde_json::from_value((*first).clone())?;
         let route: Weak<Route> = get_object!(ctx, &guid, Route)?;
         let mut handled = false;
@@ -597,7 +806,7 @@ impl Page {
     }
 
     fn on_web_socket_route(&self, ctx: &Context, params: Map<String, Value>) -> Result<(), Error> {
-        let first = first_object(&params).ok_or(Error::InvalidParams)?;
+        let first = first_object(&params).ok_or(Error::InvalidMessage)?;
         let OnlyGuid { guid } = serde_json::from_value((*first).clone())?;
         let route = get_object!(ctx, &guid, WebSocketRoute)?;
         let mut handled = false;
@@ -631,21 +840,44 @@ impl Page {
         &self,
         args: AccessibilitySnapshotArgs,
     ) -> ArcResult<Option<AccessibilitySnapshotResponse>> {
-        let v = send_message!(self, "accessibilitySnapshot", args);
-        let first = match first(&v) {
+        let first = match self.accessibility_snapshot_json(args).await? {
             None => return Ok(None),
             Some(x) => x,
         };
         let res: AccessibilitySnapshotResponse =
-            serde_json::from_value((*first).clone()).map_err(Error::Serde)?;
+            serde_json::from_value(first).map_err(Error::Serde)?;
         Ok(Some(res))
     }
 
+    pub(crate) async fn accessibility_snapshot_json(
+        &self,
+        args: AccessibilitySnapshotArgs,
+    ) -> ArcResult<Option<Value>> {
+        let v = send_message!(self, "accessibilitySnapshot", args);
+        Ok(first(&v).map(|x| (*x).clone()))
+    }
+
     pub(crate) async fn bring_to_front(&self) -> ArcResult<()> {
         let _ = send_message!(self, "bringToFront", Map::new());
         Ok(())
     }
 
+    /// The driver emits `fileChooser` only after subscribing to it. A subscribed page doesn't show
+    /// native file choosers.
+    pub(crate) async fn intercept_file_chooser(&self) -> ArcResult<()> {
+        #[derive(Serialize)]
+        struct Args {
+            event: &'static str,
+            enabled: bool,
+        }
+        let args = Args {
+            event: "fileChooser",
+            enabled: true,
+        };
+        let _ = send_message!(self, "updateSubscription", args);
+        Ok(())
+    }
+
     pub(crate) async fn add_init_script(&self, source: &str) -> ArcResult<()> {
         let mut args = HashMap::new();
         args.insert("source", source);
@@ -657,6 +889,9 @@ impl Page {
         &self,
         args: PdfArgs<'_, '_, '_, '_, '_, '_, '_, '_, '_, '_>,
     ) -> ArcResult<Vec<u8>> {
+        if let Some(ranges) = args.page_ranges {
+            validate_page_ranges(ranges)?;
+        }
         let path = args.path.clone();
         let v = send_message!(self, "pdf", args);
         let b64 = only_str(&v)?;
@@ -679,7 +914,42 @@ impl Page {
         Ok(())
     }
 
-    pub(crate) async fn screenshot(&self, args: ScreenshotArgs) -> ArcResult<Vec<u8>> {
+    pub(crate) async fn screenshot(&self, mut args: ScreenshotArgs) -> ArcResult<Vec<u8>> {
+        if args.r#type.is_none() {
+            args.r#type = args.path.as_deref().and_then(ScreenshotType::from_path);
+        }
+        if args.clip.is_some() && args.full_page == Some(true) {
+            let msg = "clip and full_page can't be set at once";
+            return Err(Error::InvalidParams(msg.into()).into());
+        }
+        if args.quality.is_some() && args.r#type != Some(ScreenshotType::Jpeg) {
+            let msg = "quality is only supported for jpeg screenshots";
+            return Err(Error::InvalidParams(msg.into()).into());
+        }
+        if args.wait_for_fonts {
+            let timeout = args
+                .timeout
+                .unwrap_or_else(|| self.default_timeout() as f64);
+            let started = std::time::Instant::now();
+            let frame = upgrade(&self.main_frame)?;
+            frame
+                .evaluate::<f64, ()>(
+                    "timeout => {
+                        const ready = document.fonts.ready.then(() => {});
+                        if (!timeout) return ready;
+                        const expired = new Promise((_, reject) => setTimeout(
+                            () => reject(new Error(`Timeout ${timeout}ms exceeded waiting for fonts`)), timeout));
+                        return Promise.race([ready, expired]);
+                    }",
+                    Some(timeout),
+                )
+                .await?;
+            // 0 disables the timeout. Otherwise the capture gets what the font wait left over.
+            if timeout > 0. {
+                let elapsed = started.elapsed().as_secs_f64() * 1000.;
+                args.timeout = Some((timeout - elapsed).max(1.));
+            }
+        }
         let path = args.path.clone();
         let v = send_message!(self, "screenshot", args);
         let b64 = only_str(&v)?;
@@ -695,6 +965,41 @@ impl Page {
         Ok(())
     }
 
+    pub(crate) async fn emulate_vision_deficiency(
+        &self,
+        deficiency: VisionDeficiency,
+    ) -> ArcResult<()> {
+        #[derive(Serialize)]
+        struct Params {
+            r#type: VisionDeficiency,
+        }
+        let session = self.cdp_session("emulate_vision_deficiency").await?;
+        let params = serde_json::to_value(Params { r#type: deficiency }).map_err(Error::Serde)?;
+        upgrade(&session)?
+            .send("Emulation.setEmulatedVisionDeficiency", Some(params))
+            .await?;
+        Ok(())
+    }
+
+    /// CDP session attached to this page, kept alive so that emulation overrides persist.
+    async fn cdp_session(&self, feature: &'static str) -> ArcResult<Weak<CDPSession>> {
+        if let Some(s) = self.var.lock().unwrap().cdp_session.clone() {
+            if s.upgrade().is_some() {
+                return Ok(s);
+            }
+        }
+        let context = upgrade(&self.browser_context)?;
+        if let Some(browser) = context.browser().and_then(|b| b.upgrade()) {
+            if browser.name() != "chromium" {
+                return Err(Error::ChromiumOnly(feature).into());
+            }
+        }
+        let this = get_object!(self.context()?.lock().unwrap(), self.guid(), Page)?;
+        let session = context.new_cdp_session_with_page(this).await?;
+        self.var.lock().unwrap().cdp_session = Some(session.clone());
+        Ok(session)
+    }
+
     pub(crate) async fn opener(&self) -> ArcResult<Option<Weak<Page>>> {
         let v = send_message!(self, "opener", Map::new());
         let guid = match as_only_guid(&v) {
@@ -709,15 +1014,37 @@ impl Page {
     where
         T: IntoIterator<Item = (String, String)>,
     {
+        let headers = headers.into_iter().map(Header::from).collect();
+        self.send_extra_http_headers(headers).await
+    }
+
+    /// Merges `headers` into the ones set before. A header replaces an existing one with the same
+    /// name, compared case-insensitively, keeping its position.
+    pub(crate) async fn add_extra_http_headers<T>(&self, headers: T) -> ArcResult<()>
+    where
+        T: IntoIterator<Item = (String, String)>,
+    {
+        let mut merged = self.extra_http_headers();
+        for h in headers.into_iter().map(Header::from) {
+            match merged
+                .iter_mut()
+                .find(|x| x.name.eq_ignore_ascii_case(&h.name))
+            {
+                Some(x) => *x = h,
+                None => merged.push(h),
+            }
+        }
+        self.send_extra_http_headers(merged).await
+    }
+
+    async fn send_extra_http_headers(&self, headers: Vec<Header>) -> ArcResult<()> {
         #[derive(Serialize)]
         #[serde(rename_all = "camelCase")]
-        struct Args {
-            headers: Vec<Header>,
+        struct Args<'a> {
+            headers: &'a [Header],
         }
-        let args = Args {
-            headers: headers.into_iter().map(Header::from).collect(),
-        };
-        let _ = send_message!(self, "setExtraHTTPHeaders", args);
+        let _ = send_message!(self, "setExtraHTTPHeaders", Args { headers: &headers });
+        self.var.lock().unwrap().extra_http_headers = headers;
         Ok(())
     }
 }
@@ -793,6 +1120,10 @@ impl Page {
         Ok(())
     }
 
+    fn extra_http_headers(&self) -> Vec<Header> {
+        self.var.lock().unwrap().extra_http_headers.clone()
+    }
+
     pub(crate) fn video(&self) -> Option<Video> {
         self.var.lock().unwrap().video.clone()
     }
@@ -825,6 +1156,7 @@ impl Page {
             .cloned()
             .collect();
         let f = get_object!(ctx, &guid, Frame)?;
+        upgrade(&f)?.set_detached();
         self.emit_event(Evt::FrameDetached(f));
         Ok(())
     }
@@ -889,6 +1221,16 @@ impl Page {
         Ok(())
     }
 
+    /// Whether the browser was obtained through `BrowserType::connect`. Artifacts of remote
+    /// browsers live on another machine and can only be streamed.
+    fn is_remote(&self) -> bool {
+        self.browser_context
+            .upgrade()
+            .and_then(|c| c.browser())
+            .and_then(|b| b.upgrade())
+            .is_some_and(|b| b.is_remote())
+    }
+
     fn on_download(&self, ctx: &Context, params: Map<String, Value>) -> Result<(), Error> {
         #[derive(Deserialize)]
         #[serde(rename_all = "camelCase")]
@@ -903,8 +1245,7 @@ impl Page {
             artifact: OnlyGuid { guid },
         } = serde_json::from_value(params.into())?;
         let artifact = get_object!(ctx, &guid, Artifact)?;
-        // TODO: set_is_remote
-        // artifactObject._isRemote = !!this._browserContext._browser && this._browserContext._browser._isRemote;
+        upgrade(&artifact)?.set_is_remote(self.is_remote());
         let download = Download::new(artifact, url, suggested_filename);
         self.emit_event(Evt::Download(Arc::new(download)));
         Ok(())
@@ -914,6 +1255,7 @@ impl Page {
         let v = params.into();
         let guid = only_guid(&v)?;
         let artifact = get_object!(ctx, guid, Artifact)?;
+        upgrade(&artifact)?.set_is_remote(self.is_remote());
         let video = Video::new(artifact);
         self.set_video(video.clone())?;
         self.emit_event(Evt::Video(video));
@@ -933,8 +1275,8 @@ impl Page {
         } = serde_json::from_value(params.into())?;
         let element = get_object!(ctx, &guid, ElementHandle)?;
         let this = get_object!(ctx, self.guid(), Page)?;
-        let _file_chooser = FileChooser::new(this, element, is_multiple);
-        // self.emit_event(Evt::FileChooser(file_chooser));
+        let file_chooser = FileChooser::new(this, element, is_multiple);
+        self.emit_event(Evt::FileChooser(file_chooser));
         Ok(())
     }
 }
@@ -956,12 +1298,12 @@ impl RemoteObject for Page {
         match method.as_str() {
             "close" => self.on_close(ctx)?,
             "frameattached" => {
-                let first = first_object(&params).ok_or(Error::InvalidParams)?;
+                let first = first_object(&params).ok_or(Error::InvalidMessage)?;
                 let OnlyGuid { guid } = serde_json::from_value((*first).clone())?;
                 self.on_frame_attached(ctx, guid)?;
             }
             "framedetached" => {
-                let first = first_object(&params).ok_or(Error::InvalidParams)?;
+                let first = first_object(&params).ok_or(Error::InvalidMessage)?;
                 let OnlyGuid { guid } = serde_json::from_value((*first).clone())?;
                 self.on_frame_detached(ctx, guid)?;
             }
@@ -969,7 +1311,7 @@ impl RemoteObject for Page {
             "domcontentloaded" => self.emit_event(Evt::DomContentLoaded),
             "crash" => self.emit_event(Evt::Crash),
             "console" => {
-                let first = first_object(&params).ok_or(Error::InvalidParams)?;
+                let first = first_object(&params).ok_or(Error::InvalidMessage)?;
                 let OnlyGuid { guid } = serde_json::from_value((*first).clone())?;
                 let console = get_object!(ctx, &guid, ConsoleMessage)?;
                 self.emit_event(Evt::Console(console));
@@ -981,7 +1323,7 @@ impl RemoteObject for Page {
                 }
             }
             "request" => {
-                let first = first_object(&params).ok_or(Error::InvalidParams)?;
+                let first = first_object(&params).ok_or(Error::InvalidMessage)?;
                 let OnlyGuid { guid } = serde_json::from_value((*first).clone())?;
                 let request = get_object!(ctx, &guid, Request)?;
                 self.emit_event(Evt::Request(request));
@@ -989,40 +1331,42 @@ impl RemoteObject for Page {
             "requestfailed" => self.on_request_failed(ctx, params)?,
             "requestfinished" => self.on_request_finished(ctx, params)?,
             "response" => {
-                let first = first_object(&params).ok_or(Error::InvalidParams)?;
+                let first = first_object(&params).ok_or(Error::InvalidMessage)?;
                 let OnlyGuid { guid } = serde_json::from_value((*first).clone())?;
                 let response = get_object!(ctx, &guid, Response)?;
                 self.emit_event(Evt::Response(response));
             }
             "route" => self.on_route(ctx, params)?,
+            "bindingCall" => self.on_binding_call(ctx, params)?,
+            "locatorHandlerTriggered" => self.on_locator_handler_triggered(ctx, params)?,
             "popup" => {
-                let first = first_object(&params).ok_or(Error::InvalidParams)?;
+                let first = first_object(&params).ok_or(Error::InvalidMessage)?;
                 let OnlyGuid { guid } = serde_json::from_value((*first).clone())?;
                 let page = get_object!(ctx, &guid, Page)?;
                 self.emit_event(Evt::Popup(page));
             }
             "webSocketRoute" => self.on_web_socket_route(ctx, params)?,
             "websocket" => {
-                let first = first_object(&params).ok_or(Error::InvalidParams)?;
+                let first = first_object(&params).ok_or(Error::InvalidMessage)?;
                 let OnlyGuid { guid } = serde_json::from_value((*first).clone())?;
                 let websocket = get_object!(ctx, &guid, WebSocket)?;
                 self.emit_event(Evt::WebSocket(websocket));
             }
             "worker" => {
-                let first = first_object(&params).ok_or(Error::InvalidParams)?;
+                let first = first_object(&params).ok_or(Error::InvalidMessage)?;
                 let OnlyGuid { guid } = serde_json::from_value((*first).clone())?;
                 let worker = get_object!(ctx, &guid, Worker)?;
                 self.on_worker(ctx, worker)?;
             }
             "dialog" => {
-                let first = first_object(&params).ok_or(Error::InvalidParams)?;
+                let first = first_object(&params).ok_or(Error::InvalidMessage)?;
                 let OnlyGuid { guid } = serde_json::from_value((*first).clone())?;
                 let dialog = get_object!(ctx, &guid, Dialog)?;
                 self.emit_event(Evt::Dialog(dialog));
             }
             "download" => self.on_download(ctx, params)?,
             "video" => self.on_video(ctx, params)?,
-            "filechooser" => self.on_file_chooser(ctx, params)?,
+            "fileChooser" => self.on_file_chooser(ctx, params)?,
             _ => {}
         }
         Ok(())
@@ -1036,8 +1380,7 @@ pub(crate) enum Evt {
     Console(Weak<ConsoleMessage>),
     Dialog(Weak<Dialog>),
     Download(Arc<Download>),
-    /// Not Implemented Yet
-    // FileChooser(FileChooser),
+    FileChooser(FileChooser),
     DomContentLoaded,
     PageError(String),
     Request(Weak<Request>),
@@ -1071,7 +1414,7 @@ pub enum EventType {
     Console,
     Dialog,
     Download,
-    // FileChooser,
+    FileChooser,
     DomContentLoaded,
     PageError,
     Request,
@@ -1098,7 +1441,7 @@ impl IsEvent for Evt {
             Self::Console(_) => EventType::Console,
             Self::Dialog(_) => EventType::Dialog,
             Self::Download(_) => EventType::Download,
-            // Self::FileChooser(_) => EventType::FileChooser,
+            Self::FileChooser(_) => EventType::FileChooser,
             Self::DomContentLoaded => EventType::DomContentLoaded,
             Self::PageError(_) => EventType::PageError,
             Self::Request(_) => EventType::Request,
@@ -1237,7 +1580,11 @@ pub(crate) struct ScreenshotArgs {
     pub(crate) omit_background: Option<bool>,
     pub(crate) full_page: Option<bool>,
     pub(crate) clip: Option<FloatRect>,
+    pub(crate) scale: Option<ScreenshotScale>,
+    pub(crate) animations: Option<ScreenshotAnimations>,
     pub(crate) path: Option<PathBuf>,
+    #[serde(skip)]
+    pub(crate) wait_for_fonts: bool,
 }
 
 #[skip_serializing_none]
@@ -1246,13 +1593,86 @@ pub(crate) struct ScreenshotArgs {
 pub(crate) struct EmulateMediaArgs {
     pub(crate) media: Option<Media>,
     pub(crate) color_scheme: Option<ColorScheme>,
+    pub(crate) reduced_motion: Option<ReducedMotion>,
+    pub(crate) forced_colors: Option<ForcedColors>,
+}
+
+/// Accepts comma separated pages and ranges like `1-5, 8, 11-13`, where either end of a range may be omitted.
+/// An empty string means all pages.
+fn validate_page_ranges(ranges: &str) -> Result<(), Error> {
+    let invalid = || {
+        Error::InvalidParams(format!(
+            "page ranges {:?}, expected e.g. \"1-5, 8, 11-13\"",
+            ranges
+        ))
+    };
+    if ranges.trim().is_empty() {
+        return Ok(());
+    }
+    let page = |s: &str| -> Result<Option<u32>, Error> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Ok(None);
+        }
+        match s.parse::<u32>() {
+            Ok(n) if n > 0 && s.bytes().all(|b| b.is_ascii_digit()) => Ok(Some(n)),
+            _ => Err(invalid()),
+        }
+    };
+    for item in ranges.split(',') {
+        match item.split_once('-') {
+            None => {
+                page(item)?.ok_or_else(invalid)?;
+            }
+            Some((start, end)) => match (page(start)?, page(end)?) {
+                (None, None) => return Err(invalid()),
+                (Some(s), Some(e)) if s > e => return Err(invalid()),
+                _ => {}
+            },
+        }
+    }
+    Ok(())
+}
+
+/// Vision deficiency to emulate with [`crate::api::Page::emulate_vision_deficiency`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum VisionDeficiency {
+    /// Reset emulating
+    None,
+    Achromatopsia,
+    BlurredVision,
+    Deuteranopia,
+    Protanopia,
+    Tritanopia,
 }
 
 #[derive(Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Media {
     /// Reset emulating
+    #[serde(rename = "no-override")]
     Null,
     Print,
     Screen,
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReducedMotion {
+    /// Reset emulating
+    #[serde(rename = "no-override")]
+    Null,
+    Reduce,
+    NoPreference,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ForcedColors {
+    /// Reset emulating
+    #[serde(rename = "no-override")]
+    Null,
+    Active,
+    None,
+}
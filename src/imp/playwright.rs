@@ -2,6 +2,7 @@ use crate::{
     api::{browser::ContextBuilder, browser_type::PersistentContextLauncher},
     imp::{
         api_request_context::{APIRequestContext, NewContextArgs},
+        browser::Browser,
         browser_type::BrowserType,
         core::*,
         impl_future::*,
@@ -21,6 +22,7 @@ pub(crate) struct Playwright {
     webkit: Weak<BrowserType>,
     selectors: Option<Weak<Selectors>>,
     devices: Vec<DeviceDescriptor>,
+    pre_launched_browser: Option<Weak<Browser>>,
 }
 
 impl Playwright {
@@ -33,6 +35,10 @@ impl Playwright {
             Some(OnlyGuid { guid }) => get_object!(ctx, &guid, Selectors).ok(),
             None => None,
         };
+        let pre_launched_browser = match i.pre_launched_browser {
+            Some(OnlyGuid { guid }) => get_object!(ctx, &guid, Browser).ok(),
+            None => None,
+        };
         let devices = i.device_descriptors;
         Ok(Self {
             channel,
@@ -41,6 +47,7 @@ impl Playwright {
             webkit,
             selectors,
             devices,
+            pre_launched_browser,
         })
     }
 
@@ -68,12 +75,17 @@ impl Playwright {
         self.selectors.clone()
     }
 
+    /// The browser a Playwright server launched for this connection
+    pub(crate) fn pre_launched_browser(&self) -> Option<Weak<Browser>> {
+        self.pre_launched_browser.clone()
+    }
+
     pub(crate) async fn new_api_request_context(
         &self,
         args: NewContextArgs,
     ) -> ArcResult<Weak<APIRequestContext>> {
         let v = send_message!(self, "newRequest", args);
-        let request = v.get("request").ok_or(Error::InvalidParams)?;
+        let request = v.get("request").ok_or(Error::InvalidMessage)?;
         let guid = only_guid(request)?;
         let ctx = get_object!(self.context()?.lock().unwrap(), guid, APIRequestContext)?;
         Ok(ctx)
@@ -108,6 +120,8 @@ struct Initializer {
     selectors: Option<OnlyGuid>,
     #[serde(default)]
     device_descriptors: Vec<DeviceDescriptor>,
+    #[serde(default)]
+    pre_launched_browser: Option<OnlyGuid>,
 }
 
 pub(crate) struct WaitInitialObject {
@@ -1,9 +1,10 @@
 use crate::imp::{
     core::*,
     frame::Frame,
+    page::Page,
     prelude::*,
     response::Response,
-    utils::{Header, ResponseTiming},
+    utils::{Header, RequestFailure, ResponseTiming},
 };
 use base64::{engine::general_purpose, Engine as _};
 
@@ -24,7 +25,7 @@ pub(crate) struct Request {
 #[derive(Debug, Default)]
 pub(crate) struct Variable {
     redirected_to: Option<Weak<Request>>,
-    failure: Option<String>,
+    failure: Option<RequestFailure>,
     timing: Option<ResponseTiming>,
     response_end: Option<f64>,
 }
@@ -49,7 +50,11 @@ impl Request {
                 (k, v)
             })
             .collect();
-        let frame = get_object!(ctx, &frame.guid, Frame)?;
+        // Requests issued by service workers are not associated with any frame.
+        let frame = match frame {
+            Some(OnlyGuid { guid }) => get_object!(ctx, &guid, Frame)?,
+            None => Weak::new(),
+        };
         let redirected_from =
             match redirected_from.map(|OnlyGuid { guid }| get_object!(ctx, &guid, Request)) {
                 None => None,
@@ -96,12 +101,20 @@ impl Request {
         self.frame.clone()
     }
 
+    pub(crate) fn page(&self) -> Option<Weak<Page>> {
+        self.frame.upgrade().and_then(|f| f.page())
+    }
+
     pub(crate) fn post_data(&self) -> Option<Vec<u8>> {
         general_purpose::STANDARD
             .decode(self.post_data.as_ref()?)
             .ok()
     }
 
+    pub(crate) fn post_data_base64(&self) -> Option<&str> {
+        self.post_data.as_deref()
+    }
+
     pub(crate) fn post_data_as_string(&self) -> Option<String> {
         let bytes = self.post_data()?;
         let s = String::from_utf8(bytes).ok()?;
@@ -112,6 +125,16 @@ impl Request {
         &self.headers
     }
 
+    /// Headers as sent over the wire, in the order they were sent.
+    /// Header names keep their original case.
+    pub(crate) async fn headers_array(&self) -> ArcResult<Vec<Header>> {
+        let v = send_message!(self, "rawRequestHeaders", Map::new());
+        let first = first(&v).ok_or(Error::InvalidMessage)?;
+        let headers: Vec<Header> =
+            serde_json::from_value((*first).clone()).map_err(Error::Serde)?;
+        Ok(headers)
+    }
+
     pub(crate) fn redirected_from(&self) -> Option<Weak<Request>> {
         self.redirected_from.clone()
     }
@@ -136,7 +159,7 @@ impl Request {
         self.var.lock().unwrap().response_end
     }
 
-    pub(crate) fn failure(&self) -> Option<String> {
+    pub(crate) fn failure(&self) -> Option<RequestFailure> {
         self.var.lock().unwrap().failure.clone()
     }
 
@@ -161,7 +184,7 @@ impl Request {
 
     pub(crate) fn set_failure(&self, failure: Option<String>) {
         let var = &mut self.var.lock().unwrap();
-        var.failure = failure;
+        var.failure = failure.map(RequestFailure::from);
     }
 }
 
@@ -187,7 +210,7 @@ struct Initializer {
     url: String,
     resource_type: String,
     method: String,
-    frame: OnlyGuid,
+    frame: Option<OnlyGuid>,
     is_navigation_request: bool,
     // base64
     post_data: Option<String>,
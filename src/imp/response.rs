@@ -13,6 +13,8 @@ pub(crate) struct Response {
     status: i32,
     status_text: String,
     request: Weak<Request>,
+    headers: HashMap<String, String>,
+    from_service_worker: bool,
 }
 
 impl Response {
@@ -23,7 +25,17 @@ impl Response {
             status_text,
             request,
             timing,
+            headers,
+            from_service_worker,
         } = serde_json::from_value(channel.initializer.clone())?;
+        let headers: HashMap<_, _> = headers
+            .into_iter()
+            .map(Into::<(_, _)>::into)
+            .map(|(mut k, v)| {
+                k.make_ascii_lowercase();
+                (k, v)
+            })
+            .collect();
         let request = get_object!(ctx, &request.guid, Request)?;
         upgrade(&request)?.set_response_timing(timing);
         Ok(Self {
@@ -32,6 +44,8 @@ impl Response {
             status,
             status_text,
             request,
+            headers,
+            from_service_worker,
         })
     }
 
@@ -45,6 +59,10 @@ impl Response {
         &self.status_text
     }
 
+    pub(crate) fn from_service_worker(&self) -> bool {
+        self.from_service_worker
+    }
+
     pub(crate) fn ok(&self) -> bool {
         self.status == 0 || (200..300).contains(&self.status)
     }
@@ -72,16 +90,40 @@ impl Response {
         self.request.clone()
     }
 
-    pub(crate) async fn headers(&self) -> ArcResult<Vec<Header>> {
-        let v = send_message!(self, "body", Map::new());
-        let first = first(&v).ok_or(Error::InvalidParams)?;
-        let mut headers: Vec<Header> =
+    pub(crate) fn headers(&self) -> &HashMap<String, String> {
+        &self.headers
+    }
+
+    /// Headers as sent over the wire, including duplicates such as `set-cookie`.
+    /// Header names keep their original case.
+    pub(crate) async fn headers_array(&self) -> ArcResult<Vec<Header>> {
+        let v = send_message!(self, "rawResponseHeaders", Map::new());
+        let first = first(&v).ok_or(Error::InvalidMessage)?;
+        let headers: Vec<Header> =
             serde_json::from_value((*first).clone()).map_err(Error::Serde)?;
-        for h in headers.iter_mut() {
-            h.name.make_ascii_lowercase();
-        }
         Ok(headers)
     }
+
+    pub(crate) async fn all_headers(&self) -> ArcResult<HashMap<String, String>> {
+        let headers = self.headers_array().await?;
+        let mut map: HashMap<String, String> = HashMap::new();
+        for Header { mut name, value } in headers {
+            name.make_ascii_lowercase();
+            let sep = if name == "set-cookie" { "\n" } else { ", " };
+            map.entry(name)
+                .and_modify(|v| {
+                    v.push_str(sep);
+                    v.push_str(&value);
+                })
+                .or_insert(value);
+        }
+        Ok(map)
+    }
+
+    pub(crate) async fn header_value(&self, name: &str) -> ArcResult<Option<String>> {
+        let name = name.to_ascii_lowercase();
+        Ok(self.all_headers().await?.remove(&name))
+    }
 }
 
 impl RemoteObject for Response {
@@ -101,4 +143,8 @@ struct Initializer {
     status_text: String,
     request: OnlyGuid,
     timing: ResponseTiming,
+    #[serde(default)]
+    headers: Vec<Header>,
+    #[serde(default)]
+    from_service_worker: bool,
 }
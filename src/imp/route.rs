@@ -36,6 +36,13 @@ impl Route {
         Ok(())
     }
 
+    pub(crate) async fn redirect_navigation_request(&self, url: &str) -> ArcResult<()> {
+        let mut args = HashMap::new();
+        args.insert("url", url);
+        let _ = send_message!(self, "redirectNavigationRequest", args);
+        Ok(())
+    }
+
     pub(crate) async fn r#continue(&self, args: ContinueArgs<'_, '_, '_>) -> ArcResult<()> {
         let _ = send_message!(self, "continue", args);
         Ok(())
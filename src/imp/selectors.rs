@@ -3,11 +3,17 @@ use crate::imp::{core::*, prelude::*};
 #[derive(Debug)]
 pub(crate) struct Selectors {
     channel: ChannelOwner,
+    engines: Mutex<Vec<String>>,
+    test_id_attribute: Mutex<Option<String>>,
 }
 
 impl Selectors {
     pub(crate) fn new(channel: ChannelOwner) -> Self {
-        Self { channel }
+        Self {
+            channel,
+            engines: Mutex::default(),
+            test_id_attribute: Mutex::default(),
+        }
     }
 
     pub(crate) async fn register(
@@ -16,16 +22,40 @@ impl Selectors {
         script: &str,
         content_script: bool,
     ) -> Result<(), Arc<Error>> {
+        if self.engines.lock().unwrap().iter().any(|e| e == name) {
+            return Err(Error::SelectorEngineAlreadyRegistered(name.to_owned()).into());
+        }
         let args = RegisterArgs {
             name,
             source: script,
             content_script,
         };
         let _ = send_message!(self, "register", args);
+        self.engines.lock().unwrap().push(name.to_owned());
         Ok(())
     }
 }
 
+impl Selectors {
+    pub(crate) async fn set_test_id_attribute(&self, name: &str) -> ArcResult<()> {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Args<'a> {
+            test_id_attribute_name: &'a str,
+        }
+        let args = Args {
+            test_id_attribute_name: name,
+        };
+        let _ = send_message!(self, "setTestIdAttributeName", args);
+        *self.test_id_attribute.lock().unwrap() = Some(name.to_owned());
+        Ok(())
+    }
+
+    pub(crate) fn test_id_attribute(&self) -> Option<String> {
+        self.test_id_attribute.lock().unwrap().clone()
+    }
+}
+
 impl RemoteObject for Selectors {
     fn channel(&self) -> &ChannelOwner {
         &self.channel
@@ -65,6 +95,8 @@ mod tests {
         let res = fut.await;
         dbg!(&res);
         assert!(res.is_ok());
+        let res = s.register("foo", "()", false).await;
+        assert!(res.is_err());
         Ok::<(), crate::imp::core::Error>(())
     });
 }
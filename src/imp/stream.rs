@@ -5,6 +5,8 @@ use std::{
     io::{BufWriter, Write},
 };
 
+const CHUNK_SIZE: u32 = 1024 * 1024;
+
 #[derive(Debug)]
 pub(crate) struct Stream {
     channel: ChannelOwner,
@@ -15,30 +17,53 @@ impl Stream {
         Self { channel }
     }
 
+    /// Writes the rest of the stream into `path` chunk by chunk and closes the stream.
     pub(crate) async fn save_as<P: AsRef<Path>>(&self, path: P) -> ArcResult<()> {
         let file = File::create(path).map_err(Error::from)?;
         let mut writer = BufWriter::new(file);
         loop {
-            let v = send_message!(self, "read", Map::new());
-            let b64 = only_str(&v)?;
-            if b64.is_empty() {
+            let bytes = self.read(CHUNK_SIZE).await?;
+            if bytes.is_empty() {
                 break;
-            } else {
-                let bytes = general_purpose::STANDARD
-                    .decode(b64)
-                    .map_err(Error::InvalidBase64)?;
-                writer.write(&bytes).map_err(Error::from)?;
             }
+            writer.write_all(&bytes).map_err(Error::from)?;
         }
-        Ok(())
+        writer.flush().map_err(Error::from)?;
+        self.close().await
     }
 
-    // with open(path, mode="wb") as file:
-    //    while True:
-    //        binary = await self._channel.send("read")
-    //        if not binary:
-    //            break
-    //        file.write(base64.b64decode(binary))
+    /// Reads the rest of the stream into memory and closes the stream.
+    pub(crate) async fn read_to_end(&self) -> ArcResult<Vec<u8>> {
+        let mut buf = Vec::new();
+        loop {
+            let bytes = self.read(CHUNK_SIZE).await?;
+            if bytes.is_empty() {
+                break;
+            }
+            buf.extend_from_slice(&bytes);
+        }
+        self.close().await?;
+        Ok(buf)
+    }
+
+    /// Reads at most `size` bytes. An empty chunk means the end of the stream.
+    pub(crate) async fn read(&self, size: u32) -> ArcResult<Vec<u8>> {
+        #[derive(Serialize)]
+        struct Args {
+            size: u32,
+        }
+        let v = send_message!(self, "read", Args { size });
+        let b64 = only_str(&v)?;
+        let bytes = general_purpose::STANDARD
+            .decode(b64)
+            .map_err(Error::InvalidBase64)?;
+        Ok(bytes)
+    }
+
+    pub(crate) async fn close(&self) -> ArcResult<()> {
+        let _ = send_message!(self, "close", Map::new());
+        Ok(())
+    }
 }
 
 impl RemoteObject for Stream {
@@ -24,15 +24,20 @@ impl Tracing {
         Ok(())
     }
 
+    /// `location` is shown as the source of the group, a file path or a URL.
     pub(crate) async fn group(&self, name: &str, location: Option<&str>) -> ArcResult<()> {
+        #[derive(Serialize)]
+        struct Location<'a> {
+            file: &'a str,
+        }
         #[skip_serializing_none]
         #[derive(Serialize)]
         #[serde(rename_all = "camelCase")]
         struct Args<'a> {
             name: &'a str,
-            #[serde(rename = "location")]
-            location: Option<&'a str>,
+            location: Option<Location<'a>>,
         }
+        let location = location.map(|file| Location { file });
         let args = Args { name, location };
         let _ = send_message!(self, "tracingGroup", args);
         Ok(())
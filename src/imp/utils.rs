@@ -1,5 +1,6 @@
 use crate::imp::prelude::*;
 use base64::{engine::general_purpose, Engine as _};
+use std::convert::TryFrom;
 
 #[derive(Debug, Deserialize, Clone, Serialize, PartialEq, Eq)]
 pub struct Viewport {
@@ -36,13 +37,26 @@ pub struct HttpCredentials {
 }
 
 #[derive(Debug, Deserialize, Serialize, Eq, PartialEq, Clone, Copy)]
-#[serde(rename_all = "snake_case")]
+#[serde(rename_all = "kebab-case")]
 pub enum ColorScheme {
+    /// Reset emulating
+    #[serde(rename = "no-override")]
+    Null,
     Dark,
     Light,
     NoPreference,
 }
 
+/// Whether pages of a context may register service workers.
+#[derive(Debug, Deserialize, Serialize, Eq, PartialEq, Clone, Copy)]
+#[serde(rename_all = "kebab-case")]
+pub enum ServiceWorkerPolicy {
+    Allow,
+    /// Requests are then never served by a service worker, so [`BrowserContext::route`](crate::api::BrowserContext::route)
+    /// sees all of them.
+    Block,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Deserialize, Serialize)]
 pub struct StorageState {
@@ -94,6 +108,26 @@ impl Cookie {
             same_site: None,
         }
     }
+
+    /// Checks what the driver would reject with a less helpful message: a cookie needs either `url` or both `domain`
+    /// and `path`, and `url` can't be a blank or data URL.
+    pub(crate) fn validate(&self) -> Result<(), crate::Error> {
+        let invalid = |reason| {
+            let msg = format!("cookie {:?}: {}", self.name, reason);
+            Err(crate::Error::InvalidParams(msg))
+        };
+        match (&self.url, &self.domain, &self.path) {
+            (Some(url), None, None) => {
+                if url == "about:blank" || url.starts_with("data:") {
+                    return invalid("blank and data URLs can't have cookies");
+                }
+            }
+            (Some(_), _, _) => return invalid("url can't be set together with domain or path"),
+            (None, Some(_), Some(_)) => {}
+            (None, _, _) => return invalid("either url or both domain and path must be set"),
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Eq, PartialEq, Clone, Copy)]
@@ -103,6 +137,32 @@ pub enum SameSite {
     Strict,
 }
 
+/// Parses `Strict`, `Lax` or `None`, ignoring case as the `Set-Cookie` header does. Any other value is an error,
+/// so typos don't reach the driver.
+impl std::str::FromStr for SameSite {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "lax" => Ok(Self::Lax),
+            "none" => Ok(Self::None),
+            "strict" => Ok(Self::Strict),
+            _ => Err(crate::Error::InvalidParams(format!(
+                "SameSite {:?}, expected Strict, Lax or None",
+                s
+            ))),
+        }
+    }
+}
+
+impl TryFrom<&str> for SameSite {
+    type Error = crate::Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct OriginState {
@@ -134,6 +194,95 @@ pub enum KeyboardModifier {
     Shift,
 }
 
+/// Resource type of a request as it was perceived by the rendering engine.
+#[derive(Debug, Deserialize, Serialize, Eq, PartialEq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum ResourceType {
+    Document,
+    Stylesheet,
+    Image,
+    Media,
+    Font,
+    Script,
+    Texttrack,
+    Xhr,
+    Fetch,
+    Eventsource,
+    Websocket,
+    Manifest,
+    Other,
+}
+
+impl ResourceType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Document => "document",
+            Self::Stylesheet => "stylesheet",
+            Self::Image => "image",
+            Self::Media => "media",
+            Self::Font => "font",
+            Self::Script => "script",
+            Self::Texttrack => "texttrack",
+            Self::Xhr => "xhr",
+            Self::Fetch => "fetch",
+            Self::Eventsource => "eventsource",
+            Self::Websocket => "websocket",
+            Self::Manifest => "manifest",
+            Self::Other => "other",
+        }
+    }
+}
+
+/// Permission that can be granted with `BrowserContext::grant_permissions`.
+/// Converts into the `String` the driver expects: `&[Permission::ClipboardRead.into()]`.
+#[derive(Debug, Deserialize, Serialize, Eq, PartialEq, Clone, Copy)]
+#[serde(rename_all = "kebab-case")]
+pub enum Permission {
+    Geolocation,
+    Midi,
+    MidiSysex,
+    Notifications,
+    Camera,
+    Microphone,
+    BackgroundSync,
+    AmbientLightSensor,
+    Accelerometer,
+    Gyroscope,
+    Magnetometer,
+    AccessibilityEvents,
+    ClipboardRead,
+    ClipboardWrite,
+    PaymentHandler,
+}
+
+impl Permission {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Geolocation => "geolocation",
+            Self::Midi => "midi",
+            Self::MidiSysex => "midi-sysex",
+            Self::Notifications => "notifications",
+            Self::Camera => "camera",
+            Self::Microphone => "microphone",
+            Self::BackgroundSync => "background-sync",
+            Self::AmbientLightSensor => "ambient-light-sensor",
+            Self::Accelerometer => "accelerometer",
+            Self::Gyroscope => "gyroscope",
+            Self::Magnetometer => "magnetometer",
+            Self::AccessibilityEvents => "accessibility-events",
+            Self::ClipboardRead => "clipboard-read",
+            Self::ClipboardWrite => "clipboard-write",
+            Self::PaymentHandler => "payment-handler",
+        }
+    }
+}
+
+impl From<Permission> for String {
+    fn from(p: Permission) -> String {
+        p.as_str().to_owned()
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Eq, PartialEq, Clone, Copy)]
 #[serde(rename_all = "lowercase")]
 pub enum MouseButton {
@@ -171,6 +320,38 @@ pub enum ScreenshotType {
     Png,
 }
 
+/// Pixel scale of a screenshot.
+#[derive(Debug, Deserialize, Serialize, Eq, PartialEq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum ScreenshotScale {
+    /// A single pixel per CSS pixel. Keeps screenshots the same size regardless of the device scale factor.
+    Css,
+    /// A single pixel per device pixel, so high-dpi devices produce larger images. This is the default.
+    Device,
+}
+
+/// Whether CSS animations run while taking a screenshot.
+#[derive(Debug, Deserialize, Serialize, Eq, PartialEq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum ScreenshotAnimations {
+    /// Finite animations are fast-forwarded to completion and infinite ones are canceled to their initial state.
+    Disabled,
+    /// Animations are left as they are. This is the default.
+    Allow,
+}
+
+impl ScreenshotType {
+    /// Infers the type from the extension of the path to save to.
+    pub(crate) fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "png" => Some(Self::Png),
+            "jpg" | "jpeg" => Some(Self::Jpeg),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Eq, PartialEq, Clone, Copy)]
 #[serde(rename_all = "lowercase")]
 pub enum ElementState {
@@ -237,9 +418,46 @@ pub struct PdfMargins<'a, 'b, 'c, 'd> {
     pub left: Option<Length<'d>>,
 }
 
+/// Converts a url glob into an anchored regex source.
+/// `**` matches any characters, `*` matches any characters except `/`, `?` matches a single character
+/// and `{a,b}` matches either alternative.
+pub(crate) fn glob_to_regex(glob: &str) -> String {
+    let mut re = String::from("^");
+    let mut in_group = false;
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                re.push_str(".*");
+            }
+            '*' => re.push_str("[^/]*"),
+            '?' => re.push('.'),
+            '{' => {
+                in_group = true;
+                re.push('(');
+            }
+            '}' if in_group => {
+                in_group = false;
+                re.push(')');
+            }
+            ',' if in_group => re.push('|'),
+            '\\' => {
+                if let Some(n) = chars.next() {
+                    re.push_str(&regex::escape(&n.to_string()));
+                }
+            }
+            c => re.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    re.push('$');
+    re
+}
+
 #[derive(Debug, Serialize, PartialEq)]
 pub struct File {
     pub name: String,
+    #[serde(rename = "mimeType")]
     pub mime: String,
     pub buffer: String,
 }
@@ -250,6 +468,34 @@ impl File {
         Self { name, mime, buffer }
     }
 }
+
+/// A file to upload with [`ElementHandle::set_input_files`](crate::api::ElementHandle::set_input_files).
+#[derive(Debug, PartialEq)]
+pub enum SetInputFilesSource {
+    /// Sent to the driver in memory.
+    File(File),
+    /// Read by the driver from the local file system, which suits large files.
+    /// Relative paths are resolved against the current working directory.
+    Path(PathBuf),
+}
+
+impl From<File> for SetInputFilesSource {
+    fn from(x: File) -> Self {
+        Self::File(x)
+    }
+}
+
+impl From<PathBuf> for SetInputFilesSource {
+    fn from(x: PathBuf) -> Self {
+        Self::Path(x)
+    }
+}
+
+impl From<&Path> for SetInputFilesSource {
+    fn from(x: &Path) -> Self {
+        Self::Path(x.into())
+    }
+}
 /// Browser distribution channel.
 // TODO: kebab case
 #[derive(Debug, Deserialize, Serialize, Eq, PartialEq, Clone, Copy)]
@@ -296,3 +542,128 @@ pub struct ResponseTiming {
     /// Time immediately after the browser starts requesting the resource from the server, cache, or local resource. The value\nis given in milliseconds relative to `startTime`, -1 if not available.
     pub response_start: f64,
 }
+
+/// Why a request failed, as reported by `requestfailed` events.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestFailure {
+    pub error_code: RequestErrorCode,
+    /// Human-readable error message as reported by the browser, e.g. `'net::ERR_FAILED'`.
+    pub error_text: String,
+}
+
+/// Common failure causes. Browsers report them with different texts, which are classified here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestErrorCode {
+    /// Cancelled by the page, navigation or [`Route::abort`](crate::api::Route::abort).
+    Aborted,
+    NameNotResolved,
+    ConnectionRefused,
+    TimedOut,
+    InternetDisconnected,
+    Failed,
+    /// Any text that is not classified above
+    Other,
+}
+
+impl From<String> for RequestFailure {
+    fn from(error_text: String) -> Self {
+        let t = error_text.to_ascii_lowercase();
+        let has = |xs: &[&str]| xs.iter().any(|x| t.contains(x));
+        let error_code = if has(&[
+            "err_aborted",
+            "ns_error_abort",
+            "ns_binding_aborted",
+            "cancelled",
+        ]) {
+            RequestErrorCode::Aborted
+        } else if has(&[
+            "err_name_not_resolved",
+            "ns_error_unknown_host",
+            "hostname could not be found",
+        ]) {
+            RequestErrorCode::NameNotResolved
+        } else if has(&[
+            "err_connection_refused",
+            "ns_error_connection_refused",
+            "connection refused",
+            "could not connect",
+        ]) {
+            RequestErrorCode::ConnectionRefused
+        } else if has(&["err_timed_out", "ns_error_net_timeout", "timed out"]) {
+            RequestErrorCode::TimedOut
+        } else if has(&["err_internet_disconnected", "ns_error_offline", "offline"]) {
+            RequestErrorCode::InternetDisconnected
+        } else if has(&["err_failed", "ns_error_failure"]) {
+            RequestErrorCode::Failed
+        } else {
+            RequestErrorCode::Other
+        };
+        Self {
+            error_code,
+            error_text,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glob() {
+        let m = |g: &str, url: &str| regex::Regex::new(&glob_to_regex(g)).unwrap().is_match(url);
+        assert!(m("**/api/login", "http://localhost:8080/api/login"));
+        assert!(!m("**/api/login", "http://localhost:8080/api/login?x=1"));
+        assert!(m("**/*.{png,jpg}", "http://a.com/img/b.png"));
+        assert!(!m("http://a.com/*.png", "http://a.com/img/b.png"));
+        assert!(m("http://a.com/?.png", "http://a.com/b.png"));
+    }
+
+    #[test]
+    fn same_site() {
+        assert_eq!(SameSite::try_from("None").unwrap(), SameSite::None);
+        assert_eq!("lax".parse::<SameSite>().unwrap(), SameSite::Lax);
+        assert_eq!(SameSite::try_from("STRICT").unwrap(), SameSite::Strict);
+        assert!(SameSite::try_from("Stirct").is_err());
+    }
+
+    #[test]
+    fn cookie_validation() {
+        assert!(Cookie::with_url("a", "b", "https://example.com")
+            .validate()
+            .is_ok());
+        assert!(Cookie::with_domain_path("a", "b", ".example.com", "/")
+            .validate()
+            .is_ok());
+        assert!(Cookie::with_url("a", "b", "about:blank")
+            .validate()
+            .is_err());
+        let mut c = Cookie::with_domain_path("a", "b", "example.com", "/");
+        c.url = Some("https://example.com".into());
+        assert!(c.validate().is_err());
+        c.url = None;
+        c.path = None;
+        assert!(matches!(
+            c.validate(),
+            Err(crate::Error::InvalidParams(msg)) if msg.starts_with("cookie \"a\"")
+        ));
+    }
+
+    #[test]
+    fn request_failure() {
+        let code = |t: &str| RequestFailure::from(t.to_owned()).error_code;
+        assert_eq!(code("net::ERR_ABORTED"), RequestErrorCode::Aborted);
+        assert_eq!(code("NS_ERROR_ABORT"), RequestErrorCode::Aborted);
+        assert_eq!(code("Request cancelled"), RequestErrorCode::Aborted);
+        assert_eq!(
+            code("net::ERR_NAME_NOT_RESOLVED"),
+            RequestErrorCode::NameNotResolved
+        );
+        assert_eq!(
+            code("NS_ERROR_CONNECTION_REFUSED"),
+            RequestErrorCode::ConnectionRefused
+        );
+        assert_eq!(code("net::ERR_FAILED"), RequestErrorCode::Failed);
+        assert_eq!(code("something else"), RequestErrorCode::Other);
+    }
+}
@@ -11,7 +11,11 @@ impl Video {
     }
 
     pub(crate) fn path(&self) -> Result<PathBuf, Error> {
-        Ok(upgrade(&self.artifact)?.absolute_path.as_str().into())
+        let artifact = upgrade(&self.artifact)?;
+        if artifact.is_remote() {
+            return Err(Error::RemoteArtifact);
+        }
+        Ok(artifact.absolute_path.as_str().into())
     }
 
     pub(crate) async fn save_as<P: AsRef<Path>>(&self, path: P) -> ArcResult<()> {
@@ -54,7 +54,9 @@ impl WebSocketRoute {
         {
             let mut var = self.var.lock().unwrap();
             if var.connected {
-                return Err(Arc::new(Error::InvalidParams));
+                return Err(Arc::new(Error::InvalidParams(
+                    "the route is already connected to the server".into(),
+                )));
             }
             var.connected = true;
         }
@@ -88,9 +88,9 @@ impl Worker {
         self.var.lock().unwrap().page = Some(page);
     }
 
-    // pub(crate) fn set_browser_context(&self, browser_context: Weak<BrowserContext>) {
-    //    self.var.lock().unwrap().browser_context = Some(browser_context);
-    //}
+    pub(crate) fn set_browser_context(&self, browser_context: Weak<BrowserContext>) {
+        self.var.lock().unwrap().browser_context = Some(browser_context);
+    }
 
     fn on_close(&self, ctx: &Context) -> Result<(), Error> {
         let this = get_object!(ctx, self.guid(), Worker)?;
@@ -98,7 +98,9 @@ impl Worker {
         if let Some(page) = var.page.as_ref().and_then(Weak::upgrade) {
             page.remove_worker(&this);
         }
-        // var.context.remove_service_worker(&this)
+        if let Some(context) = var.browser_context.as_ref().and_then(Weak::upgrade) {
+            context.remove_service_worker(&this);
+        }
         self.emit_event(Evt::Close);
         Ok(())
     }
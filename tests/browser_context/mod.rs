@@ -1,14 +1,16 @@
 use super::Which;
+use futures::stream::StreamExt;
 use playwright::api::{
-    browser::RecordVideo, Browser, BrowserContext, BrowserType, Cookie, LocalStorageEntry,
-    OriginState, StorageState,
+    browser::RecordVideo, browser_context, Browser, BrowserContext, BrowserType, Cookie,
+    LocalStorageEntry, OriginState, Permission, RequestOptions, RouteFromHarOptions, SameSite,
+    ServiceWorkerPolicy, StorageState,
 };
 
 pub async fn all(
     browser: &Browser,
     persistent: &BrowserContext,
     port: u16,
-    _which: Which,
+    which: Which,
 ) -> BrowserContext {
     let c = launch(browser).await;
     assert_ne!(persistent, &c);
@@ -16,9 +18,21 @@ pub async fn all(
     storage_state(&c, port).await;
     set_offline_should_work(browser, port).await;
     set_timeout(&c).await;
+    default_timeout_should_propagate(&c).await;
     cookies_should_work(&c).await;
     add_init_script_should_work(&c).await;
     pages_should_work(&c).await;
+    request_event_should_carry_page(&c, port).await;
+    expose_function_should_reach_all_pages(&c, port).await;
+    route_from_har_should_work(browser).await;
+    touchscreen_requires_has_touch(browser).await;
+    api_request_methods(&c, port).await;
+    api_request_retry_delays(&c, port).await;
+    tracing_scope(browser).await;
+    if which == Which::Chromium {
+        clipboard_permissions(browser, port).await;
+        service_workers(browser, port).await;
+    }
     c
 }
 
@@ -28,9 +42,23 @@ pub async fn persistent(t: &BrowserType, _port: u16, which: Which) -> BrowserCon
         // XXX: launch with permissions not work on firefox
         check_launched_permissions(&c).await;
     }
+    if Which::Chromium != which {
+        persistent_cdp_session_is_chromium_only(&c).await;
+    }
     c
 }
 
+async fn persistent_cdp_session_is_chromium_only(c: &BrowserContext) {
+    let page = c.new_page().await.unwrap();
+    let err = page.new_cdp_session().await.err();
+    assert!(
+        matches!(err.as_deref(), Some(playwright::Error::ChromiumOnly(_))),
+        "{:?}",
+        err
+    );
+    page.close(None).await.unwrap();
+}
+
 async fn launch(b: &Browser) -> BrowserContext {
     let c = b
         .context_builder()
@@ -88,6 +116,28 @@ async fn set_timeout(c: &BrowserContext) {
     c.set_default_timeout(10000).await.unwrap();
 }
 
+async fn default_timeout_should_propagate(c: &BrowserContext) {
+    use std::time::{Duration, Instant};
+    let existing = c.new_page().await.unwrap();
+    c.set_default_timeout(100).await.unwrap();
+    let created = c.new_page().await.unwrap();
+    for p in &[&existing, &created] {
+        let start = Instant::now();
+        assert!(p.click_builder("#missing").click().await.is_err());
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+    created.set_default_timeout(10000).await.unwrap();
+    let wait = created
+        .wait_for_selector_builder("#missing")
+        .wait_for_selector();
+    assert!(tokio::time::timeout(Duration::from_millis(500), wait)
+        .await
+        .is_err());
+    c.set_default_timeout(10000).await.unwrap();
+    existing.close(None).await.unwrap();
+    created.close(None).await.unwrap();
+}
+
 async fn cookies_should_work(c: &BrowserContext) {
     ensure_cookies_are_cleared(c).await;
     let cookie = Cookie {
@@ -107,6 +157,28 @@ async fn cookies_should_work(c: &BrowserContext) {
     assert_eq!(&first.name, "foo");
     assert_eq!(&first.value, "bar");
     ensure_cookies_are_cleared(c).await;
+    let cookie = Cookie {
+        name: "cross".into(),
+        value: "site".into(),
+        url: Some("https://example.com/".into()),
+        domain: None,
+        path: None,
+        expires: None,
+        http_only: None,
+        secure: Some(true),
+        same_site: Some(SameSite::None),
+    };
+    c.add_cookies(&[cookie]).await.unwrap();
+    let cookies = c.cookies(&[]).await.unwrap();
+    let first = cookies.into_iter().next().unwrap();
+    assert_eq!(first.same_site, Some(SameSite::None));
+    assert_eq!(first.secure, Some(true));
+    ensure_cookies_are_cleared(c).await;
+    let mut malformed = Cookie::with_url("nowhere", "value", "https://example.com/");
+    malformed.url = None;
+    let err = c.add_cookies(&[malformed]).await.unwrap_err();
+    assert!(matches!(*err, playwright::Error::InvalidParams(_)));
+    ensure_cookies_are_cleared(c).await;
 }
 
 async fn ensure_cookies_are_cleared(c: &BrowserContext) {
@@ -142,6 +214,29 @@ async fn add_init_script_should_work(c: &BrowserContext) {
     p.close(None).await.unwrap();
 }
 
+async fn request_event_should_carry_page(c: &BrowserContext, port: u16) {
+    let p1 = c.new_page().await.unwrap();
+    let p2 = c.new_page().await.unwrap();
+    let url = super::url_static(port, "/empty.html");
+    let mut rx = c.subscribe_event().unwrap();
+    p2.goto_builder(&url).goto().await.unwrap();
+    let mut request = None;
+    let response = loop {
+        match rx.next().await.unwrap().unwrap() {
+            browser_context::Event::Request(r) => request = Some(r),
+            browser_context::Event::Response(r) => break r,
+            _ => {}
+        }
+    };
+    let request = request.unwrap();
+    assert_eq!(request.page().unwrap(), Some(p2.clone()));
+    assert_ne!(request.page().unwrap(), Some(p1.clone()));
+    assert_eq!(response.page().unwrap(), Some(p2.clone()));
+    assert_eq!(request.frame(), p2.main_frame());
+    p1.close(None).await.unwrap();
+    p2.close(None).await.unwrap();
+}
+
 async fn set_offline_should_work(browser: &Browser, port: u16) {
     let c = browser
         .context_builder()
@@ -192,3 +287,270 @@ async fn storage_state(c: &BrowserContext, port: u16) {
         ]
     );
 }
+
+async fn expose_function_should_reach_all_pages(c: &BrowserContext, port: u16) {
+    let existing = c.new_page().await.unwrap();
+    c.expose_function("contextMul", |(a, b): (i32, i32)| async move { a * b })
+        .await
+        .unwrap();
+    let created = c.new_page().await.unwrap();
+    let product: i32 = existing
+        .eval("() => window.contextMul(2, 3)")
+        .await
+        .unwrap();
+    assert_eq!(product, 6);
+    let url = super::url_static(port, "/empty.html");
+    created.goto_builder(&url).goto().await.unwrap();
+    let product: i32 = created.eval("() => window.contextMul(4, 5)").await.unwrap();
+    assert_eq!(product, 20);
+    c.remove_exposed_function("contextMul").await.unwrap();
+    let removed: bool = existing
+        .eval("() => !('contextMul' in window)")
+        .await
+        .unwrap();
+    assert!(removed);
+    created.goto_builder(&url).goto().await.unwrap();
+    let removed: bool = created
+        .eval("() => !('contextMul' in window)")
+        .await
+        .unwrap();
+    assert!(removed);
+    existing.close(None).await.unwrap();
+    created.close(None).await.unwrap();
+}
+
+const HAR: &str = r#"{
+  "log": {
+    "version": "1.2",
+    "creator": { "name": "Playwright", "version": "1.0" },
+    "pages": [],
+    "entries": [{
+      "startedDateTime": "2022-01-01T00:00:00.000Z",
+      "time": 1,
+      "request": {
+        "method": "GET",
+        "url": "https://har.test/",
+        "httpVersion": "HTTP/1.1",
+        "cookies": [],
+        "headers": [],
+        "queryString": [],
+        "headersSize": -1,
+        "bodySize": 0
+      },
+      "response": {
+        "status": 200,
+        "statusText": "OK",
+        "httpVersion": "HTTP/1.1",
+        "cookies": [],
+        "headers": [{ "name": "content-type", "value": "text/html" }],
+        "content": { "size": 30, "mimeType": "text/html", "text": "<title>served from har</title>" },
+        "redirectURL": "",
+        "headersSize": -1,
+        "bodySize": 30
+      },
+      "cache": {},
+      "timings": { "send": 0, "wait": 1, "receive": 0 }
+    }]
+  }
+}"#;
+
+async fn route_from_har_should_work(browser: &Browser) {
+    let c = browser.context_builder().build().await.unwrap();
+    let missing = super::temp_dir().join("missing.har");
+    let err = c
+        .route_from_har(&missing, RouteFromHarOptions::default())
+        .await;
+    assert!(err.is_err());
+    let har = super::temp_dir().join("route_from_har.har");
+    std::fs::write(&har, HAR).unwrap();
+    c.route_from_har(&har, RouteFromHarOptions::default())
+        .await
+        .unwrap();
+    let page = c.new_page().await.unwrap();
+    page.goto_builder("https://har.test/").goto().await.unwrap();
+    assert_eq!(page.title().await.unwrap(), "served from har");
+    // not in the HAR, aborted by default
+    assert!(page
+        .goto_builder("https://har.test/missing")
+        .goto()
+        .await
+        .is_err());
+    c.close().await.unwrap();
+}
+
+async fn touchscreen_requires_has_touch(browser: &Browser) {
+    let c = browser.context_builder().build().await.unwrap();
+    let page = c.new_page().await.unwrap();
+    let err = page.touch_screen.tap(10., 10.).await.unwrap_err();
+    assert!(matches!(*err, playwright::Error::TouchNotEnabled));
+    page.set_content_builder("<button>tap</button>")
+        .set_content()
+        .await
+        .unwrap();
+    let err = page.locator("button").tap(None).await.unwrap_err();
+    assert!(matches!(*err, playwright::Error::TouchNotEnabled));
+    c.close().await.unwrap();
+}
+
+async fn clipboard_permissions(browser: &Browser, port: u16) {
+    let c = browser.context_builder().build().await.unwrap();
+    let page = c.new_page().await.unwrap();
+    let origin = format!("http://localhost:{}", port);
+    c.grant_permissions(
+        &[
+            Permission::ClipboardRead.into(),
+            Permission::ClipboardWrite.into(),
+        ],
+        Some(&origin),
+    )
+    .await
+    .unwrap();
+    let query = "() => navigator.permissions.query({ name: 'clipboard-write' }).then(s => s.state)";
+    page.goto_builder(&format!("http://127.0.0.1:{}/static/empty.html", port))
+        .goto()
+        .await
+        .unwrap();
+    let state: String = page.eval(query).await.unwrap();
+    assert_ne!(state, "granted");
+    page.goto_builder(&super::url_static(port, "/empty.html"))
+        .goto()
+        .await
+        .unwrap();
+    let state: String = page.eval(query).await.unwrap();
+    assert_eq!(state, "granted");
+    let text: String = page
+        .eval(
+            "async () => {
+                await navigator.clipboard.writeText('copied');
+                return navigator.clipboard.readText();
+            }",
+        )
+        .await
+        .unwrap();
+    assert_eq!(text, "copied");
+    c.close().await.unwrap();
+}
+
+async fn service_workers(browser: &Browser, port: u16) {
+    let url = super::url_static(port, "/sw.html");
+    let c = browser.context_builder().build().await.unwrap();
+    let page = c.new_page().await.unwrap();
+    assert!(c.service_workers().unwrap().is_empty());
+    let (evt, _) = tokio::join!(
+        c.expect_event(browser_context::EventType::ServiceWorker),
+        page.goto_builder(&url).goto()
+    );
+    let worker = match evt.unwrap() {
+        browser_context::Event::ServiceWorker(w) => w,
+        _ => unreachable!(),
+    };
+    assert_eq!(worker.url().unwrap(), super::url_static(port, "/sw.js"));
+    assert!(c.service_workers().unwrap() == vec![worker]);
+    c.close().await.unwrap();
+
+    let c = browser
+        .context_builder()
+        .service_workers(ServiceWorkerPolicy::Block)
+        .build()
+        .await
+        .unwrap();
+    let page = c.new_page().await.unwrap();
+    page.goto_builder(&url).goto().await.unwrap();
+    let registered: bool = page
+        .eval("async () => !!(await window.registration)")
+        .await
+        .unwrap();
+    assert!(!registered);
+    assert!(c.service_workers().unwrap().is_empty());
+    c.close().await.unwrap();
+}
+
+async fn api_request_methods(c: &BrowserContext, port: u16) {
+    let request = c.request().unwrap();
+    let url = format!("http://localhost:{}/options", port);
+    let res = request.options(&url, None).await.unwrap();
+    assert_eq!(res.status(), 200);
+    let allow = res
+        .headers()
+        .into_iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case("allow"))
+        .map(|(_, v)| v);
+    assert_eq!(allow.as_deref(), Some("GET, HEAD, OPTIONS"));
+    // lowercase methods are sent uppercased
+    let options = RequestOptions {
+        method: Some("options".into()),
+        ..RequestOptions::default()
+    };
+    let res = request.fetch(&url, Some(options)).await.unwrap();
+    assert_eq!(res.status(), 200);
+}
+
+async fn api_request_retry_delays(c: &BrowserContext, port: u16) {
+    let request = c.request().unwrap();
+    let flaky = format!("http://localhost:{}/flaky", port);
+    // two 503s, then the third attempt succeeds
+    let started = std::time::Instant::now();
+    let res = request
+        .get(
+            &flaky,
+            Some(RequestOptions::default().retry_delays(vec![50., 100.])),
+        )
+        .await
+        .unwrap();
+    assert_eq!(res.status(), 200);
+    assert!(started.elapsed() >= std::time::Duration::from_millis(150));
+}
+
+async fn tracing_scope(browser: &Browser) {
+    use playwright::api::TracingStartOptions;
+    let c = browser.context_builder().build().await.unwrap();
+    let tracing = c.tracing().unwrap();
+    let path = super::temp_dir().join("scoped-trace.zip");
+    let _ = std::fs::remove_file(&path);
+    {
+        let _guard = tracing
+            .scope(TracingStartOptions::default(), path.clone())
+            .await
+            .unwrap();
+        let page = c.new_page().await.unwrap();
+        page.set_content_builder("<p>traced</p>")
+            .set_content()
+            .await
+            .unwrap();
+    }
+    // the stop runs in the background after the drop
+    for _ in 0..50 {
+        if path.is_file() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    assert!(path.is_file());
+
+    let path = super::temp_dir().join("scoped-trace-stopped.zip");
+    let _ = std::fs::remove_file(&path);
+    let guard = tracing
+        .scope(TracingStartOptions::default(), path.clone())
+        .await
+        .unwrap();
+    guard.stop().await.unwrap();
+    assert!(path.is_file());
+
+    let path = super::temp_dir().join("grouped-trace.zip");
+    let _ = std::fs::remove_file(&path);
+    let options = TracingStartOptions {
+        title: Some("Titled chunk"),
+        ..TracingStartOptions::default()
+    };
+    let guard = tracing.scope(options, path.clone()).await.unwrap();
+    tracing
+        .group_with_url("checkout", "https://example.com/checkout")
+        .await
+        .unwrap();
+    tracing.group("inner", None).await.unwrap();
+    tracing.group_end().await.unwrap();
+    tracing.group_end().await.unwrap();
+    guard.stop().await.unwrap();
+    assert!(path.is_file());
+    c.close().await.unwrap();
+}
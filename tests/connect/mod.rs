@@ -1,5 +1,7 @@
 use super::{free_local_port, install_browser, playwright_with_driver, Which};
-use playwright::api::{page, Browser, BrowserType};
+#[cfg(feature = "tungstenite")]
+use playwright::api::ConnectOptions;
+use playwright::api::{page, Browser, BrowserType, ConnectOverCdpOptions};
 use serde::Deserialize;
 
 pub(super) async fn connect_over_cdp(which: Which) {
@@ -14,6 +16,66 @@ pub(super) async fn connect_over_cdp(which: Which) {
     ws(&browser_type).await;
 }
 
+#[cfg(feature = "tungstenite")]
+pub(super) async fn connect(which: Which) {
+    let mut playwright = playwright_with_driver().await;
+    install_browser(&playwright, which);
+    let browser_type = match which {
+        Which::Chromium => playwright.chromium(),
+        _ => return,
+    };
+    let port = free_local_port().unwrap();
+    let ws_endpoint = format!("ws://127.0.0.1:{}/", port);
+    let err = browser_type
+        .connect(&ws_endpoint, ConnectOptions::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(*err, playwright::Error::Transport(_)), "{:?}", err);
+    let mut server = std::process::Command::new(playwright.driver().executable())
+        .args(&[
+            "run-server",
+            "--host",
+            "127.0.0.1",
+            "--port",
+            &port.to_string(),
+        ])
+        .spawn()
+        .unwrap();
+    let mut retries = 0;
+    let browser = loop {
+        match browser_type
+            .connect(&ws_endpoint, ConnectOptions::default())
+            .await
+        {
+            Ok(b) => break b,
+            // the server is still starting
+            Err(_) if retries < 50 => {
+                retries += 1;
+                tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+            }
+            Err(e) => panic!("{:?}", e),
+        }
+    };
+    let context = browser.context_builder().build().await.unwrap();
+    let page = context.new_page().await.unwrap();
+    page.set_content_builder("<p>remote</p>")
+        .set_content()
+        .await
+        .unwrap();
+    let text: String = page.eval("() => document.body.textContent").await.unwrap();
+    assert_eq!(text, "remote");
+    browser.close().await.unwrap();
+    // dropping the handle disconnects as well
+    let browser = browser_type
+        .connect(&ws_endpoint, ConnectOptions::default())
+        .await
+        .unwrap();
+    let context = browser.context_builder().build().await.unwrap();
+    drop(browser);
+    assert!(context.new_page().await.is_err());
+    server.kill().unwrap();
+}
+
 async fn http(browser_type: &BrowserType) {
     let port = free_local_port().unwrap();
     let browser = browser_type
@@ -74,9 +136,12 @@ async fn ws(browser_type: &BrowserType) {
         cdp1.close().await.unwrap();
     }
     {
+        let options = ConnectOverCdpOptions {
+            timeout: Some(10_000.),
+            ..ConnectOverCdpOptions::default()
+        };
         let cdp2 = browser_type
-            .connect_over_cdp_builder(&ws_endpoint)
-            .connect_over_cdp()
+            .connect_over_cdp(&ws_endpoint, options)
             .await
             .unwrap();
         assert_eq!(cdp2.contexts().unwrap().len(), 1);
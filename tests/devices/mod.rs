@@ -1,6 +1,6 @@
 use super::Which;
 use playwright::{
-    api::{page, Page, Viewport},
+    api::{page, Browser, Page, Viewport},
     Playwright,
 };
 
@@ -30,6 +30,26 @@ pub async fn all(playwright: &Playwright, port: u16, _which: Which) {
         .launch()
         .await
         .unwrap();
+    iphone_13(playwright, &browser).await;
+}
+
+async fn iphone_13(playwright: &Playwright, browser: &Browser) {
+    let device = playwright
+        .devices()
+        .into_iter()
+        .find(|d| d.name == "iPhone 13")
+        .unwrap();
+    assert!(device.is_mobile);
+    let ctx = browser
+        .context_builder()
+        .set_device(&device)
+        .build()
+        .await
+        .unwrap();
+    let page = ctx.new_page().await.unwrap();
+    let user_agent: String = page.eval("() => navigator.userAgent").await.unwrap();
+    assert!(user_agent.contains("iPhone"), "{}", user_agent);
+    ctx.close().await.unwrap();
 }
 
 async fn check_size(page: &Page) {
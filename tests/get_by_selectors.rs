@@ -35,6 +35,7 @@ async fn run() -> Result<(), playwright::Error> {
       <label>Username <input aria-label="Username" placeholder="user name" /></label>
       <img alt="Playwright logo" src="data:image/gif;base64,R0lGODlhAQABAIAAAAAAAP///ywAAAAAAQABAAACAUwAOw==" />
       <div title="Greeting">Hello!</div>
+      <div role="slider" aria-label="Volume" aria-valuenow="3" tabindex="0"></div>
     </main>
     "#)
     .timeout(30_000.0)
@@ -85,6 +86,11 @@ async fn run() -> Result<(), playwright::Error> {
         .await?;
     assert_eq!(title_text.trim(), "Hello!");
 
+    // aria role and accessible name
+    let slider = page.locator("[aria-label=Volume]");
+    assert_eq!(slider.aria_role().await?, "slider");
+    assert_eq!(slider.accessible_name().await?, "Volume");
+
     context.close().await.ok();
     browser.close().await.ok();
     Ok(())
@@ -1,7 +1,8 @@
 use super::Which;
 use futures::stream::StreamExt;
 use playwright::api::{
-    page, BrowserContext, DocumentLoadState, Geolocation, Page, Viewport,
+    page, worker, BrowserContext, DocumentLoadState, DragOptions, File, Geolocation, Page,
+    RequestErrorCode, Viewport,
 };
 use tokio::time::{timeout, Duration};
 
@@ -30,6 +31,8 @@ pub async fn all(c: &BrowserContext, port: u16, which: Which) {
     load_events_should_fire(&page, port).await;
     domcontentloaded_event_should_fire(&page, port).await;
     opener_should_work(c).await;
+    expect_popup_should_work(c).await;
+    wait_for_typed_event(c, port).await;
     opener_should_be_null_after_parent_close(c).await;
     page_url_should_include_hashes(c, port).await;
     dialog_should_fire(&page).await;
@@ -41,35 +44,84 @@ pub async fn all(c: &BrowserContext, port: u16, which: Which) {
     wait_for_load_state_should_work(&page, port).await;
     wait_for_url_should_work(&page, port).await;
     permissions(c, &page, port, which).await;
-    if which != Which::Firefox {
-        // XXX: go_back response is null on firefox
-        navigations(&page, port).await;
-    }
+    navigations(&page, port, which).await;
     front_should_work(c, &page).await;
     concurrent!(
         which,
         set_extra_http_headers(c, port),
+        add_extra_http_headers(c, port),
+        extra_http_headers_keep_order(c, port),
+        response_headers(c, port),
+        wait_for_response_should_work(c, port),
+        block_resources(c, port),
+        response_from_service_worker(c, port, which),
+        wait_for_request_should_work(c, port),
+        request_response_should_work(c, port),
+        expect_request_finished_should_work(c, port),
+        capture_json_responses(c, port),
+        request_failure_should_be_classified(c, port),
+        expose_function_survives_navigation(c, port),
+        expect_navigation(c, port),
+        remove_exposed_function(c),
+        on_console(c),
+        console_message_args(c, port),
+        expect_console_message(c),
         focus_should_work(c),
         add_script_tag_includes_source_url(c, port),
+        add_style_tag(c),
         reload_should_worker(c),
+        reload_wait_until(c, port),
+        wait_for_navigation(c, port),
         screenshot_should_work(&page),
+        screenshot_waits_for_fonts(c, port),
+        screenshot_full_page_and_clip(c),
+        element_screenshot(c),
+        locator_screenshot(c),
+        locator_evaluate_handle(c),
+        locator_with(c),
+        locator_handler(c),
+        locator_aria_snapshot(c),
+        frame_detached(c),
+        frame_title_content(c),
+        page_content(c, port),
         title_should_work(&page),
+        pause_without_inspector(c),
         check_should_work(c),
         pointer(c),
+        mouse_wheel(c),
+        wait_for_function(c),
+        eval_with_timeout(c),
+        wait_for_selector_all(c),
+        mouse_gesture(c),
+        touchscreen_tap(c),
+        drag_and_drop(c),
+        frame_actions(c),
+        trial_click(c),
+        click_button_and_modifiers(c, port),
+        cdp_network_conditions(c, port, which),
         viewport(c),
+        device_scale_factor(c),
         download(c, port),
+        expect_download(c, port),
         workers_should_work(c, port, which),
         accessibility(c),
         query_selector_and_eval(c),
-        input(c)
+        evaluate_handle(c),
+        input(c),
+        keyboard_insert_text(c),
+        keyboard_hold_shift(c)
     );
-    // TODO
-    // file_chooser(c, port).await;
+    file_chooser(c, port).await;
+    set_input_files(c, port).await;
+    page_set_input_files(c, port).await;
     if which != Which::Firefox {
         pdf_should_work(&page).await;
     }
     video(&page).await;
+    video_save_as(c).await;
     emulate_media(&page).await;
+    emulate_media_features(&page).await;
+    emulate_vision_deficiency(&page, which).await;
 }
 
 macro_rules! done {
@@ -158,24 +210,104 @@ async fn reload_should_worker(c: &BrowserContext) {
     close(&page).await;
 }
 
-async fn navigations(page: &Page, port: u16) {
+async fn reload_wait_until(c: &BrowserContext, port: u16) {
+    let page = new(c).await;
+    let url = super::url_static(port, "/empty.html");
+    page.goto_builder(&url).goto().await.unwrap();
+    let response = page
+        .reload_builder()
+        .wait_until(DocumentLoadState::DomContentLoaded)
+        .timeout(5_000.0)
+        .reload()
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(response.ok().unwrap());
+    assert_eq!(response.url().unwrap(), url);
+    let response = page
+        .reload_builder()
+        .wait_until(DocumentLoadState::Commit)
+        .reload()
+        .await
+        .unwrap();
+    assert!(response.is_some());
+    close(&page).await;
+}
+
+async fn wait_for_navigation(c: &BrowserContext, port: u16) {
+    use page::WaitForNavigationOptions;
+    use playwright::api::UrlOrPredicate;
+    let p = new(c).await;
+    let url = super::url_static(port, "/empty.html");
+    p.goto_builder(&url).goto().await.unwrap();
+    p.eval::<()>(
+        r#"() => {
+            const a = document.createElement('a');
+            a.id = 'route';
+            a.href = '#';
+            a.textContent = 'route';
+            a.onclick = e => {
+                e.preventDefault();
+                history.pushState({}, '', '/spa/other');
+                history.pushState({}, '', '/spa/route');
+            };
+            document.body.appendChild(a);
+        }"#,
+    )
+    .await
+    .unwrap();
+    let (response, clicked) = tokio::join!(
+        p.wait_for_navigation(WaitForNavigationOptions {
+            url: Some("**/spa/route".into()),
+            ..Default::default()
+        }),
+        p.click_builder("#route").click()
+    );
+    clicked.unwrap();
+    assert_eq!(response.unwrap(), None);
+    assert!(p.url().unwrap().ends_with("/spa/route"));
+    let url2 = super::url_static(port, "/empty2.html");
+    let (response, navigated) = tokio::join!(
+        p.wait_for_navigation(WaitForNavigationOptions {
+            url: Some(UrlOrPredicate::Predicate(Box::new(|u: &String| {
+                u.ends_with("/empty2.html")
+            }))),
+            wait_until: Some(DocumentLoadState::DomContentLoaded),
+            timeout: Some(5_000.0)
+        }),
+        p.goto_builder(&url2).goto()
+    );
+    navigated.unwrap();
+    let response = response.unwrap().unwrap();
+    assert!(response.ok().unwrap());
+    assert_eq!(response.url().unwrap(), url2);
+    close(&p).await;
+}
+
+async fn navigations(page: &Page, port: u16, which: Which) {
     assert_eq!(page.go_back_builder().go_back().await.unwrap(), None);
     let url1 = super::url_static(port, "/empty.html");
     let url2 = super::url_static(port, "/empty2.html");
     page.goto_builder(&url1).goto().await.unwrap();
     page.goto_builder(&url2).goto().await.unwrap();
-    {
-        let response = page.go_back_builder().go_back().await.unwrap().unwrap();
+    let response = page
+        .go_back_builder()
+        .wait_until(DocumentLoadState::DomContentLoaded)
+        .timeout(5_000.0)
+        .go_back()
+        .await
+        .unwrap();
+    assert_eq!(page.url().unwrap(), url1);
+    // firefox restores from the back-forward cache without a response
+    if which != Which::Firefox {
+        let response = response.unwrap();
         assert!(response.ok().unwrap());
         assert_eq!(response.url().unwrap(), url1);
     }
-    {
-        let response = page
-            .go_forward_builder()
-            .go_forward()
-            .await
-            .unwrap()
-            .unwrap();
+    let response = page.go_forward_builder().go_forward().await.unwrap();
+    assert_eq!(page.url().unwrap(), url2);
+    if which != Which::Firefox {
+        let response = response.unwrap();
         assert!(response.ok().unwrap());
         assert_eq!(response.url().unwrap(), url2);
     }
@@ -214,17 +346,42 @@ async fn workers_should_work(c: &BrowserContext, port: u16, which: Which) {
             .unwrap(),
         "worker function result"
     );
+    assert_eq!(
+        w.evaluate::<_, i32>("([a, b]) => a * b", Some((6, 7)))
+            .await
+            .unwrap(),
+        42
+    );
+    let mut events = w.subscribe_event().unwrap();
     page.goto_builder(&empty).goto().await.unwrap();
     assert_eq!(workers().len(), 0);
+    let closed = timeout(Duration::from_secs(5), events.next())
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert!(matches!(closed, worker::Event::Close));
     close(&page).await;
 }
 
 async fn ensure_timeout(page: &Page) {
-    page.set_default_timeout(500).await.unwrap();
-    match page.expect_event(page::EventType::Load).await {
+    let res = page
+        .with_timeout(500, page.expect_event(page::EventType::Load))
+        .await
+        .unwrap();
+    match res {
         Err(playwright::Error::Timeout) => {}
         _ => panic!("Not expected"),
     }
+    // the 500ms override is gone, so a load that takes longer than it still resolves
+    let (evt, _) = tokio::join!(
+        page.expect_event(page::EventType::Load),
+        async {
+            tokio::time::sleep(Duration::from_millis(700)).await;
+            page.reload_builder().reload().await
+        }
+    );
+    assert!(evt.is_ok());
 }
 
 async fn permissions(c: &BrowserContext, page: &Page, port: u16, which: Which) {
@@ -290,6 +447,39 @@ async fn viewport(c: &BrowserContext) {
     close(&p).await;
 }
 
+async fn device_scale_factor(c: &BrowserContext) {
+    use playwright::api::ScreenshotScale;
+    let browser = c.browser().unwrap().unwrap();
+    let css = Viewport {
+        width: 400,
+        height: 300,
+    };
+    let retina = browser
+        .context_builder()
+        .viewport(Some(css.clone()))
+        .device_scale_factor(2.0)
+        .build()
+        .await
+        .unwrap();
+    let p = retina.new_page().await.unwrap();
+    assert_eq!(p.viewport_size().unwrap(), Some(css));
+    assert!((p.device_scale_factor().await.unwrap() - 2.0).abs() < f64::EPSILON);
+    let shot = |scale: ScreenshotScale| p.screenshot_builder().scale(scale).screenshot();
+    assert_eq!(png_size(&p.screenshot_builder().screenshot().await.unwrap()), (800, 600));
+    assert_eq!(png_size(&shot(ScreenshotScale::Device).await.unwrap()), (800, 600));
+    assert_eq!(png_size(&shot(ScreenshotScale::Css).await.unwrap()), (400, 300));
+    let resized = Viewport {
+        width: 200,
+        height: 100,
+    };
+    p.set_viewport_size(resized.clone()).await.unwrap();
+    assert_eq!(p.viewport_size().unwrap(), Some(resized));
+    assert!((p.device_scale_factor().await.unwrap() - 2.0).abs() < f64::EPSILON);
+    assert_eq!(png_size(&shot(ScreenshotScale::Device).await.unwrap()), (400, 200));
+    assert_eq!(png_size(&shot(ScreenshotScale::Css).await.unwrap()), (200, 100));
+    retina.close().await.unwrap();
+}
+
 async fn download(c: &BrowserContext, port: u16) {
     let p = new(c).await;
     p.set_content_builder(&format!(
@@ -320,14 +510,55 @@ async fn download(c: &BrowserContext, port: u16) {
     close(&p).await;
 }
 
+async fn expect_download(c: &BrowserContext, port: u16) {
+    let p = new(c).await;
+    p.set_content_builder(&format!(
+        r#"<a href="{}">download</a>"#,
+        super::url_download(port, "/worker.html")
+    ))
+    .set_content()
+    .await
+    .unwrap();
+    let download = p
+        .expect_download(|| p.click_builder("a").click())
+        .await
+        .unwrap();
+    assert!(!download.suggested_filename().is_empty());
+    let bytes = download.read().await.unwrap();
+    assert_eq!(bytes, std::fs::read("tests/server/worker.html").unwrap());
+    download.delete().await.unwrap();
+    let failed = p
+        .expect_download(|| p.click_builder("#missing").timeout(100.).click())
+        .await;
+    assert!(failed.is_err());
+    let download = p
+        .expect_download(|| p.click_builder("a").click())
+        .await
+        .unwrap();
+    download.cancel().await.unwrap();
+    // cancelling a finished download is a no-op
+    let failure = download.failure().await.unwrap();
+    assert!(failure.is_none() || failure.as_deref() == Some("canceled"));
+    close(&p).await;
+}
+
 async fn video(p: &Page) {
     let video = p.video().unwrap().unwrap();
     dbg!(video.path().unwrap());
-    // TODO
-    // let path = super::temp_dir().join("video.webm");
-    // video.save_as(&path).await.unwrap();
-    // assert!(path.is_file());
-    // video.delete().await.unwrap();
+}
+
+async fn video_save_as(c: &BrowserContext) {
+    let p = new(c).await;
+    let video = p.video().unwrap().unwrap();
+    p.set_content_builder("<div>video</div>")
+        .set_content()
+        .await
+        .unwrap();
+    close(&p).await;
+    let path = super::temp_dir().join("video.webm");
+    video.save_as(&path).await.unwrap();
+    assert!(path.is_file());
+    video.delete().await.unwrap();
 }
 
 async fn add_script_tag_includes_source_url(c: &BrowserContext, port: u16) {
@@ -356,6 +587,23 @@ async fn add_script_tag_includes_source_url(c: &BrowserContext, port: u16) {
     close(&page).await;
 }
 
+async fn add_style_tag(c: &BrowserContext) {
+    let page = new(c).await;
+    let style = page
+        .add_style_tag_builder()
+        .content("body { background: red }")
+        .add_style_tag()
+        .await
+        .unwrap();
+    assert_eq!(style.inner_html().await.unwrap(), "body { background: red }");
+    let background: String = page
+        .eval("() => getComputedStyle(document.body).backgroundColor")
+        .await
+        .unwrap();
+    assert_eq!(background, "rgb(255, 0, 0)");
+    close(&page).await;
+}
+
 async fn accessibility(c: &BrowserContext) {
     let p = new(c).await;
     use playwright::api::accessibility::SnapshotResponse;
@@ -418,11 +666,21 @@ async fn accessibility(c: &BrowserContext) {
         .await
         .unwrap();
     assert_ne!(snapshot, input_response);
+    let json = ac
+        .snapshot_builder()
+        .try_root(p.query_selector("input").await.unwrap().unwrap())
+        .unwrap()
+        .snapshot_json()
+        .await
+        .unwrap();
+    assert_eq!(json["role"], "textbox");
+    assert_eq!(json["name"], "Empty input");
+    assert_eq!(json["focused"], true);
     close(&p).await;
 }
 
 async fn screenshot_should_work(p: &Page) {
-    use playwright::api::ScreenshotType;
+    use playwright::api::{ScreenshotAnimations, ScreenshotScale, ScreenshotType};
     let path = super::temp_dir().join("screenshot.jpg");
     p.screenshot_builder()
         .r#type(ScreenshotType::Jpeg)
@@ -432,12 +690,354 @@ async fn screenshot_should_work(p: &Page) {
         .await
         .unwrap();
     assert!(path.is_file());
+    let css = p
+        .screenshot_builder()
+        .scale(ScreenshotScale::Css)
+        .screenshot()
+        .await
+        .unwrap();
+    assert!(!css.is_empty());
+    let settled = p
+        .screenshot_builder()
+        .animations(ScreenshotAnimations::Disabled)
+        .wait_for_fonts(true)
+        .timeout(5000.0)
+        .screenshot()
+        .await
+        .unwrap();
+    assert!(!settled.is_empty());
+}
+
+async fn screenshot_waits_for_fonts(c: &BrowserContext, port: u16) {
+    use std::time::Duration;
+    let p = new(c).await;
+    p.route("**/slow.woff", |route| async move {
+        tokio::time::sleep(Duration::from_millis(1000)).await;
+        let _ = route.abort(None).await;
+    })
+    .await
+    .unwrap();
+    let load = "url => {
+        const font = new FontFace('slow', `url(${url})`);
+        document.fonts.add(font);
+        font.load().catch(() => {});
+    }";
+    let url = super::url_static(port, "/slow.woff");
+    p.evaluate::<_, ()>(load, url.as_str()).await.unwrap();
+    let pending = p
+        .screenshot_builder()
+        .wait_for_fonts(true)
+        .timeout(300.0)
+        .screenshot()
+        .await;
+    assert!(pending.is_err());
+    p.screenshot_builder()
+        .wait_for_fonts(true)
+        .timeout(5000.0)
+        .screenshot()
+        .await
+        .unwrap();
+    let status: String = p.eval("() => document.fonts.status").await.unwrap();
+    assert_eq!(status, "loaded");
+    close(&p).await;
+}
+
+async fn screenshot_full_page_and_clip(c: &BrowserContext) {
+    use playwright::api::{FloatRect, ScreenshotScale, ScreenshotType};
+    let p = new(c).await;
+    p.set_content_builder(r#"<div style="height: 3000px"></div>"#)
+        .set_content()
+        .await
+        .unwrap();
+    let shot = |full_page: bool| {
+        p.screenshot_builder()
+            .scale(ScreenshotScale::Css)
+            .full_page(full_page)
+            .screenshot()
+    };
+    let (_, viewport_height) = png_size(&shot(false).await.unwrap());
+    let (_, full_height) = png_size(&shot(true).await.unwrap());
+    assert!(full_height > viewport_height);
+    let clip = FloatRect {
+        x: 10.,
+        y: 10.,
+        width: 50.,
+        height: 30.,
+    };
+    let clipped = p
+        .screenshot_builder()
+        .scale(ScreenshotScale::Css)
+        .clip(clip)
+        .omit_background(true)
+        .screenshot()
+        .await
+        .unwrap();
+    assert_eq!(png_size(&clipped), (50, 30));
+    let both = p
+        .screenshot_builder()
+        .clip(clip)
+        .full_page(true)
+        .screenshot()
+        .await;
+    assert!(both.is_err());
+    let png_quality = p
+        .screenshot_builder()
+        .r#type(ScreenshotType::Png)
+        .quality(50)
+        .screenshot()
+        .await;
+    assert!(png_quality.is_err());
+    let jpeg = p
+        .screenshot_builder()
+        .r#type(ScreenshotType::Jpeg)
+        .quality(50)
+        .screenshot()
+        .await
+        .unwrap();
+    assert!(!jpeg.is_empty());
+    close(&p).await;
+}
+
+async fn element_screenshot(c: &BrowserContext) {
+    use playwright::api::{ElementScreenshotOptions, ScreenshotAnimations, ScreenshotScale};
+    let p = new(c).await;
+    p.set_content_builder(
+        r#"<div style="height: 2000px"></div>
+        <div id="box" style="width: 50px; height: 30px; background: red"></div>"#,
+    )
+    .set_content()
+    .await
+    .unwrap();
+    let element = p.query_selector("#box").await.unwrap().unwrap();
+    let path = super::temp_dir().join("element.png");
+    let png = element
+        .screenshot(ElementScreenshotOptions {
+            path: Some(path.clone()),
+            scale: Some(ScreenshotScale::Css),
+            animations: Some(ScreenshotAnimations::Disabled),
+            ..ElementScreenshotOptions::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(png_size(&png), (50, 30));
+    assert_eq!(std::fs::read(&path).unwrap(), png);
+    let quality = element
+        .screenshot(ElementScreenshotOptions {
+            quality: Some(50),
+            ..ElementScreenshotOptions::default()
+        })
+        .await;
+    assert!(quality.is_err());
+    close(&p).await;
+}
+
+async fn locator_screenshot(c: &BrowserContext) {
+    use playwright::api::ElementScreenshotOptions;
+    let p = new(c).await;
+    p.set_content_builder(
+        r#"<div class="box" style="display: none; width: 40px; height: 20px; background: red"></div>
+        <div class="box" style="width: 10px; height: 10px; background: blue"></div>
+        <script>
+          setTimeout(() => { document.querySelector('.box').style.display = 'block'; }, 100);
+        </script>"#,
+    )
+    .set_content()
+    .await
+    .unwrap();
+    let png = p
+        .locator(".box")
+        .screenshot(ElementScreenshotOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(png_size(&png), (40, 20));
+    let missing = p
+        .locator("#missing")
+        .screenshot(ElementScreenshotOptions {
+            timeout: Some(100.0),
+            ..ElementScreenshotOptions::default()
+        })
+        .await;
+    assert!(missing.is_err());
+    close(&p).await;
+}
+
+async fn locator_with(c: &BrowserContext) {
+    let p = new(c).await;
+    p.set_content_builder(
+        r#"<ul>
+          <li>apple <button>buy</button></li>
+          <li>banana <button>buy</button></li>
+          <li>cherry <span>sold out</span></li>
+        </ul>"#,
+    )
+    .set_content()
+    .await
+    .unwrap();
+    let banana = p.locator_with("li", None, Some("banana"));
+    assert_eq!(banana.count().await.unwrap(), 1);
+    assert_eq!(banana.inner_text(None).await.unwrap(), "banana buy");
+    let button = p.locator("button");
+    let in_stock = p.locator_with("li", Some(&button), None);
+    assert_eq!(in_stock.count().await.unwrap(), 2);
+    close(&p).await;
+}
+
+async fn locator_evaluate_handle(c: &BrowserContext) {
+    let p = new(c).await;
+    p.set_content_builder(r#"<div id="parent"><span data-name="child">x</span></div>"#)
+        .set_content()
+        .await
+        .unwrap();
+    let span = p.locator("span");
+    let parent = span.evaluate_element_handle("el => el.parentElement").await.unwrap();
+    assert_eq!(parent.get_attribute("id").await.unwrap().as_deref(), Some("parent"));
+    let mut dataset = span.evaluate_handle("el => el.dataset").await.unwrap();
+    let name: String = dataset.get_property("name").await.unwrap().json_value().await.unwrap();
+    assert_eq!(name, "child");
+    let mut parent = span.evaluate_handle("el => el.parentElement").await.unwrap();
+    let tag: String = parent.get_property("tagName").await.unwrap().json_value().await.unwrap();
+    assert_eq!(tag, "DIV");
+    close(&p).await;
+}
+
+async fn locator_aria_snapshot(c: &BrowserContext) {
+    let p = new(c).await;
+    p.set_content_builder(r#"<nav><a href="/">Home</a><a href="/about">About</a></nav>"#)
+        .set_content()
+        .await
+        .unwrap();
+    let tree = p.locator("nav").aria_snapshot(None).await.unwrap();
+    assert!(tree.contains("- navigation"), "{}", tree);
+    assert!(tree.contains(r#"link "Home""#), "{}", tree);
+    assert!(tree.contains(r#"link "About""#), "{}", tree);
+    close(&p).await;
+}
+
+async fn locator_handler(c: &BrowserContext) {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+    let p = new(c).await;
+    p.set_content_builder(
+        r#"<button id="start" onclick="document.getElementById('banner').style.display = 'block'">start</button>
+        <button id="target" onclick="window.clicked = true">target</button>
+        <div id="banner" style="display: none; position: fixed; inset: 0; background: white">
+          <button id="accept" onclick="this.parentElement.style.display = 'none'">accept</button>
+        </div>"#,
+    )
+    .set_content()
+    .await
+    .unwrap();
+    let banner = p.locator("#banner");
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    p.add_locator_handler(&banner, move |banner| {
+        let counter = counter.clone();
+        async move {
+            counter.fetch_add(1, Ordering::SeqCst);
+            banner.locator("#accept").click_builder().click().await.unwrap();
+        }
+    })
+    .await
+    .unwrap();
+    p.click_builder("#start").click().await.unwrap();
+    p.click_builder("#target").click().await.unwrap();
+    assert!(p.eval::<bool>("() => window.clicked").await.unwrap());
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    p.remove_locator_handler(&banner).await.unwrap();
+    p.click_builder("#start").click().await.unwrap();
+    let blocked = p.click_builder("#target").timeout(500.0).click().await;
+    assert!(blocked.is_err());
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    close(&p).await;
+}
+
+async fn frame_title_content(c: &BrowserContext) {
+    let p = new(c).await;
+    p.set_content_builder(r#"<iframe srcdoc="<title>Child</title><p>child</p>"></iframe>"#)
+        .set_content()
+        .await
+        .unwrap();
+    let frame = p.main_frame().child_frames().unwrap().remove(0);
+    assert_eq!(frame.title().await.unwrap(), "Child");
+    frame
+        .eval::<()>("() => { document.body.innerHTML += '<i id=marker></i>' }")
+        .await
+        .unwrap();
+    let html = frame.content().await.unwrap();
+    assert!(html.contains(r#"<i id="marker"></i>"#), "{}", html);
+    assert!(!p.content().await.unwrap().contains("marker"));
+    close(&p).await;
+}
+
+async fn page_content(c: &BrowserContext, port: u16) {
+    let p = new(c).await;
+    let url = super::url_static(port, "/form.html");
+    let response = p.goto_builder(&url).goto().await.unwrap().unwrap();
+    p.eval::<()>("() => { document.body.innerHTML += '<i id=added></i>' }")
+        .await
+        .unwrap();
+    let html = p.content().await.unwrap();
+    assert!(html.starts_with("<!DOCTYPE html>"), "{}", html);
+    assert!(html.contains(r#"<i id="added"></i>"#), "{}", html);
+    assert!(!response.text().await.unwrap().contains("added"));
+    close(&p).await;
+}
+
+async fn frame_detached(c: &BrowserContext) {
+    let p = new(c).await;
+    p.set_content_builder(r#"<iframe srcdoc="<p>child</p>"></iframe>"#)
+        .set_content()
+        .await
+        .unwrap();
+    let main = p.main_frame();
+    let frame = main.child_frames().unwrap().remove(0);
+    assert!(!frame.is_detached());
+    let (evt, removed) = tokio::join!(
+        p.expect_event(page::EventType::FrameDetached),
+        p.eval::<()>("() => document.querySelector('iframe').remove()")
+    );
+    removed.unwrap();
+    match evt.unwrap() {
+        page::Event::FrameDetached(f) => assert_eq!(f, frame),
+        _ => unreachable!(),
+    }
+    assert!(frame.is_detached());
+    assert_eq!(frame.parent_frame().unwrap(), None);
+    assert!(main.child_frames().unwrap().is_empty());
+    let err = frame.eval::<i32>("() => 1").await.unwrap_err();
+    assert!(matches!(*err, playwright::Error::FrameDetached));
+    let err = frame.click_builder("p").click().await.unwrap_err();
+    assert!(matches!(*err, playwright::Error::FrameDetached));
+    close(&p).await;
+}
+
+/// Width and height from the IHDR chunk
+fn png_size(png: &[u8]) -> (u32, u32) {
+    let n = |i: usize| u32::from_be_bytes([png[i], png[i + 1], png[i + 2], png[i + 3]]);
+    (n(16), n(20))
 }
 
 async fn pdf_should_work(p: &Page) {
     let path = super::temp_dir().join("pdf.pdf");
     p.pdf_builder().path(path.clone()).pdf().await.unwrap();
     assert!(path.is_file());
+    p.pdf_builder().page_ranges("1, 1-1").pdf().await.unwrap();
+    p.add_style_tag("@page { size: A4 }", None).await.unwrap();
+    let css_sized = super::temp_dir().join("pdf_css_page_size.pdf");
+    p.pdf_builder()
+        .format("Letter")
+        .prefer_css_page_size(true)
+        .path(css_sized.clone())
+        .pdf()
+        .await
+        .unwrap();
+    assert!(css_sized.is_file());
+    for malformed in &["0", "2-1", "1,,2", "a-3", "-"] {
+        let err = p.pdf_builder().page_ranges(malformed).pdf().await;
+        assert!(err.is_err(), "{}", malformed);
+    }
 }
 
 async fn emulate_media(p: &Page) {
@@ -473,6 +1073,68 @@ async fn emulate_media(p: &Page) {
     assert!(!print().await);
 }
 
+async fn emulate_media_features(p: &Page) {
+    use playwright::api::{
+        page::{ForcedColors, ReducedMotion},
+        ColorScheme,
+    };
+    let matches = |query: &'static str| async move {
+        p.evaluate::<_, bool>("q => matchMedia(q).matches", query).await.unwrap()
+    };
+    p.emulate_media_builder()
+        .color_scheme(ColorScheme::Dark)
+        .reduced_motion(ReducedMotion::Reduce)
+        .emulate_media()
+        .await
+        .unwrap();
+    assert!(matches("(prefers-color-scheme: dark)").await);
+    assert!(matches("(prefers-reduced-motion: reduce)").await);
+    p.emulate_media_builder()
+        .color_scheme(ColorScheme::Light)
+        .emulate_media()
+        .await
+        .unwrap();
+    assert!(matches("(prefers-color-scheme: light)").await);
+    assert!(matches("(prefers-reduced-motion: reduce)").await);
+    p.emulate_media_builder()
+        .color_scheme(ColorScheme::Dark)
+        .forced_colors(ForcedColors::Active)
+        .reduced_motion(ReducedMotion::Null)
+        .emulate_media()
+        .await
+        .unwrap();
+    assert!(matches("(prefers-color-scheme: dark)").await);
+    assert!(!matches("(prefers-reduced-motion: reduce)").await);
+    // Reset to the context's color scheme, which defaults to light
+    p.emulate_media_builder()
+        .color_scheme(ColorScheme::Null)
+        .forced_colors(ForcedColors::Null)
+        .emulate_media()
+        .await
+        .unwrap();
+    assert!(!matches("(prefers-color-scheme: dark)").await);
+    assert!(matches("(prefers-color-scheme: light)").await);
+    assert!(!matches("(forced-colors: active)").await);
+}
+
+async fn emulate_vision_deficiency(p: &Page, which: Which) {
+    use playwright::api::page::VisionDeficiency;
+    let result = p
+        .emulate_vision_deficiency(VisionDeficiency::Deuteranopia)
+        .await;
+    if which == Which::Chromium {
+        result.unwrap();
+        p.emulate_vision_deficiency(VisionDeficiency::None)
+            .await
+            .unwrap();
+    } else {
+        match &*result.unwrap_err() {
+            playwright::Error::ChromiumOnly(_) => (),
+            e => panic!("{:?}", e),
+        }
+    }
+}
+
 async fn check_should_work(c: &BrowserContext) {
     let p = new(c).await;
     p.set_content_builder(r#"<input type="checkbox" />"#)
@@ -485,23 +1147,162 @@ async fn check_should_work(c: &BrowserContext) {
     p.uncheck_builder("input").uncheck().await.unwrap();
     let checked = p.is_checked("input", None).await.unwrap();
     assert!(!checked);
-    close(&p).await;
-}
-
-async fn title_should_work(p: &Page) {
-    p.eval::<String>(r#"() => document.title = "foo""#)
+    for expected in [true, true, false, false] {
+        p.set_checked_builder("input", expected)
+            .force(true)
+            .timeout(1000.)
+            .set_checked()
+            .await
+            .unwrap();
+        assert_eq!(p.is_checked("input", None).await.unwrap(), expected);
+    }
+    p.set_checked_builder("input", true)
+        .trial(true)
+        .set_checked()
         .await
         .unwrap();
-    assert_eq!(p.title().await.unwrap(), "foo");
+    assert!(!p.is_checked("input", None).await.unwrap());
+    close(&p).await;
 }
 
-async fn pointer(c: &BrowserContext) {
+async fn trial_click(c: &BrowserContext) {
     let p = new(c).await;
-    p.set_content_builder(r#"<input type="checkbox" />"#)
+    p.set_content_builder(r#"<input type="checkbox" /><button disabled>x</button>"#)
         .set_content()
         .await
         .unwrap();
-    let checked = || async {
+    p.click_builder("input").trial(true).click().await.unwrap();
+    p.dblclick_builder("input")
+        .trial(true)
+        .dblclick()
+        .await
+        .unwrap();
+    p.check_builder("input").trial(true).check().await.unwrap();
+    assert!(!p.is_checked("input", None).await.unwrap());
+    // the checks still run, so a disabled button is not clickable
+    let res = p
+        .click_builder("button")
+        .trial(true)
+        .timeout(300.)
+        .click()
+        .await;
+    assert!(res.is_err());
+    p.click_builder("input")
+        .no_wait_after(true)
+        .click()
+        .await
+        .unwrap();
+    assert!(p.is_checked("input", None).await.unwrap());
+    close(&p).await;
+}
+
+async fn click_button_and_modifiers(c: &BrowserContext, port: u16) {
+    use playwright::api::{KeyboardModifier, MouseButton};
+    let p = new(c).await;
+    let empty = super::url_static(port, "/empty.html");
+    p.goto_builder(&empty).goto().await.unwrap();
+    p.set_content_builder(&format!(
+        r#"<div id="menu" oncontextmenu="window.menu = true; return false">menu</div>
+        <a href="{}?ctrl-click">link</a>"#,
+        empty
+    ))
+    .set_content()
+    .await
+    .unwrap();
+    p.click_builder("#menu")
+        .button(MouseButton::Right)
+        .click()
+        .await
+        .unwrap();
+    assert!(p.eval::<bool>("() => window.menu === true").await.unwrap());
+    p.click_builder("a")
+        .modifiers(vec![KeyboardModifier::Control])
+        .click()
+        .await
+        .unwrap();
+    // other tests open pages in the same context concurrently, so look the tab up by its url
+    let mut tab = None;
+    for _ in 0..50 {
+        tab = c
+            .pages()
+            .unwrap()
+            .into_iter()
+            .find(|t| t.url().is_ok_and(|u| u.ends_with("?ctrl-click")));
+        if tab.is_some() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert_eq!(p.url().unwrap(), empty);
+    tab.expect("ctrl-click opens the link in a new tab")
+        .close(None)
+        .await
+        .unwrap();
+    close(&p).await;
+}
+
+async fn cdp_network_conditions(c: &BrowserContext, port: u16, which: Which) {
+    use playwright::api::NetworkConditions;
+    let p = new(c).await;
+    if which != Which::Chromium {
+        match p.new_cdp_session().await.err().as_deref() {
+            Some(playwright::Error::ChromiumOnly(_)) => {}
+            e => panic!("{:?}", e),
+        }
+        assert!(p.main_frame().new_cdp_session().await.is_err());
+        close(&p).await;
+        return;
+    }
+    let frame_session = p.main_frame().new_cdp_session().await.unwrap();
+    frame_session.detach().await.unwrap();
+    p.goto_builder(&super::url_static(port, "/empty.html"))
+        .goto()
+        .await
+        .unwrap();
+    let session = p.new_cdp_session().await.unwrap();
+    session.enable_network().await.unwrap();
+    let fetch = "() => fetch('/static/data.json').then(() => true, () => false)";
+    session
+        .emulate_network_conditions(NetworkConditions {
+            offline: true,
+            ..NetworkConditions::default()
+        })
+        .await
+        .unwrap();
+    assert!(!p.eval::<bool>(fetch).await.unwrap());
+    session
+        .emulate_network_conditions(NetworkConditions::default())
+        .await
+        .unwrap();
+    assert!(p.eval::<bool>(fetch).await.unwrap());
+    session.detach().await.unwrap();
+    close(&p).await;
+}
+
+async fn title_should_work(p: &Page) {
+    p.eval::<String>(r#"() => document.title = "foo""#)
+        .await
+        .unwrap();
+    assert_eq!(p.title().await.unwrap(), "foo");
+}
+
+async fn pause_without_inspector(c: &BrowserContext) {
+    if std::env::var_os("PWDEBUG").is_some() {
+        return;
+    }
+    let p = new(c).await;
+    p.pause().await.unwrap();
+    assert_eq!(p.eval::<i32>("() => 1 + 1").await.unwrap(), 2);
+    close(&p).await;
+}
+
+async fn pointer(c: &BrowserContext) {
+    let p = new(c).await;
+    p.set_content_builder(r#"<input type="checkbox" />"#)
+        .set_content()
+        .await
+        .unwrap();
+    let checked = || async {
         p.eval::<bool>("() => document.querySelector('input').checked")
             .await
             .unwrap()
@@ -515,6 +1316,242 @@ async fn pointer(c: &BrowserContext) {
     close(&p).await;
 }
 
+async fn mouse_wheel(c: &BrowserContext) {
+    let p = new(c).await;
+    p.set_content_builder(r#"<div style="height: 10000px"></div>"#)
+        .set_content()
+        .await
+        .unwrap();
+    p.mouse.r#move(50., 50., None).await.unwrap();
+    p.mouse.wheel(0., 500.).await.unwrap();
+    p.wait_for_function_builder("() => window.scrollY > 0")
+        .wait_for_function()
+        .await
+        .unwrap();
+    close(&p).await;
+}
+
+async fn wait_for_function(c: &BrowserContext) {
+    use playwright::api::{frame::Polling, page::WaitForFunctionOptions};
+    let p = new(c).await;
+    p.eval::<()>("() => { setTimeout(() => window.__ready = true, 100); }")
+        .await
+        .unwrap();
+    let options = WaitForFunctionOptions {
+        polling: Some(Polling::Millis(20)),
+        ..WaitForFunctionOptions::default()
+    };
+    let mut ready = p
+        .wait_for_function::<()>("() => window.__ready === true", None, options)
+        .await
+        .unwrap();
+    assert!(ready.json_value::<bool>().await.unwrap());
+    let mut sum = p
+        .main_frame()
+        .wait_for_function(
+            "([a, b]) => a + b",
+            Some((1, 2)),
+            WaitForFunctionOptions::default(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(sum.json_value::<i32>().await.unwrap(), 3);
+    let options = WaitForFunctionOptions {
+        timeout: Some(100.),
+        ..WaitForFunctionOptions::default()
+    };
+    let never = p.wait_for_function::<()>("() => false", None, options).await;
+    assert!(never.is_err());
+    close(&p).await;
+}
+
+async fn wait_for_selector_all(c: &BrowserContext) {
+    let p = new(c).await;
+    p.set_content_builder("<table id=t></table>")
+        .set_content()
+        .await
+        .unwrap();
+    p.eval::<()>(
+        "() => {
+            let n = 0;
+            const id = setInterval(() => {
+                document.getElementById('t').insertRow().insertCell().textContent = n;
+                if (++n === 12) clearInterval(id);
+            }, 20);
+        }",
+    )
+    .await
+    .unwrap();
+    let rows = p.wait_for_selector_all("#t tr", 10, None).await.unwrap();
+    assert!(rows.len() >= 10);
+    let err = p
+        .wait_for_selector_all("#t tr", 100, Some(500.))
+        .await
+        .unwrap_err();
+    assert!(matches!(*err, playwright::Error::Timeout));
+    close(&p).await;
+}
+
+async fn eval_with_timeout(c: &BrowserContext) {
+    let p = new(c).await;
+    let x: i32 = p.eval_with_timeout("() => 1 + 2", 1000.0).await.unwrap();
+    assert_eq!(x, 3);
+    let err = p
+        .eval_with_timeout::<()>("() => new Promise(() => {})", 1000.0)
+        .await
+        .unwrap_err();
+    assert!(matches!(*err, playwright::Error::Timeout));
+    close(&p).await;
+}
+
+async fn mouse_gesture(c: &BrowserContext) {
+    let p = new(c).await;
+    p.set_content_builder(
+        r#"<canvas width="200" height="200" style="position: absolute; top: 0; left: 0"></canvas>
+        <script>
+            const canvas = document.querySelector('canvas');
+            const ctx = canvas.getContext('2d');
+            let drawing = false;
+            canvas.addEventListener('mousedown', e => {
+                drawing = true;
+                ctx.beginPath();
+                ctx.moveTo(e.offsetX, e.offsetY);
+            });
+            canvas.addEventListener('mousemove', e => {
+                if (!drawing) return;
+                ctx.lineTo(e.offsetX, e.offsetY);
+                ctx.lineWidth = 4;
+                ctx.stroke();
+            });
+            canvas.addEventListener('mouseup', () => drawing = false);
+        </script>"#,
+    )
+    .set_content()
+    .await
+    .unwrap();
+    let painted = || async {
+        p.eval::<usize>(
+            "() => document.querySelector('canvas').getContext('2d')
+                .getImageData(0, 0, 200, 200).data.filter((v, i) => i % 4 == 3 && v > 0).length",
+        )
+        .await
+        .unwrap()
+    };
+    assert_eq!(painted().await, 0);
+    // moves without the button held do not draw
+    p.mouse
+        .gesture()
+        .move_to(150., 20.)
+        .move_to(150., 180.)
+        .perform()
+        .await
+        .unwrap();
+    assert_eq!(painted().await, 0);
+    p.mouse
+        .gesture()
+        .move_to(20., 20.)
+        .down(None)
+        .move_to_in_steps(180., 180., 10)
+        .pause(10.)
+        .move_to(20., 180.)
+        .up(None)
+        .delay(5.)
+        .perform()
+        .await
+        .unwrap();
+    assert!(painted().await > 0);
+    close(&p).await;
+}
+
+async fn touchscreen_tap(c: &BrowserContext) {
+    use playwright::api::Position;
+    let p = new(c).await;
+    p.set_content_builder(
+        r#"<div style="position: absolute; top: 0; left: 0; width: 300px; height: 300px"></div>
+        <script>
+            window.touches = [];
+            document.querySelector('div').addEventListener('touchstart', e => {
+                const t = e.touches[0];
+                window.touches.push([t.clientX, t.clientY]);
+            });
+        </script>"#,
+    )
+    .set_content()
+    .await
+    .unwrap();
+    p.touch_screen.tap(120., 80.).await.unwrap();
+    let card = p.locator("div");
+    card.tap(Some(Position { x: 290., y: 10. })).await.unwrap();
+    card.tap(None).await.unwrap();
+    let touches: Vec<(f64, f64)> = p.eval("() => window.touches").await.unwrap();
+    assert_eq!(touches, vec![(120., 80.), (290., 10.), (150., 150.)]);
+    close(&p).await;
+}
+
+async fn drag_and_drop(c: &BrowserContext) {
+    let p = new(c).await;
+    p.set_content_builder(
+        r#"<div id="source" draggable="true" style="width: 100px; height: 100px">source</div>
+        <div id="target" style="width: 100px; height: 100px">target</div>
+        <script>
+          const target = document.querySelector('#target');
+          target.addEventListener('dragover', e => e.preventDefault());
+          target.addEventListener('drop', () => window.dropped = true);
+        </script>"#,
+    )
+    .set_content()
+    .await
+    .unwrap();
+    p.drag_and_drop("#source", "#target", DragOptions::default())
+        .await
+        .unwrap();
+    let dropped: bool = p.eval("() => window.dropped === true").await.unwrap();
+    assert!(dropped);
+    close(&p).await;
+}
+
+async fn frame_actions(c: &BrowserContext) {
+    let p = new(c).await;
+    let f = p.main_frame();
+    f.set_content_builder(
+        r#"<div id="source" draggable="true" style="width: 100px; height: 100px">source</div>
+        <div id="target" style="width: 100px; height: 100px">target</div>
+        <input id="cb" type="checkbox">
+        <button id="hidden" style="display: none" onclick="window.clicks = (window.clicks || 0) + 1">x</button>
+        <script>
+          const target = document.querySelector('#target');
+          target.addEventListener('dragover', e => e.preventDefault());
+          target.addEventListener('drop', () => window.dropped = true);
+        </script>"#,
+    )
+    .set_content()
+    .await
+    .unwrap();
+    f.drag_and_drop("#source", "#target", DragOptions::default())
+        .await
+        .unwrap();
+    let dropped: bool = f.eval("() => window.dropped === true").await.unwrap();
+    assert!(dropped);
+
+    f.set_checked_builder("#cb", true).set_checked().await.unwrap();
+    assert!(f.is_checked("#cb", None).await.unwrap());
+    let cb = f.locator("#cb");
+    cb.set_checked_builder(false).set_checked().await.unwrap();
+    assert!(!cb.is_checked(None).await.unwrap());
+
+    // a hidden button can't be clicked, but a synthetic event skips the actionability checks
+    let clicked = f.click_builder("#hidden").timeout(300.).click().await;
+    assert!(clicked.is_err());
+    f.dispatch_event::<()>("#hidden", "click", None).await.unwrap();
+    f.locator("#hidden")
+        .dispatch_event("click", Some(serde_json::json!({ "bubbles": true })))
+        .await
+        .unwrap();
+    let clicks: i32 = f.eval("() => window.clicks").await.unwrap();
+    assert_eq!(clicks, 2);
+    close(&p).await;
+}
+
 async fn new(c: &BrowserContext) -> Page {
     let page = c.new_page().await.unwrap();
     set_timeout(&page).await;
@@ -543,6 +1580,65 @@ async fn input(c: &BrowserContext) {
     close(&p).await;
 }
 
+async fn keyboard_insert_text(c: &BrowserContext) {
+    let p = new(c).await;
+    done!(p
+        .set_content_builder(
+            r#"<input type="text" value="" />
+            <script>
+              window.keys = [];
+              document.querySelector('input').addEventListener('keydown', e => keys.push(e.key));
+            </script>"#
+        )
+        .set_content());
+    done!(p.focus("input", None));
+    done!(p.keyboard.insert_text("😀嗨"));
+    let value: String = done!(p.eval("() => document.querySelector('input').value"));
+    assert_eq!(value, "😀嗨");
+    let keys: Vec<String> = done!(p.eval("() => window.keys"));
+    assert!(keys.is_empty());
+    done!(p.keyboard.r#type("ab", None));
+    let keys: Vec<String> = done!(p.eval("() => window.keys"));
+    assert_eq!(keys, vec!["a", "b"]);
+    close(&p).await;
+}
+
+async fn keyboard_hold_shift(c: &BrowserContext) {
+    let p = new(c).await;
+    done!(p
+        .set_content_builder(
+            r#"<ul><li>0</li><li>1</li><li>2</li><li>3</li><li>4</li></ul>
+            <input type="text" />
+            <script>
+              const items = [...document.querySelectorAll('li')];
+              let anchor = 0;
+              window.selected = [];
+              items.forEach((li, i) => li.addEventListener('click', e => {
+                if (!e.shiftKey) anchor = i;
+                const [from, to] = [Math.min(anchor, i), Math.max(anchor, i)];
+                window.selected = items.map((_, j) => j).filter(j => j >= from && j <= to);
+              }));
+              document.querySelector('input').addEventListener('keydown', () => window.down = Date.now());
+              document.querySelector('input').addEventListener('keyup', () => window.held = Date.now() - window.down);
+            </script>"#
+        )
+        .set_content());
+    done!(p.click_builder("li:nth-child(2)").click());
+    done!(p.keyboard.down("Shift"));
+    done!(p.click_builder("li:nth-child(4)").click());
+    done!(p.keyboard.up("Shift"));
+    let selected: Vec<i32> = done!(p.eval("() => window.selected"));
+    assert_eq!(selected, vec![1, 2, 3]);
+    done!(p.click_builder("li:nth-child(5)").click());
+    let selected: Vec<i32> = done!(p.eval("() => window.selected"));
+    assert_eq!(selected, vec![4]);
+
+    done!(p.press_builder("input", "a").delay(100.).press());
+    let held: f64 = done!(p.eval("() => window.held"));
+    assert!(held >= 90., "{}", held);
+    close(&p).await;
+}
+
 async fn context_pages_visibility(c: &BrowserContext) {
     let page = new(c).await;
     let pages = c.pages().unwrap();
@@ -793,6 +1889,40 @@ async fn opener_should_work(c: &BrowserContext) {
     close(&page).await;
 }
 
+async fn wait_for_typed_event(c: &BrowserContext, port: u16) {
+    let page = new(c).await;
+    let url = super::url_static(port, "/empty.html");
+    let (response, _) = tokio::join!(
+        page.wait_for::<page::ResponseEvent>(),
+        page.goto_builder(&url).goto()
+    );
+    assert_eq!(response.unwrap().url().unwrap(), url);
+    let (loaded, _) = tokio::join!(
+        page.wait_for::<page::LoadEvent>(),
+        page.reload_builder().reload()
+    );
+    loaded.unwrap();
+    let (popup, _) = tokio::join!(
+        page.wait_for::<page::PopupEvent>(),
+        page.eval::<()>("() => window.open('about:blank')")
+    );
+    let popup = popup.unwrap();
+    assert_eq!(popup.opener().await.unwrap().as_ref(), Some(&page));
+    close(&popup).await;
+    close(&page).await;
+}
+
+async fn expect_popup_should_work(c: &BrowserContext) {
+    let page = new(c).await;
+    let popup = page
+        .expect_popup(|| page.eval::<()>("() => window.open('about:blank')"))
+        .await
+        .unwrap();
+    assert_eq!(popup.opener().await.unwrap().as_ref(), Some(&page));
+    close(&popup).await;
+    close(&page).await;
+}
+
 async fn opener_should_be_null_after_parent_close(c: &BrowserContext) {
     let page = new(c).await;
     let (popup_evt, _) = tokio::join!(
@@ -841,6 +1971,450 @@ async fn set_extra_http_headers(c: &BrowserContext, port: u16) {
     close(&p).await;
 }
 
+async fn add_extra_http_headers(c: &BrowserContext, port: u16) {
+    let p = new(c).await;
+    let url = super::url_static(port, "/empty.html");
+    let request_headers = || async {
+        let (request, _) = tokio::join!(
+            p.wait_for_request("**/empty.html".into(), None),
+            p.goto_builder(&url).goto()
+        );
+        request.unwrap().headers().unwrap()
+    };
+    p.set_extra_http_headers(vec![("hoge".into(), "hoge".into())])
+        .await
+        .unwrap();
+    p.add_extra_http_headers(vec![
+        ("piyo".into(), "piyo".into()),
+        ("HOGE".into(), "fuga".into()),
+    ])
+    .await
+    .unwrap();
+    let headers = request_headers().await;
+    assert_eq!(headers.get("foo").unwrap(), "bar"); // set by BrowserContext
+    assert_eq!(headers.get("hoge").unwrap(), "fuga");
+    assert_eq!(headers.get("piyo").unwrap(), "piyo");
+    p.set_extra_http_headers(vec![("bar".into(), "bar".into())])
+        .await
+        .unwrap();
+    let headers = request_headers().await;
+    assert_eq!(headers.get("foo").unwrap(), "bar");
+    assert_eq!(headers.get("bar").unwrap(), "bar");
+    assert!(headers.get("hoge").is_none());
+    assert!(headers.get("piyo").is_none());
+    close(&p).await;
+}
+
+async fn extra_http_headers_keep_order(c: &BrowserContext, port: u16) {
+    let p = new(c).await;
+    p.set_extra_http_headers(vec![
+        ("x-b".into(), "1".into()),
+        ("x-a".into(), "2".into()),
+        ("x-c".into(), "3".into()),
+    ])
+    .await
+    .unwrap();
+    let url = super::url_static(port, "/empty.html");
+    let (request, _) = tokio::join!(
+        p.wait_for_request("**/empty.html".into(), None),
+        p.goto_builder(&url).goto()
+    );
+    let names: Vec<String> = request
+        .unwrap()
+        .headers_array()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|h| h.name.to_ascii_lowercase())
+        .filter(|n| n.starts_with("x-"))
+        .collect();
+    assert_eq!(names, vec!["x-b", "x-a", "x-c"]);
+    close(&p).await;
+}
+
+async fn response_headers(c: &BrowserContext, port: u16) {
+    let p = new(c).await;
+    let url = super::url_static(port, "/empty.html");
+    let response = p.goto_builder(&url).goto().await.unwrap().unwrap();
+    let headers = response.headers().unwrap();
+    assert!(headers["content-type"].starts_with("text/html"));
+    let value = response.header_value("Content-Type").await.unwrap();
+    assert_eq!(value.as_deref(), Some(&*headers["content-type"]));
+    let array = response.headers_array().await.unwrap();
+    assert!(array
+        .iter()
+        .any(|h| h.name.eq_ignore_ascii_case("content-type")));
+    assert_eq!(response.header_value("x-not-set").await.unwrap(), None);
+    close(&p).await;
+}
+
+async fn wait_for_response_should_work(c: &BrowserContext, port: u16) {
+    let p = new(c).await;
+    let url = super::url_static(port, "/empty.html");
+    let (response, _) = tokio::join!(
+        p.wait_for_response("**/empty.html".into(), None),
+        p.goto_builder(&url).goto()
+    );
+    assert_eq!(response.unwrap().url().unwrap(), url);
+    let (response, _) = tokio::join!(
+        p.wait_for_response(
+            page::UrlOrPredicate::Predicate(Box::new(|r| r.status().unwrap() == 200)),
+            None
+        ),
+        p.reload_builder().reload()
+    );
+    assert!(response.unwrap().ok().unwrap());
+    let response = p
+        .wait_for_response("**/never".into(), Some(100.))
+        .await;
+    assert!(response.is_err());
+    close(&p).await;
+}
+
+async fn response_from_service_worker(c: &BrowserContext, port: u16, which: Which) {
+    if which != Which::Chromium {
+        return;
+    }
+    // The worker keeps controlling its scope, so keep it out of the shared context.
+    let browser = c.browser().unwrap().unwrap();
+    let c = browser.context_builder().build().await.unwrap();
+    let p = new(&c).await;
+    let url = super::url_static(port, "/sw.html");
+    let (response, _) = tokio::join!(
+        p.wait_for_response("**/sw.html".into(), None),
+        p.goto_builder(&url).goto()
+    );
+    assert!(!response.unwrap().from_service_worker().unwrap());
+    p.wait_for_function_builder("() => !!navigator.serviceWorker.controller")
+        .wait_for_function()
+        .await
+        .unwrap();
+    let (response, text) = tokio::join!(
+        p.wait_for_response("**/sw-data".into(), None),
+        p.eval::<String>("() => fetch('/static/sw-data').then(r => r.text())")
+    );
+    assert_eq!(text.unwrap(), "from service worker");
+    assert!(response.unwrap().from_service_worker().unwrap());
+    c.close().await.unwrap();
+}
+
+async fn block_resources(c: &BrowserContext, port: u16) {
+    use playwright::api::ResourceType;
+    let p = new(c).await;
+    let url = super::url_static(port, "/empty.html");
+    p.goto_builder(&url).goto().await.unwrap();
+    let load_image = || {
+        p.eval::<String>(
+            "() => new Promise(resolve => {
+                const img = new Image();
+                img.onload = () => resolve('loaded');
+                img.onerror = () => resolve('blocked');
+                img.src = '/static/pixel.png?' + Math.random();
+            })",
+        )
+    };
+    assert_eq!(load_image().await.unwrap(), "loaded");
+    p.block_resources(&[ResourceType::Image, ResourceType::Font])
+        .await
+        .unwrap();
+    assert_eq!(load_image().await.unwrap(), "blocked");
+    let fetched: String = p
+        .eval("() => fetch('/static/empty.html').then(r => r.statusText)")
+        .await
+        .unwrap();
+    assert_eq!(fetched, "OK");
+    close(&p).await;
+}
+
+async fn wait_for_request_should_work(c: &BrowserContext, port: u16) {
+    let p1 = new(c).await;
+    let p2 = new(c).await;
+    let url = super::url_static(port, "/empty2.html");
+    let (request, _) = tokio::join!(
+        p1.wait_for_request("**/empty2.html".into(), None),
+        p1.goto_builder(&url).goto()
+    );
+    let request = request.unwrap();
+    assert_eq!(request.url().unwrap(), url);
+    assert_eq!(request.method().unwrap(), "GET");
+    let (request, _) = tokio::join!(
+        p1.wait_for_request("**/empty2.html".into(), Some(1000.)),
+        p2.goto_builder(&url).goto()
+    );
+    assert!(request.is_err());
+    close(&p1).await;
+    close(&p2).await;
+}
+
+async fn request_response_should_work(c: &BrowserContext, port: u16) {
+    let p = new(c).await;
+    let url = super::url_static(port, "/empty.html");
+    let (request, _) = tokio::join!(
+        p.expect_event(page::EventType::Request),
+        p.goto_builder(&url).goto()
+    );
+    let request = match request.unwrap() {
+        page::Event::Request(r) => r,
+        _ => unreachable!(),
+    };
+    let response = request.response().await.unwrap().unwrap();
+    assert_eq!(response.status().unwrap(), 200);
+    assert!(response.request() == request);
+    close(&p).await;
+}
+
+async fn expect_request_finished_should_work(c: &BrowserContext, port: u16) {
+    let p = new(c).await;
+    p.goto_builder(&super::url_static(port, "/empty.html"))
+        .goto()
+        .await
+        .unwrap();
+    let (request, _) = tokio::join!(
+        p.expect_request_finished("**/data.json".into(), None),
+        p.eval::<()>("async () => { await fetch('/static/data.json'); }")
+    );
+    let response = request.unwrap().response().await.unwrap().unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&response.body().await.unwrap()).unwrap();
+    assert_eq!(body, serde_json::json!({"name": "playwright", "version": 1}));
+    close(&p).await;
+}
+
+async fn capture_json_responses(c: &BrowserContext, port: u16) {
+    let p = new(c).await;
+    p.goto_builder(&super::url_static(port, "/empty.html"))
+        .goto()
+        .await
+        .unwrap();
+    let mut rx = p.capture_json_responses("**/*.json".into()).unwrap();
+    p.eval::<()>(
+        "async () => { await fetch('/static/empty2.html'); await fetch('/static/data.json'); }",
+    )
+    .await
+    .unwrap();
+    let (url, json) = rx.next().await.unwrap();
+    assert_eq!(url, super::url_static(port, "/data.json"));
+    assert_eq!(json, serde_json::json!({"name": "playwright", "version": 1}));
+    close(&p).await;
+    assert!(rx.next().await.is_none());
+}
+
+async fn request_failure_should_be_classified(c: &BrowserContext, port: u16) {
+    let p = new(c).await;
+    p.route("**/empty.html", |route| async move {
+        route.abort(None).await.unwrap();
+    })
+    .await
+    .unwrap();
+    let url = super::url_static(port, "/empty.html");
+    let (evt, _) = tokio::join!(
+        p.expect_event(page::EventType::RequestFailed),
+        p.goto_builder(&url).goto()
+    );
+    let failure = match evt.unwrap() {
+        page::Event::RequestFailed(r) => r.failure().unwrap().unwrap(),
+        _ => unreachable!(),
+    };
+    assert_eq!(failure.error_code, RequestErrorCode::Aborted);
+    let (evt, _) = tokio::join!(
+        p.expect_event(page::EventType::RequestFailed),
+        p.goto_builder("http://localhost:1/").goto()
+    );
+    let failure = match evt.unwrap() {
+        page::Event::RequestFailed(r) => r.failure().unwrap().unwrap(),
+        _ => unreachable!(),
+    };
+    assert_ne!(failure.error_code, RequestErrorCode::Aborted);
+    assert!(!failure.error_text.is_empty());
+    close(&p).await;
+}
+
+async fn expose_function_survives_navigation(c: &BrowserContext, port: u16) {
+    let p = new(c).await;
+    p.expose_function("add", |(a, b): (i32, i32)| async move { a + b })
+        .await
+        .unwrap();
+    let sum: i32 = p.eval("() => window.add(1, 2)").await.unwrap();
+    assert_eq!(sum, 3);
+    let url = super::url_static(port, "/empty.html");
+    p.goto_builder(&url).goto().await.unwrap();
+    let sum: i32 = p.eval("() => window.add(3, 4)").await.unwrap();
+    assert_eq!(sum, 7);
+    p.reload_builder().reload().await.unwrap();
+    let sum: i32 = p.eval("() => window.add(5, 6)").await.unwrap();
+    assert_eq!(sum, 11);
+    close(&p).await;
+}
+
+async fn expect_navigation(c: &BrowserContext, port: u16) {
+    use page::WaitForNavigationOptions;
+    let p = new(c).await;
+    let url = super::url_static(port, "/empty.html");
+    p.set_content_builder(&format!(r#"<a href="{}">link</a>"#, url))
+        .set_content()
+        .await
+        .unwrap();
+    let link = p.locator("a");
+    let response = p
+        .expect_navigation(|| link.click_builder().click(), WaitForNavigationOptions::default())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(response.url().unwrap(), url);
+    assert_eq!(response.status().unwrap(), 200);
+    assert_eq!(p.url().unwrap(), url);
+    let response = p
+        .expect_navigation(
+            || p.eval::<()>("() => history.pushState({}, '', '#foo')"),
+            WaitForNavigationOptions {
+                wait_until: Some(DocumentLoadState::DomContentLoaded),
+                ..WaitForNavigationOptions::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert!(response.is_none());
+    assert_eq!(p.url().unwrap(), format!("{}#foo", url));
+    let boom = "() => new Promise((_, no) => setTimeout(() => no(new Error('boom')), 500))";
+    let err = p
+        .expect_navigation(
+            || p.eval::<()>(boom),
+            WaitForNavigationOptions {
+                timeout: Some(100.0),
+                ..WaitForNavigationOptions::default()
+            },
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(*err, playwright::Error::ErrorResponded(_)), "{:?}", err);
+    close(&p).await;
+}
+
+async fn on_console(c: &BrowserContext) {
+    use std::sync::{Arc, Mutex};
+    let p = new(c).await;
+    let logged = Arc::new(Mutex::new(Vec::new()));
+    let sink = logged.clone();
+    let subscription = p
+        .on(page::EventType::Console, move |e| {
+            if let page::Event::Console(msg) = e {
+                sink.lock().unwrap().push(msg.text().unwrap());
+            }
+        })
+        .unwrap();
+    p.eval::<()>("() => console.log('first')").await.unwrap();
+    for _ in 0..50 {
+        if !logged.lock().unwrap().is_empty() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    subscription.unsubscribe();
+    p.eval::<()>("() => console.log('second')").await.unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(*logged.lock().unwrap(), vec!["first".to_owned()]);
+    close(&p).await;
+}
+
+async fn console_message_args(c: &BrowserContext, port: u16) {
+    let p = new(c).await;
+    p.goto_builder(&super::url_static(port, "/empty.html"))
+        .goto()
+        .await
+        .unwrap();
+    let (evt, _) = tokio::join!(
+        p.expect_event(page::EventType::Console),
+        p.eval::<()>("() => console.warn('hello', 5, {foo: 'bar'})")
+    );
+    let msg = match evt.unwrap() {
+        page::Event::Console(msg) => msg,
+        _ => unreachable!(),
+    };
+    assert_eq!(msg.r#type().unwrap(), "warning");
+    assert!(msg.text().unwrap().starts_with("hello 5"));
+    let mut args = msg.args().unwrap();
+    assert_eq!(args.len(), 3);
+    assert_eq!(args[1].json_value::<i32>().await.unwrap(), 5);
+    let mut foo = args[2].get_property("foo").await.unwrap();
+    assert_eq!(foo.json_value::<String>().await.unwrap(), "bar");
+    let location = msg.location().unwrap();
+    assert!(location.line_number >= 0);
+    assert!(location.column_number >= 0);
+    close(&p).await;
+}
+
+async fn expect_console_message(c: &BrowserContext) {
+    let p = new(c).await;
+    let other = new(c).await;
+    let (msg, _, _) = tokio::join!(
+        p.expect_console_message(|m| m.r#type().is_ok_and(|t| t == "error"), None),
+        other.eval::<()>("() => console.error('other')"),
+        p.eval::<()>("() => { console.log('noise'); console.error('boom'); }")
+    );
+    assert_eq!(msg.unwrap().text().unwrap(), "boom");
+    let err = p
+        .expect_console_message(|m| m.text().is_ok_and(|t| t == "never"), Some(100.0))
+        .await
+        .unwrap_err();
+    assert!(matches!(*err, playwright::Error::Timeout));
+    close(&other).await;
+    close(&p).await;
+}
+
+async fn remove_exposed_function(c: &BrowserContext) {
+    let p = new(c).await;
+    let is_defined = || async {
+        p.eval::<bool>("() => typeof window.foo !== 'undefined'")
+            .await
+            .unwrap()
+    };
+    p.expose_function("foo", |_: Vec<i32>| async move { 42 })
+        .await
+        .unwrap();
+    assert!(is_defined().await);
+    p.remove_exposed_function("foo").await.unwrap();
+    assert!(!is_defined().await);
+    p.reload_builder().reload().await.unwrap();
+    assert!(!is_defined().await);
+    p.expose_function("foo", |_: Vec<i32>| async move { 43 })
+        .await
+        .unwrap();
+    p.reload_builder().reload().await.unwrap();
+    assert!(is_defined().await);
+    let result: i32 = p.eval("() => window.foo()").await.unwrap();
+    assert_eq!(result, 43);
+    close(&p).await;
+}
+
+async fn evaluate_handle(c: &BrowserContext) {
+    let p = new(c).await;
+    let mut window = p.evaluate_handle::<()>("() => window", None).await.unwrap();
+    p.eval::<()>("() => { window.__counter = 1; }").await.unwrap();
+    let mut counter = window.get_property("__counter").await.unwrap();
+    assert_eq!(counter.json_value::<i32>().await.unwrap(), 1);
+    let mut point = p
+        .main_frame()
+        .evaluate_handle("([x, y]) => ({ x, y, sum: x + y })", Some((2, 3)))
+        .await
+        .unwrap();
+    let mut sum = point.get_property("sum").await.unwrap();
+    assert_eq!(sum.json_value::<i32>().await.unwrap(), 5);
+    let mut properties = point.get_properties().await.unwrap();
+    let mut keys: Vec<_> = properties.keys().cloned().collect();
+    keys.sort();
+    assert_eq!(keys, vec!["sum", "x", "y"]);
+    let mut x = properties.remove("x").unwrap();
+    x.dispose().await.unwrap();
+    let y = properties.get_mut("y").unwrap();
+    assert_eq!(y.json_value::<i32>().await.unwrap(), 3);
+    point.dispose().await.unwrap();
+    window.dispose().await.unwrap();
+    let mut body = p.evaluate_handle::<()>("() => document.body", None).await.unwrap();
+    let mut tag = body.get_property("tagName").await.unwrap();
+    assert_eq!(tag.json_value::<String>().await.unwrap(), "BODY");
+    body.dispose().await.unwrap();
+    close(&p).await;
+}
+
 async fn query_selector_and_eval(c: &BrowserContext) {
     let p = new(c).await;
     p.set_content_builder(r#"<div><h1>foo</h1><div class="foo">bar</div></div>"#)
@@ -898,38 +2472,133 @@ async fn query_selector_and_eval(c: &BrowserContext) {
     close(&p).await;
 }
 
-// async fn file_chooser(c: &BrowserContext, port: u16) {
-//    let p = new(c).await;
-//    let url = super::url_static(port, "/form.html");
-//    p.goto_builder(&url).goto().await.unwrap();
-//    let (maybe_file_chooser, _) = tokio::join!(
-//        p.expect_event(page::EventType::FileChooser),
-//        p.click_builder("input[type=file]").click()
-//    );
-//    let file_chooser = match maybe_file_chooser.unwrap() {
-//        page::Event::FileChooser(file_chooser) => file_chooser,
-//        _ => unreachable!()
-//    };
-//    assert_eq!(file_chooser.page(), p);
-//    assert!(file_chooser.is_multiple());
-//    assert_eq!(
-//        file_chooser.element(),
-//        p.query_selector("input[type=file]").await.unwrap().unwrap()
-//    );
-//    file_chooser
-//        .set_input_files_builder(File {
-//            name: "a".into(),
-//            mime: "text/plain".into(),
-//            buffer: "a\n".into()
-//        })
-//        .add_file(File {
-//            name: "b".into(),
-//            mime: "text/plain".into(),
-//            buffer: "b\n".into()
-//        })
-//        .set_input_files()
-//        .await
-//        .unwrap();
-//    tokio::time::sleep(std::time::Duration::from_secs(5)).await;
-//    close(&p).await;
-//}
+async fn set_input_files(c: &BrowserContext, port: u16) {
+    let p = new(c).await;
+    let url = super::url_static(port, "/form.html");
+    p.goto_builder(&url).goto().await.unwrap();
+    let input = p.query_selector("input[type=file]").await.unwrap().unwrap();
+    let names = || async {
+        p.eval::<Vec<String>>(
+            "() => Array.from(document.querySelector('input').files).map(f => f.name)",
+        )
+        .await
+        .unwrap()
+    };
+    let path = super::temp_dir().join("upload.txt");
+    std::fs::write(&path, "hello\n").unwrap();
+    input.set_input_files(vec![path.into()], None).await.unwrap();
+    assert_eq!(names().await, vec!["upload.txt"]);
+    let text: String = p
+        .eval("() => document.querySelector('input').files[0].text()")
+        .await
+        .unwrap();
+    assert_eq!(text, "hello\n");
+    input
+        .set_input_files(
+            vec![File::new("memory.txt".into(), "text/plain".into(), b"a").into()],
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(names().await, vec!["memory.txt"]);
+    input.set_input_files(vec![], None).await.unwrap();
+    assert!(names().await.is_empty());
+    close(&p).await;
+}
+
+async fn page_set_input_files(c: &BrowserContext, port: u16) {
+    let p = new(c).await;
+    let url = super::url_static(port, "/form.html");
+    p.goto_builder(&url).goto().await.unwrap();
+    let names = || async {
+        p.eval::<Vec<String>>(
+            "() => Array.from(document.querySelector('input').files).map(f => f.name)",
+        )
+        .await
+        .unwrap()
+    };
+    p.set_input_files_builder(
+        "input[type=file]",
+        File::new("a.txt".into(), "text/plain".into(), b"a"),
+    )
+    .add_file(File::new("b.txt".into(), "text/plain".into(), b"b"))
+    .timeout(3000.)
+    .set_input_files()
+    .await
+    .unwrap();
+    assert_eq!(names().await, vec!["a.txt", "b.txt"]);
+    p.set_input_files_builder(
+        "input[type=file]",
+        File::new("a.txt".into(), "text/plain".into(), b"a"),
+    )
+    .clear_files()
+    .set_input_files()
+    .await
+    .unwrap();
+    assert!(names().await.is_empty());
+    let path = super::temp_dir().join("page_upload.txt");
+    std::fs::write(&path, "hello").unwrap();
+    p.set_input_files("input[type=file]", vec![path.into()], None)
+        .await
+        .unwrap();
+    assert_eq!(names().await, vec!["page_upload.txt"]);
+    let input = p.locator("input[type=file]");
+    let a = super::temp_dir().join("locator_a.txt");
+    let b = super::temp_dir().join("locator_b.txt");
+    std::fs::write(&a, "a").unwrap();
+    std::fs::write(&b, "b").unwrap();
+    input
+        .set_input_paths_builder(&a)
+        .add_path(&b)
+        .set_input_files()
+        .await
+        .unwrap();
+    assert_eq!(names().await, vec!["locator_a.txt", "locator_b.txt"]);
+    let mixed = input
+        .set_input_paths_builder(&a)
+        .add_file(File::new("c.txt".into(), "text/plain".into(), b"c"))
+        .set_input_files()
+        .await;
+    assert!(mixed.is_err());
+    input
+        .set_input_paths_builder(&a)
+        .clear()
+        .set_input_files()
+        .await
+        .unwrap();
+    assert!(names().await.is_empty());
+    close(&p).await;
+}
+
+async fn file_chooser(c: &BrowserContext, port: u16) {
+    let p = new(c).await;
+    let url = super::url_static(port, "/form.html");
+    p.goto_builder(&url).goto().await.unwrap();
+    let file_chooser = p
+        .expect_file_chooser(|| p.click_builder("input[type=file]").click())
+        .await
+        .unwrap();
+    assert_eq!(file_chooser.page(), p);
+    assert!(file_chooser.is_multiple());
+    assert_eq!(
+        file_chooser.element(),
+        p.query_selector("input[type=file]").await.unwrap().unwrap()
+    );
+    file_chooser
+        .set_input_files_builder(File::new("a.txt".into(), "text/plain".into(), b"a\n"))
+        .add_file(File::new("b.txt".into(), "text/plain".into(), b"b\n"))
+        .set_input_files()
+        .await
+        .unwrap();
+    let names: Vec<String> = p
+        .eval("() => Array.from(document.querySelector('input').files).map(f => f.name)")
+        .await
+        .unwrap();
+    assert_eq!(names, vec!["a.txt", "b.txt"]);
+    let (evt, _) = tokio::join!(
+        p.expect_event(page::EventType::FileChooser),
+        p.click_builder("input[type=file]").click()
+    );
+    assert!(matches!(evt.unwrap(), page::Event::FileChooser(_)));
+    close(&p).await;
+}
@@ -1,10 +1,11 @@
 use super::Which;
-use playwright::api::{Playwright, Selectors};
+use playwright::api::{selectors::RegisterOptions, Playwright, Selectors};
 
 pub async fn all(playwright: &Playwright, which: Which) {
     let selectors = playwright.selectors();
 
     register_should_work(playwright, &selectors, which).await;
+    set_test_id_attribute_should_work(playwright, &selectors, which).await;
 }
 
 async fn register_should_work(playwright: &Playwright, selectors: &Selectors, which: Which) {
@@ -19,7 +20,18 @@ async fn register_should_work(playwright: &Playwright, selectors: &Selectors, wh
           return Array.from(root.querySelectorAll(selector));
         }
       })";
-    selectors.register("tag", snip, false).await.unwrap();
+    selectors
+        .register("tag", snip, RegisterOptions::default())
+        .await
+        .unwrap();
+    let err = selectors
+        .register("tag", snip, RegisterOptions::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        &*err,
+        playwright::Error::SelectorEngineAlreadyRegistered(name) if name == "tag"
+    ));
     let t = match which {
         Which::Webkit => playwright.webkit(),
         Which::Firefox => playwright.firefox(),
@@ -38,3 +50,36 @@ async fn register_should_work(playwright: &Playwright, selectors: &Selectors, wh
         .await
         .unwrap();
 }
+
+async fn set_test_id_attribute_should_work(
+    playwright: &Playwright,
+    selectors: &Selectors,
+    which: Which,
+) {
+    selectors.set_test_id_attribute("data-cy").await.unwrap();
+    let t = match which {
+        Which::Webkit => playwright.webkit(),
+        Which::Firefox => playwright.firefox(),
+        Which::Chromium => playwright.chromium(),
+    };
+    let browser = t.launcher().launch().await.unwrap();
+    let bc = browser.context_builder().build().await.unwrap();
+    let page = bc.new_page().await.unwrap();
+    page.set_content_builder(
+        r#"<button data-testid="go">a</button><button data-cy="go">b</button>"#,
+    )
+    .set_content()
+    .await
+    .unwrap();
+    let button = page.get_by_test_id("go");
+    assert_eq!(button.count().await.unwrap(), 1);
+    assert_eq!(button.inner_text(None).await.unwrap(), "b");
+    selectors
+        .set_test_id_attribute("data-testid")
+        .await
+        .unwrap();
+    assert_eq!(
+        page.get_by_test_id("go").inner_text(None).await.unwrap(),
+        "a"
+    );
+}
@@ -0,0 +1,70 @@
+use playwright::Playwright;
+
+playwright::runtime_test!(shadow_dom, {
+    run().await.unwrap();
+});
+
+async fn run() -> Result<(), playwright::Error> {
+    let playwright = match Playwright::initialize().await {
+        Ok(p) => p,
+        Err(playwright::Error::Timeout) => {
+            eprintln!("Playwright driver initialization timed out; skipping shadow dom test.");
+            return Ok(());
+        }
+        Err(e) => return Err(e),
+    };
+    if let Err(e) = playwright.prepare() {
+        eprintln!("Playwright prepare failed ({e:?}); skipping shadow dom test.");
+        return Ok(());
+    }
+
+    let chromium = playwright.chromium();
+    let browser = chromium.launcher().headless(true).launch().await?;
+    let context = browser.context_builder().build().await?;
+    let page = context.new_page().await?;
+
+    page.set_content_builder(
+        r#"<my-widget></my-widget>
+        <closed-widget></closed-widget>
+        <script>
+          customElements.define('my-widget', class extends HTMLElement {
+            constructor() {
+              super();
+              const root = this.attachShadow({ mode: 'open' });
+              root.innerHTML = '<button onclick="window.clicked = true">Shadow</button>';
+            }
+          });
+          customElements.define('closed-widget', class extends HTMLElement {
+            constructor() {
+              super();
+              const root = this.attachShadow({ mode: 'closed' });
+              root.innerHTML = '<button>Closed</button>';
+            }
+          });
+        </script>"#,
+    )
+    .set_content()
+    .await?;
+
+    // Open shadow roots are pierced by default
+    assert_eq!(page.locator("my-widget button").count().await?, 1);
+
+    let button = page.locator("my-widget").locator("button");
+    assert_eq!(button.inner_text(Some(5_000.0)).await?, "Shadow");
+    button.click_builder().click().await?;
+    let clicked: bool = page.eval("() => window.clicked === true").await?;
+    assert!(clicked);
+
+    // Closed shadow roots are not
+    assert_eq!(
+        page.locator("closed-widget")
+            .locator("button")
+            .count()
+            .await?,
+        0
+    );
+
+    context.close().await.ok();
+    browser.close().await.ok();
+    Ok(())
+}
@@ -15,7 +15,13 @@ use tokio::task::spawn;
 use tokio::task::spawn;
 
 use playwright::Playwright;
-use std::path::PathBuf;
+use std::{
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Which {
@@ -40,6 +46,8 @@ playwright::runtime_test!(
     connect_over_cdp,
     connect::connect_over_cdp(Which::Chromium).await
 );
+#[cfg(feature = "tungstenite")]
+playwright::runtime_test!(connect, connect::connect(Which::Chromium).await);
 
 async fn page(which: Which) {
     std::fs::create_dir_all(temp_dir()).unwrap();
@@ -85,6 +93,9 @@ async fn playwright_with_driver() -> Playwright {
     playwright
 }
 
+/// `/flaky` answers 503 this many times before succeeding.
+const FLAKY_FAILURES: usize = 2;
+
 #[cfg(any(feature = "rt-tokio", feature = "rt-actix"))]
 async fn start_test_server(port: u16) {
     use warp::{
@@ -107,7 +118,21 @@ async fn start_test_server(port: u16) {
     let download = warp::path("download")
         .and(warp::fs::dir("tests/server"))
         .with(warp::reply::with::headers(headers));
-    let route = r#static.or(download);
+    let options = warp::path("options").and(warp::options()).map(|| {
+        warp::http::Response::builder()
+            .header("Allow", "GET, HEAD, OPTIONS")
+            .body("")
+    });
+    let attempts = Arc::new(AtomicUsize::new(0));
+    let flaky = warp::path("flaky").map(move || {
+        let status = if attempts.fetch_add(1, Ordering::SeqCst) < FLAKY_FAILURES {
+            503
+        } else {
+            200
+        };
+        warp::http::Response::builder().status(status).body("")
+    });
+    let route = r#static.or(download).or(options).or(flaky);
     spawn(async move {
         warp::serve(route).run(([127, 0, 0, 1], port)).await;
     });
@@ -126,6 +151,23 @@ async fn start_test_server(port: u16) {
         }))
         .serve_dir("tests/server/")
         .unwrap();
+    let attempts = Arc::new(AtomicUsize::new(0));
+    app.at("/flaky").get(move |_| {
+        let attempts = attempts.clone();
+        async move {
+            let status = if attempts.fetch_add(1, Ordering::SeqCst) < FLAKY_FAILURES {
+                503
+            } else {
+                200
+            };
+            Ok(tide::Response::new(status))
+        }
+    });
+    app.at("/options").options(|_| async {
+        let mut res = tide::Response::new(200);
+        res.insert_header("Allow", "GET, HEAD, OPTIONS");
+        Ok(res)
+    });
     spawn(async move {
         app.listen(format!("127.0.0.1:{}", port)).await.unwrap();
     });